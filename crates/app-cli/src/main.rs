@@ -0,0 +1,742 @@
+use {
+    anyhow::{anyhow, Context, Result},
+    app_client::constants::{
+        DEFAULT_VESTING_DURATION, ROLE_DISTRIBUTOR, ROLE_METADATA_MANAGER, ROLE_PARAMETER_MANAGER,
+        ROLE_PHASE_MANAGER,
+    },
+    app_client::{
+        accounts::{
+            deserialize_admin_registry, deserialize_app, deserialize_proposal, deserialize_user,
+            deserialize_vesting,
+        },
+        errors::decode_instruction_error,
+        instructions::*,
+        pdas::{
+            find_admin_registry_pda, find_program_pda, find_proposal_pda, find_user_pda,
+            find_vesting_pda,
+        },
+        AdminAction, AdminRegistry, App, CurveKind, InitTokenParams, Proposal, TradeDeadline, User,
+        Vesting, VestingSchedule,
+    },
+    clap::{Args, Parser, Subcommand, ValueEnum},
+    solana_client::rpc_client::RpcClient,
+    solana_sdk::{
+        commitment_config::CommitmentConfig,
+        instruction::Instruction,
+        pubkey::Pubkey,
+        signature::{read_keypair_file, Keypair, Signer},
+        transaction::{Transaction, TransactionError},
+    },
+};
+
+#[derive(Parser)]
+#[command(name = "app-cli", about = "Operate and use the app program")]
+struct Cli {
+    /// RPC URL, or one of the monikers localhost, devnet, testnet, mainnet-beta
+    #[arg(
+        short,
+        long,
+        global = true,
+        env = "APP_RPC_URL",
+        default_value = "localhost"
+    )]
+    url: String,
+    /// Keypair that signs and pays for transactions
+    #[arg(short, long, global = true, env = "APP_KEYPAIR")]
+    keypair: Option<String>,
+    /// Program to talk to, defaults to the deployed address
+    #[arg(long, global = true)]
+    program_id: Option<Pubkey>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create the program state, the mint and its metadata
+    Initialize(InitializeArgs),
+    /// End the ambassador phase and open trading to everyone
+    DisableInitialStage,
+    /// Accept admin rights granted to the signer
+    AcceptAdministrator,
+    /// Grant or revoke ambassador status
+    SetAmbassador { user: Pubkey, status: bool },
+    /// Rename the token and point its metadata at a new URI
+    UpdateMetadata {
+        name: String,
+        symbol: String,
+        uri: String,
+    },
+    /// Send locked tokens from the admin to a recipient
+    DistributeToken {
+        receipient: Pubkey,
+        amount_of_tokens: u128,
+        #[arg(long, default_value_t = 0)]
+        update_payout_by: i128,
+        #[command(flatten)]
+        schedule: VestingArgs,
+    },
+    /// Grant admin rights, which the user then accepts, or revoke them. Timelocked, so this
+    /// proposes the change, shorthand for `propose set-administrator`
+    SetAdministrator { user: Pubkey, status: bool },
+    /// Tokens a referrer must hold to earn referral bonuses. Timelocked, so this proposes the
+    /// change, shorthand for `propose set-staking-requirement`
+    SetStakingRequirement { amount_of_tokens: u128 },
+    /// Burn what a vesting grant still locks
+    RevokeVesting { receipient: Pubkey, index: u64 },
+    /// Shift the program's clock by some seconds, feature-dev deployments only
+    #[cfg(feature = "feature-dev")]
+    SetClockOffset {
+        #[arg(allow_hyphen_values = true)]
+        offset: i64,
+    },
+    /// Spend lamports on tokens
+    Buy {
+        lamports: u64,
+        #[arg(long, default_value_t = 0)]
+        min_tokens_out: u64,
+        #[arg(long)]
+        referred_by: Option<Pubkey>,
+        #[command(flatten)]
+        deadline: DeadlineArgs,
+    },
+    /// Sell tokens for lamports
+    Sell {
+        tokens: u64,
+        #[arg(long, default_value_t = 0)]
+        min_lamports_out: u64,
+        #[command(flatten)]
+        deadline: DeadlineArgs,
+    },
+    /// Turn dividends into tokens
+    Reinvest {
+        #[arg(long)]
+        min_tokens_out: Option<u64>,
+        #[command(flatten)]
+        deadline: DeadlineArgs,
+    },
+    /// Withdraw dividends and referral bonuses
+    Withdraw,
+    /// Sell every token and withdraw
+    Exit {
+        #[arg(long, default_value_t = 0)]
+        min_lamports_out: u64,
+        #[command(flatten)]
+        deadline: DeadlineArgs,
+    },
+    /// Send tokens to another wallet
+    Transfer {
+        to: Pubkey,
+        tokens: u64,
+        #[command(flatten)]
+        deadline: DeadlineArgs,
+    },
+    /// Open a proposal for an admin action, needed once the admin threshold is above 1 and for
+    /// the timelocked admin, role and staking requirement changes
+    Propose {
+        #[command(subcommand)]
+        action: ProposalAction,
+    },
+    /// Approve an active proposal
+    ApproveProposal { id: u64 },
+    /// Apply a queued proposal once its timelock has passed, anyone can
+    ExecuteProposal { id: u64 },
+    /// Cancel an active proposal
+    CancelProposal { id: u64 },
+    /// Print the decoded program state
+    ShowApp,
+    /// Print the decoded state and vesting grants of a user, the signer by default
+    ShowUser { user: Option<Pubkey> },
+    /// Print the decoded state of a proposal
+    ShowProposal { id: u64 },
+    /// Print the admins and the users granted admin that haven't accepted yet
+    ShowAdmins,
+}
+
+#[derive(Subcommand)]
+enum ProposalAction {
+    DisableInitialStage,
+    /// Grant admin rights, which the user then accepts, or revoke them
+    SetAdministrator {
+        user: Pubkey,
+        status: bool,
+    },
+    SetAmbassador {
+        user: Pubkey,
+        status: bool,
+    },
+    /// Replace the roles of a user, none to revoke them all
+    SetRoles {
+        user: Pubkey,
+        #[arg(value_enum)]
+        roles: Vec<RoleArg>,
+    },
+    /// Tokens a referrer must hold to earn referral bonuses
+    SetStakingRequirement {
+        amount_of_tokens: u128,
+    },
+    /// Approvals a proposal needs before it can be executed
+    SetAdminThreshold {
+        threshold: u8,
+    },
+    /// Share of every trade paid as dividends, as a divisor
+    SetDividendFee {
+        dividend_fee: u8,
+    },
+    /// Seconds timelocked proposals wait once approved
+    SetTimelockDelay {
+        delay: i64,
+    },
+    /// Rename the token and point its metadata at a new URI
+    UpdateMetadata {
+        name: String,
+        symbol: String,
+        uri: String,
+    },
+    /// Burn what a vesting grant still locks
+    RevokeVesting {
+        receipient: Pubkey,
+        index: u64,
+    },
+}
+
+impl From<ProposalAction> for AdminAction {
+    fn from(action: ProposalAction) -> Self {
+        match action {
+            ProposalAction::DisableInitialStage => AdminAction::DisableInitialStage,
+            ProposalAction::SetAdministrator { user, status } => {
+                AdminAction::SetAdministrator { user, status }
+            }
+            ProposalAction::SetAmbassador { user, status } => {
+                AdminAction::SetAmbassador { user, status }
+            }
+            ProposalAction::SetRoles { user, roles } => AdminAction::SetRoles {
+                user,
+                roles: role_bits(&roles),
+            },
+            ProposalAction::SetStakingRequirement { amount_of_tokens } => {
+                AdminAction::SetStakingRequirement { amount_of_tokens }
+            }
+            ProposalAction::SetAdminThreshold { threshold } => {
+                AdminAction::SetAdminThreshold { threshold }
+            }
+            ProposalAction::SetDividendFee { dividend_fee } => {
+                AdminAction::SetDividendFee { dividend_fee }
+            }
+            ProposalAction::SetTimelockDelay { delay } => AdminAction::SetTimelockDelay { delay },
+            ProposalAction::UpdateMetadata { name, symbol, uri } => {
+                AdminAction::UpdateMetadata { name, symbol, uri }
+            }
+            ProposalAction::RevokeVesting { receipient, index } => {
+                AdminAction::RevokeVesting { receipient, index }
+            }
+        }
+    }
+}
+
+#[derive(Args)]
+struct InitializeArgs {
+    #[arg(long, default_value = "app")]
+    name: String,
+    #[arg(long, default_value = "APP")]
+    symbol: String,
+    #[arg(long, default_value = "")]
+    uri: String,
+    #[arg(long, default_value_t = 9)]
+    decimals: u8,
+    #[arg(long, default_value_t = 10)]
+    dividend_fee: u8,
+    #[arg(long, default_value_t = 100_000)]
+    token_initial_price: u128,
+    #[arg(long, default_value_t = 100)]
+    token_incremental_price: u128,
+    #[arg(long, default_value_t = 1 << 32)]
+    magnitude: u64,
+    #[arg(long, default_value_t = 2_000_000_000_000)]
+    staking_requirement: u128,
+    #[arg(long, value_enum, default_value_t = CurveArg::Linear)]
+    curve: CurveArg,
+    #[arg(long, default_value_t = 1_000_000_000)]
+    ambassador_max_purchase: u128,
+    #[arg(long, default_value_t = 20_000_000_000)]
+    ambassador_quota: u128,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum CurveArg {
+    Linear,
+    Exponential,
+    ConstantProduct,
+}
+
+impl From<CurveArg> for CurveKind {
+    fn from(curve: CurveArg) -> Self {
+        match curve {
+            CurveArg::Linear => CurveKind::Linear,
+            CurveArg::Exponential => CurveKind::Exponential,
+            CurveArg::ConstantProduct => CurveKind::ConstantProduct,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum RoleArg {
+    PhaseManager,
+    Distributor,
+    ParameterManager,
+    MetadataManager,
+}
+
+impl RoleArg {
+    fn bit(self) -> u8 {
+        match self {
+            RoleArg::PhaseManager => ROLE_PHASE_MANAGER,
+            RoleArg::Distributor => ROLE_DISTRIBUTOR,
+            RoleArg::ParameterManager => ROLE_PARAMETER_MANAGER,
+            RoleArg::MetadataManager => ROLE_METADATA_MANAGER,
+        }
+    }
+}
+
+fn role_bits(roles: &[RoleArg]) -> u8 {
+    roles.iter().fold(0, |bits, role| bits | role.bit())
+}
+
+// Names of the roles set in `bits`
+fn role_names(bits: u8) -> String {
+    RoleArg::value_variants()
+        .iter()
+        .filter(|role| bits & role.bit() != 0)
+        .filter_map(|role| role.to_possible_value())
+        .map(|value| value.get_name().to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[derive(Args)]
+struct DeadlineArgs {
+    /// Reject the trade once this slot has passed
+    #[arg(long)]
+    valid_until_slot: Option<u64>,
+    /// Reject the trade once this unix timestamp has passed
+    #[arg(long)]
+    valid_until_ts: Option<i64>,
+}
+
+impl From<DeadlineArgs> for TradeDeadline {
+    fn from(deadline: DeadlineArgs) -> Self {
+        TradeDeadline {
+            valid_until_slot: deadline.valid_until_slot,
+            valid_until_ts: deadline.valid_until_ts,
+        }
+    }
+}
+
+#[derive(Args)]
+struct VestingArgs {
+    /// Unix timestamp the vesting starts at, defaults to the time of the grant
+    #[arg(long)]
+    start_time: Option<i64>,
+    /// Seconds after the start before anything unlocks
+    #[arg(long, default_value_t = 0)]
+    cliff: i64,
+    /// Seconds after the start when everything has unlocked
+    #[arg(long, default_value_t = DEFAULT_VESTING_DURATION)]
+    duration: i64,
+    /// Seconds between releases, 0 releases every second
+    #[arg(long, default_value_t = 0)]
+    release_interval: i64,
+}
+
+impl From<VestingArgs> for VestingSchedule {
+    fn from(schedule: VestingArgs) -> Self {
+        VestingSchedule {
+            start_time: schedule.start_time,
+            cliff: schedule.cliff,
+            duration: schedule.duration,
+            release_interval: schedule.release_interval,
+        }
+    }
+}
+
+fn rpc_url(url: &str) -> &str {
+    match url {
+        "localhost" | "l" => "http://127.0.0.1:8899",
+        "devnet" | "d" => "https://api.devnet.solana.com",
+        "testnet" | "t" => "https://api.testnet.solana.com",
+        "mainnet-beta" | "m" => "https://api.mainnet-beta.solana.com",
+        url => url,
+    }
+}
+
+fn load_keypair(path: Option<String>) -> Result<Keypair> {
+    let path = match path {
+        Some(path) => path,
+        None => format!(
+            "{}/.config/solana/id.json",
+            std::env::var("HOME").context("HOME is not set, pass --keypair")?
+        ),
+    };
+    read_keypair_file(&path).map_err(|e| anyhow!("failed to read keypair {path}: {e}"))
+}
+
+fn send(rpc: &RpcClient, payer: &Keypair, ix: Instruction) -> Result<()> {
+    let blockhash = rpc.get_latest_blockhash()?;
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer.pubkey()), &[payer], blockhash);
+
+    match rpc.send_and_confirm_transaction(&tx) {
+        Ok(signature) => {
+            println!("{signature}");
+            Ok(())
+        }
+        Err(err) => match err.get_transaction_error() {
+            Some(TransactionError::InstructionError(_, ref ix_err)) => {
+                match decode_instruction_error(ix_err) {
+                    Some(program_err) => Err(anyhow!("{program_err}")),
+                    None => Err(err.into()),
+                }
+            }
+            _ => Err(err.into()),
+        },
+    }
+}
+
+fn fetch_app(rpc: &RpcClient, program_id: &Pubkey) -> Result<App> {
+    let (pda, _) = find_program_pda(program_id);
+    let data = rpc
+        .get_account_data(&pda)
+        .with_context(|| format!("program state {pda} not found, is it initialized?"))?;
+    Ok(deserialize_app(&data)?)
+}
+
+fn fetch_user(rpc: &RpcClient, user: &Pubkey, program_id: &Pubkey) -> Result<User> {
+    let (pda, _) = find_user_pda(user, program_id);
+    let data = rpc
+        .get_account_data(&pda)
+        .with_context(|| format!("user account {pda} not found"))?;
+    Ok(deserialize_user(&data)?)
+}
+
+// A user's `vesting_count`, zero until they have an account
+fn fetch_vesting_count(rpc: &RpcClient, user: &Pubkey, program_id: &Pubkey) -> Result<u64> {
+    let (pda, _) = find_user_pda(user, program_id);
+    match rpc
+        .get_account_with_commitment(&pda, rpc.commitment())?
+        .value
+    {
+        Some(account) => Ok(deserialize_user(&account.data)?.vesting_count),
+        None => Ok(0),
+    }
+}
+
+// Proposes `action` under the next proposal id, which it prints
+fn propose(
+    rpc: &RpcClient,
+    proposer: &Pubkey,
+    action: AdminAction,
+    program_id: &Pubkey,
+) -> Result<(u64, Instruction)> {
+    let id = fetch_app(rpc, program_id)?.proposal_count;
+    println!("proposal {id}");
+    Ok((id, create_propose_ix(proposer, action, id, program_id)))
+}
+
+// Where a timelocked change proposed by its own subcommand goes from here
+fn print_next_steps(id: u64) {
+    println!("once it has enough approvals (approve-proposal {id}) and its timelock has passed,");
+    println!("apply it with execute-proposal {id}");
+}
+
+fn fetch_vesting(
+    rpc: &RpcClient,
+    user: &Pubkey,
+    index: u64,
+    program_id: &Pubkey,
+) -> Result<Vesting> {
+    let (pda, _) = find_vesting_pda(user, index, program_id);
+    let data = rpc
+        .get_account_data(&pda)
+        .with_context(|| format!("vesting grant {index} not found at {pda}"))?;
+    Ok(deserialize_vesting(&data)?)
+}
+
+fn fetch_proposal(rpc: &RpcClient, id: u64, program_id: &Pubkey) -> Result<Proposal> {
+    let (pda, _) = find_proposal_pda(id, program_id);
+    let data = rpc
+        .get_account_data(&pda)
+        .with_context(|| format!("proposal {id} not found at {pda}"))?;
+    Ok(deserialize_proposal(&data)?)
+}
+
+fn fetch_admin_registry(rpc: &RpcClient, program_id: &Pubkey) -> Result<AdminRegistry> {
+    let (pda, _) = find_admin_registry_pda(program_id);
+    let data = rpc
+        .get_account_data(&pda)
+        .with_context(|| format!("admin registry {pda} not found, is it initialized?"))?;
+    Ok(deserialize_admin_registry(&data)?)
+}
+
+fn print_app(app: &App) {
+    println!("name:                    {}", app.name);
+    println!("symbol:                  {}", app.symbol);
+    println!("decimals:                {}", app.decimals);
+    println!("dividend_fee:            {}", app.dividend_fee);
+    println!("curve:                   {:?}", app.curve);
+    println!("token_initial_price:     {}", app.token_initial_price);
+    println!("token_incremental_price: {}", app.token_incremental_price);
+    println!("contract_balance:        {}", app.contract_balance);
+    println!("token_supply:            {}", app.token_supply);
+    println!("magnitude:               {}", app.magnitude);
+    println!("staking_requirement:     {}", app.staking_requirement);
+    println!("profit_per_share:        {}", app.profit_per_share);
+    println!("is_initialized:          {}", app.is_initialized);
+    println!("is_initial_phase:        {}", app.is_initial_phase);
+    println!("event_seq:               {}", app.event_seq);
+    println!("admin_threshold:         {}", app.admin_threshold);
+    println!("proposal_count:          {}", app.proposal_count);
+    println!("timelock_delay:          {}", app.timelock_delay);
+    #[cfg(feature = "feature-dev")]
+    println!("clock_offset:            {}", app.clock_offset);
+    println!("ambassador_max_purchase: {}", app.ambassador_max_purchase);
+    println!("ambassador_quota:        {}", app.ambassador_quota);
+    println!("ambassador_purchased:    {}", app.ambassador_purchased);
+}
+
+fn print_user(user: &User) {
+    println!("authority:               {}", user.authority);
+    println!("balance:                 {}", user.balance);
+    println!("referred_balance:        {}", user.referred_balance);
+    println!("is_admin:                {}", user.is_admin);
+    println!("is_amb:                  {}", user.is_amb);
+    println!("referred_by:             {}", user.referred_by);
+    println!("payout:                  {}", user.payout);
+    println!("vesting_count:           {}", user.vesting_count);
+    println!("vesting_end_time:        {}", user.vesting_end_time);
+    println!("ambassador_purchased:    {}", user.ambassador_purchased);
+    println!("nonce:                   {}", user.nonce);
+    println!("roles:                   {}", role_names(user.roles));
+}
+
+fn print_vesting(vesting: &Vesting) {
+    println!(
+        "  grant {}: {} locked from {} to {}, cliff {}, release interval {}",
+        vesting.index,
+        vesting.total_locked,
+        vesting.start_time,
+        vesting.end_time,
+        vesting.cliff_time,
+        vesting.release_interval
+    );
+}
+
+fn show_user(rpc: &RpcClient, user: &Pubkey, program_id: &Pubkey) -> Result<()> {
+    let account = fetch_user(rpc, user, program_id)?;
+    print_user(&account);
+    for index in 0..account.vesting_count {
+        print_vesting(&fetch_vesting(rpc, user, index, program_id)?);
+    }
+    Ok(())
+}
+
+fn print_admin_registry(registry: &AdminRegistry) {
+    println!("admins:                  {}", registry.admin_count());
+    for admin in &registry.admins {
+        println!("  {admin}");
+    }
+    println!("pending:                 {}", registry.pending.len());
+    for pending in &registry.pending {
+        println!("  {} granted by {}", pending.user, pending.nominated_by);
+    }
+}
+
+fn print_proposal(proposal: &Proposal) {
+    println!("id:                      {}", proposal.id);
+    println!("proposer:                {}", proposal.proposer);
+    println!("action:                  {:?}", proposal.action);
+    println!("status:                  {:?}", proposal.status);
+    println!("created_at:              {}", proposal.created_at);
+    println!("eta:                     {}", proposal.eta);
+    println!("approvals:               {}", proposal.approvals.len());
+    for admin in &proposal.approvals {
+        println!("  {admin}");
+    }
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let rpc = RpcClient::new_with_commitment(rpc_url(&cli.url), CommitmentConfig::confirmed());
+    let program_id = cli.program_id.unwrap_or(app_client::ID);
+
+    // inspection commands don't need a wallet unless they default to it
+    match cli.command {
+        Command::ShowApp => {
+            print_app(&fetch_app(&rpc, &program_id)?);
+            return Ok(());
+        }
+        Command::ShowUser { user: Some(user) } => {
+            show_user(&rpc, &user, &program_id)?;
+            return Ok(());
+        }
+        Command::ShowProposal { id } => {
+            print_proposal(&fetch_proposal(&rpc, id, &program_id)?);
+            return Ok(());
+        }
+        Command::ShowAdmins => {
+            print_admin_registry(&fetch_admin_registry(&rpc, &program_id)?);
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    let payer = load_keypair(cli.keypair)?;
+    let signer = payer.pubkey();
+
+    let ix = match cli.command {
+        Command::Initialize(args) => create_initialize_ix(
+            &signer,
+            InitTokenParams {
+                name: args.name,
+                symbol: args.symbol,
+                uri: args.uri,
+                decimals: args.decimals,
+                dividend_fee: args.dividend_fee,
+                token_initial_price: args.token_initial_price,
+                token_incremental_price: args.token_incremental_price,
+                magnitude: args.magnitude,
+                staking_requirement: args.staking_requirement,
+                curve: args.curve.into(),
+                ambassador_max_purchase: args.ambassador_max_purchase,
+                ambassador_quota: args.ambassador_quota,
+            },
+            &program_id,
+        ),
+        Command::DisableInitialStage => create_disable_initial_stage_ix(&signer, &program_id),
+        Command::AcceptAdministrator => create_accept_administrator_ix(&signer, &program_id),
+        Command::SetAmbassador { user, status } => {
+            create_set_ambassador_ix(&signer, &user, status, &program_id)
+        }
+        Command::UpdateMetadata { name, symbol, uri } => {
+            create_update_metadata_account_ix(&signer, name, symbol, uri, &program_id)
+        }
+        Command::DistributeToken {
+            receipient,
+            amount_of_tokens,
+            update_payout_by,
+            schedule,
+        } => create_distribute_token_ix(
+            &signer,
+            &receipient,
+            amount_of_tokens,
+            update_payout_by,
+            schedule.into(),
+            fetch_vesting_count(&rpc, &receipient, &program_id)?,
+            &program_id,
+        ),
+        Command::SetAdministrator { user, status } => {
+            let (id, ix) = propose(
+                &rpc,
+                &signer,
+                AdminAction::SetAdministrator { user, status },
+                &program_id,
+            )?;
+            print_next_steps(id);
+            ix
+        }
+        Command::SetStakingRequirement { amount_of_tokens } => {
+            let (id, ix) = propose(
+                &rpc,
+                &signer,
+                AdminAction::SetStakingRequirement { amount_of_tokens },
+                &program_id,
+            )?;
+            print_next_steps(id);
+            ix
+        }
+        Command::RevokeVesting { receipient, index } => create_revoke_vesting_ix(
+            &signer,
+            &receipient,
+            index,
+            fetch_vesting_count(&rpc, &receipient, &program_id)?,
+            &program_id,
+        ),
+        #[cfg(feature = "feature-dev")]
+        Command::SetClockOffset { offset } => {
+            create_set_clock_offset_ix(&signer, offset, &program_id)
+        }
+        Command::Buy {
+            lamports,
+            min_tokens_out,
+            referred_by,
+            deadline,
+        } => create_buy_ix(
+            &signer,
+            lamports,
+            min_tokens_out,
+            referred_by,
+            deadline.into(),
+            &program_id,
+        ),
+        Command::Sell {
+            tokens,
+            min_lamports_out,
+            deadline,
+        } => create_sell_ix(
+            &signer,
+            tokens,
+            min_lamports_out,
+            deadline.into(),
+            fetch_vesting_count(&rpc, &signer, &program_id)?,
+            &program_id,
+        ),
+        Command::Reinvest {
+            min_tokens_out,
+            deadline,
+        } => create_reinvest_ix(&signer, min_tokens_out, deadline.into(), &program_id),
+        Command::Withdraw => create_withdraw_ix(&signer, &program_id),
+        Command::Exit {
+            min_lamports_out,
+            deadline,
+        } => create_exit_ix(
+            &signer,
+            min_lamports_out,
+            deadline.into(),
+            fetch_vesting_count(&rpc, &signer, &program_id)?,
+            &program_id,
+        ),
+        Command::Transfer {
+            to,
+            tokens,
+            deadline,
+        } => create_transfer_ix(
+            &signer,
+            &to,
+            tokens,
+            deadline.into(),
+            fetch_vesting_count(&rpc, &signer, &program_id)?,
+            &program_id,
+        ),
+        Command::Propose { action } => propose(&rpc, &signer, action.into(), &program_id)?.1,
+        Command::ApproveProposal { id } => create_approve_proposal_ix(&signer, id, &program_id),
+        Command::ExecuteProposal { id } => {
+            let proposal = fetch_proposal(&rpc, id, &program_id)?;
+            let vesting_count = match proposal.action {
+                AdminAction::RevokeVesting { receipient, .. } => {
+                    fetch_vesting_count(&rpc, &receipient, &program_id)?
+                }
+                _ => 0,
+            };
+            create_execute_proposal_ix(&signer, id, &proposal.action, vesting_count, &program_id)
+        }
+        Command::CancelProposal { id } => create_cancel_proposal_ix(&signer, id, &program_id),
+        Command::ShowUser { user: None } => {
+            show_user(&rpc, &signer, &program_id)?;
+            return Ok(());
+        }
+        Command::ShowApp
+        | Command::ShowUser { .. }
+        | Command::ShowProposal { .. }
+        | Command::ShowAdmins => unreachable!(),
+    };
+
+    send(&rpc, &payer, ix)
+}
@@ -0,0 +1,39 @@
+use {
+    anchor_lang::{AccountDeserialize, Result},
+    app::states::{AdminRegistry, App, Proposal, User, Vesting},
+};
+
+/**
+ * Decodes the raw data of the `App` account, discriminator included.
+ */
+pub fn deserialize_app(data: &[u8]) -> Result<App> {
+    App::try_deserialize(&mut &data[..])
+}
+
+/**
+ * Decodes the raw data of a `User` account, discriminator included.
+ */
+pub fn deserialize_user(data: &[u8]) -> Result<User> {
+    User::try_deserialize(&mut &data[..])
+}
+
+/**
+ * Decodes the raw data of a `Proposal` account, discriminator included.
+ */
+pub fn deserialize_proposal(data: &[u8]) -> Result<Proposal> {
+    Proposal::try_deserialize(&mut &data[..])
+}
+
+/**
+ * Decodes the raw data of a `Vesting` account, discriminator included.
+ */
+pub fn deserialize_vesting(data: &[u8]) -> Result<Vesting> {
+    Vesting::try_deserialize(&mut &data[..])
+}
+
+/**
+ * Decodes the raw data of the `AdminRegistry` account, discriminator included.
+ */
+pub fn deserialize_admin_registry(data: &[u8]) -> Result<AdminRegistry> {
+    AdminRegistry::try_deserialize(&mut &data[..])
+}
@@ -0,0 +1,119 @@
+use {anchor_lang::solana_program::instruction::InstructionError, app::errors::ProgramError};
+
+// Every program error, in declaration order
+const PROGRAM_ERRORS: &[ProgramError] = &[
+    ProgramError::NotABagHolder,
+    ProgramError::NoPofit,
+    ProgramError::NotAnAdmin,
+    ProgramError::IsInitialPhase,
+    ProgramError::IsPostInitialPhase,
+    ProgramError::SentLessToken,
+    ProgramError::InsufficientBalance,
+    ProgramError::AlreadyInitialized,
+    ProgramError::InvalidToAccount,
+    ProgramError::NotOwner,
+    ProgramError::SelfReferral,
+    ProgramError::SelfTransfer,
+    ProgramError::InvalidDividendFee,
+    ProgramError::InvalidTokenPrice,
+    ProgramError::InvalidMagnitude,
+    ProgramError::TokensBelowMinimum,
+    ProgramError::LamportsBelowMinimum,
+    ProgramError::DeadlineExceeded,
+    ProgramError::MathOverflow,
+    ProgramError::MathUnderflow,
+    ProgramError::DivisionByZero,
+    ProgramError::AmountTooLarge,
+    ProgramError::ProposalRequired,
+    ProgramError::InvalidThreshold,
+    ProgramError::ProposalNotActive,
+    ProgramError::AlreadyApproved,
+    ProgramError::ApprovalLimitReached,
+    ProgramError::NotEnoughApprovals,
+    ProgramError::InvalidTargetAccount,
+    ProgramError::TimelockRequired,
+    ProgramError::InvalidTimelockDelay,
+    ProgramError::ProposalNotQueued,
+    ProgramError::TimelockNotElapsed,
+    ProgramError::LastAdmin,
+    ProgramError::AdminCountBelowThreshold,
+    ProgramError::AlreadyAdmin,
+    ProgramError::AlreadyNominated,
+    ProgramError::NotNominated,
+    ProgramError::AdminRegistryFull,
+    ProgramError::MissingRole,
+    ProgramError::InvalidRoles,
+    ProgramError::InvalidVestingSchedule,
+    ProgramError::MissingVestingGrants,
+    ProgramError::VestingGrantLimit,
+    ProgramError::NothingToRevoke,
+    ProgramError::DevBuildOnly,
+    ProgramError::AmbassadorCapExceeded,
+    ProgramError::CurveSupplyTooLow,
+    ProgramError::InvalidAmbassadorQuota,
+    ProgramError::InvalidDecimals,
+    ProgramError::SupplyLimitExceeded,
+    ProgramError::MetadataTooLong,
+    ProgramError::WrongExecuteInstruction,
+    ProgramError::AmbassadorQuotaExceeded,
+];
+
+/**
+ * Maps a custom error code returned by the program back to its `ProgramError`.
+ * Codes that aren't ours (anchor's own constraint errors, other programs) give `None`.
+ */
+pub fn decode_error_code(code: u32) -> Option<ProgramError> {
+    PROGRAM_ERRORS
+        .iter()
+        .find(|error| u32::from(**error) == code)
+        .copied()
+}
+
+pub fn decode_instruction_error(error: &InstructionError) -> Option<ProgramError> {
+    match error {
+        InstructionError::Custom(code) => decode_error_code(*code),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Anchor numbers the variants from 6000 in declaration order
+    const FIRST_CODE: u32 = 6000;
+
+    // every variant carries a message, so counting them counts the variants
+    fn declared_errors() -> usize {
+        include_str!("../../../programs/app/src/errors.rs")
+            .matches("#[msg(")
+            .count()
+    }
+
+    #[test]
+    fn table_lists_every_program_error() {
+        assert_eq!(PROGRAM_ERRORS.len(), declared_errors());
+    }
+
+    #[test]
+    fn every_code_round_trips() {
+        for (offset, error) in PROGRAM_ERRORS.iter().enumerate() {
+            let code = FIRST_CODE + offset as u32;
+            assert_eq!(u32::from(*error), code);
+            assert_eq!(decode_error_code(code).map(u32::from), Some(code));
+            assert_eq!(
+                decode_instruction_error(&InstructionError::Custom(code)).map(u32::from),
+                Some(code)
+            );
+        }
+    }
+
+    #[test]
+    fn foreign_codes_decode_to_none() {
+        let past_last = FIRST_CODE + PROGRAM_ERRORS.len() as u32;
+        assert!(decode_error_code(past_last).is_none());
+        // anchor's own constraint errors
+        assert!(decode_error_code(2006).is_none());
+        assert!(decode_instruction_error(&InstructionError::InvalidAccountData).is_none());
+    }
+}
@@ -0,0 +1,92 @@
+use {
+    crate::{
+        instructions::{build, with_vesting_grants},
+        pdas::*,
+    },
+    anchor_lang::{
+        prelude::Pubkey, solana_program::instruction::Instruction, AnchorDeserialize, Result,
+    },
+};
+
+/**
+ * The read-only instructions hand their answer back through the transaction return data,
+ * simulate them and decode the result with `decode_return_value`.
+ */
+pub fn decode_return_value(return_data: &[u8]) -> Result<u128> {
+    Ok(u128::try_from_slice(return_data)?)
+}
+
+fn program_read_only(program_id: &Pubkey) -> app::accounts::ProgramReadOnly {
+    let (program_data, _) = find_program_pda(program_id);
+
+    app::accounts::ProgramReadOnly { program_data }
+}
+
+pub fn get_my_dividends_ix(user: &Pubkey, including_ref: bool, program_id: &Pubkey) -> Instruction {
+    let (program_data, _) = find_program_pda(program_id);
+    let (user_data, _) = find_user_pda(user, program_id);
+
+    build(
+        program_id,
+        app::accounts::ReadOnly {
+            user_data,
+            program_data,
+        },
+        app::instruction::MyDividends { including_ref },
+    )
+}
+
+/**
+ * `at` defaults to the current time, `vesting_count` is `User::vesting_count`.
+ */
+pub fn get_vested_balance_ix(
+    user: &Pubkey,
+    at: Option<i64>,
+    vesting_count: u64,
+    program_id: &Pubkey,
+) -> Instruction {
+    let (program_data, _) = find_program_pda(program_id);
+    let (user_data, _) = find_user_pda(user, program_id);
+
+    let ix = build(
+        program_id,
+        app::accounts::ReadOnly {
+            user_data,
+            program_data,
+        },
+        app::instruction::VestedBalance { at },
+    );
+    with_vesting_grants(ix, user, vesting_count, program_id)
+}
+
+pub fn get_sell_price_ix(program_id: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        program_read_only(program_id),
+        app::instruction::SellPrice {},
+    )
+}
+
+pub fn get_buy_price_ix(program_id: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        program_read_only(program_id),
+        app::instruction::BuyPrice {},
+    )
+}
+
+pub fn get_calculate_lamports_received_ix(tokens: u128, program_id: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        program_read_only(program_id),
+        app::instruction::CalculateLamportsReceived { tokens },
+    )
+}
+
+pub fn get_calculate_tokens_received_ix(lamports: u128, program_id: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        program_read_only(program_id),
+        app::instruction::CalculateTokensReceived { lamports },
+    )
+}
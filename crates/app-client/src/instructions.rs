@@ -0,0 +1,755 @@
+use {
+    crate::pdas::*,
+    anchor_lang::{
+        prelude::Pubkey,
+        solana_program::{
+            instruction::{AccountMeta, Instruction},
+            system_program, sysvar,
+        },
+        InstructionData, ToAccountMetas,
+    },
+    anchor_spl::{associated_token, token_2022},
+    app::{
+        instructions::{InitTokenParams, TradeDeadline, VestingSchedule},
+        states::AdminAction,
+    },
+};
+
+pub(crate) fn build(
+    program_id: &Pubkey,
+    accounts: impl ToAccountMetas,
+    data: impl InstructionData,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+// Accounts shared by every instruction that moves the user's tokens
+fn trade_accounts(user: &Pubkey, program_id: &Pubkey) -> (Pubkey, Pubkey, Pubkey, Pubkey) {
+    let (program_data, _) = find_program_pda(program_id);
+    let (user_data, _) = find_user_pda(user, program_id);
+    let (mint, _) = find_mint_pda(program_id);
+    let user_ata = find_user_ata(&mint, user);
+
+    (program_data, user_data, mint, user_ata)
+}
+
+/**
+ * Appends the user's vesting grants, which instructions spending tokens take as remaining
+ * accounts until the last grant has ended. `vesting_count` is `User::vesting_count`.
+ */
+pub(crate) fn with_vesting_grants(
+    mut ix: Instruction,
+    user: &Pubkey,
+    vesting_count: u64,
+    program_id: &Pubkey,
+) -> Instruction {
+    ix.accounts.extend((0..vesting_count).map(|index| {
+        AccountMeta::new_readonly(find_vesting_pda(user, index, program_id).0, false)
+    }));
+    ix
+}
+
+pub fn create_initialize_ix(
+    admin: &Pubkey,
+    params: InitTokenParams,
+    program_id: &Pubkey,
+) -> Instruction {
+    let (program_data, _) = find_program_pda(program_id);
+    let (admin_data, _) = find_user_pda(admin, program_id);
+    let (mint, _) = find_mint_pda(program_id);
+    let (metadata, _) = find_metadata_pda(&mint);
+    let (admin_registry, _) = find_admin_registry_pda(program_id);
+
+    build(
+        program_id,
+        app::accounts::Initialize {
+            admin: *admin,
+            admin_data,
+            metadata,
+            mint,
+            program_data,
+            admin_registry,
+            rent: sysvar::rent::ID,
+            sysvar_instructions: sysvar::instructions::ID,
+            system_program: system_program::ID,
+            token_program: token_2022::ID,
+            token_metadata_program: mpl_token_metadata::ID,
+        },
+        app::instruction::Initialize { metadata: params },
+    )
+}
+
+pub fn create_buy_ix(
+    user: &Pubkey,
+    lamports_to_send: u64,
+    min_tokens_out: u64,
+    referred_by: Option<Pubkey>,
+    deadline: TradeDeadline,
+    program_id: &Pubkey,
+) -> Instruction {
+    let (program_data, user_data, mint, user_ata) = trade_accounts(user, program_id);
+    // referring yourself is rejected on-chain, so drop it like the TS SDK does
+    let referred_by = referred_by.filter(|referral| referral != user);
+    let referred_by_data = referred_by.map(|referral| find_user_pda(&referral, program_id).0);
+
+    build(
+        program_id,
+        app::accounts::Buy {
+            mint,
+            user: *user,
+            user_data,
+            user_ata,
+            referred_by_data,
+            program_data,
+            system_program: system_program::ID,
+            token_program: token_2022::ID,
+            associated_token_program: associated_token::ID,
+            event_authority: find_event_authority_pda(program_id).0,
+            program: *program_id,
+        },
+        app::instruction::Buy {
+            lamports_to_send,
+            referred_by,
+            min_tokens_out,
+            deadline,
+        },
+    )
+}
+
+pub fn create_reinvest_ix(
+    user: &Pubkey,
+    min_tokens_out: Option<u64>,
+    deadline: TradeDeadline,
+    program_id: &Pubkey,
+) -> Instruction {
+    let (program_data, user_data, mint, user_ata) = trade_accounts(user, program_id);
+
+    build(
+        program_id,
+        app::accounts::Reinvest {
+            mint,
+            user: *user,
+            user_data,
+            user_ata,
+            program_data,
+            system_program: system_program::ID,
+            token_program: token_2022::ID,
+            associated_token_program: associated_token::ID,
+            event_authority: find_event_authority_pda(program_id).0,
+            program: *program_id,
+        },
+        app::instruction::Reinvest {
+            min_tokens_out,
+            deadline,
+        },
+    )
+}
+
+pub fn create_exit_ix(
+    user: &Pubkey,
+    min_lamports_out: u64,
+    deadline: TradeDeadline,
+    vesting_count: u64,
+    program_id: &Pubkey,
+) -> Instruction {
+    let (program_data, user_data, mint, user_ata) = trade_accounts(user, program_id);
+
+    let ix = build(
+        program_id,
+        app::accounts::Exit {
+            mint,
+            user: *user,
+            user_data,
+            user_ata,
+            program_data,
+            system_program: system_program::ID,
+            token_program: token_2022::ID,
+            associated_token_program: associated_token::ID,
+            event_authority: find_event_authority_pda(program_id).0,
+            program: *program_id,
+        },
+        app::instruction::Exit {
+            min_lamports_out,
+            deadline,
+        },
+    );
+    with_vesting_grants(ix, user, vesting_count, program_id)
+}
+
+pub fn create_transfer_ix(
+    user: &Pubkey,
+    to: &Pubkey,
+    amount: u64,
+    deadline: TradeDeadline,
+    vesting_count: u64,
+    program_id: &Pubkey,
+) -> Instruction {
+    let (program_data, user_data, mint, user_ata) = trade_accounts(user, program_id);
+    let (to_data, _) = find_user_pda(to, program_id);
+    let to_ata = find_user_ata(&mint, to);
+
+    let ix = build(
+        program_id,
+        app::accounts::Transfer {
+            mint,
+            user: *user,
+            user_data,
+            user_ata,
+            to_data,
+            to_info: *to,
+            to_ata,
+            program_data,
+            system_program: system_program::ID,
+            token_program: token_2022::ID,
+            associated_token_program: associated_token::ID,
+            event_authority: find_event_authority_pda(program_id).0,
+            program: *program_id,
+        },
+        app::instruction::Transfer {
+            to: *to,
+            lamports_to_send: amount,
+            deadline,
+        },
+    );
+    with_vesting_grants(ix, user, vesting_count, program_id)
+}
+
+pub fn create_withdraw_ix(user: &Pubkey, program_id: &Pubkey) -> Instruction {
+    let (program_data, user_data, mint, user_ata) = trade_accounts(user, program_id);
+
+    build(
+        program_id,
+        app::accounts::Withdraw {
+            mint,
+            user: *user,
+            user_data,
+            user_ata,
+            program_data,
+            system_program: system_program::ID,
+            token_program: token_2022::ID,
+            associated_token_program: associated_token::ID,
+            event_authority: find_event_authority_pda(program_id).0,
+            program: *program_id,
+        },
+        app::instruction::Withdraw {},
+    )
+}
+
+pub fn create_sell_ix(
+    user: &Pubkey,
+    amount: u64,
+    min_lamports_out: u64,
+    deadline: TradeDeadline,
+    vesting_count: u64,
+    program_id: &Pubkey,
+) -> Instruction {
+    let (program_data, user_data, mint, user_ata) = trade_accounts(user, program_id);
+
+    let ix = build(
+        program_id,
+        app::accounts::Sell {
+            mint,
+            user: *user,
+            user_data,
+            user_ata,
+            program_data,
+            system_program: system_program::ID,
+            token_program: token_2022::ID,
+            associated_token_program: associated_token::ID,
+            event_authority: find_event_authority_pda(program_id).0,
+            program: *program_id,
+        },
+        app::instruction::Sell {
+            lamports_to_send: amount,
+            min_lamports_out,
+            deadline,
+        },
+    );
+    with_vesting_grants(ix, user, vesting_count, program_id)
+}
+
+// Admin functions
+pub fn create_disable_initial_stage_ix(admin: &Pubkey, program_id: &Pubkey) -> Instruction {
+    let (program_data, _) = find_program_pda(program_id);
+    let (user_data, _) = find_user_pda(admin, program_id);
+
+    build(
+        program_id,
+        app::accounts::Admin {
+            user: *admin,
+            user_data,
+            program_data,
+            system_program: system_program::ID,
+            event_authority: find_event_authority_pda(program_id).0,
+            program: *program_id,
+        },
+        app::instruction::DisableInitialStage {},
+    )
+}
+
+/**
+ * `vesting_index` must be the recipient's `User::vesting_count`, zero before they have an account.
+ */
+pub fn create_distribute_token_ix(
+    admin: &Pubkey,
+    receipient: &Pubkey,
+    amount_of_tokens: u128,
+    update_payout_by: i128,
+    schedule: VestingSchedule,
+    vesting_index: u64,
+    program_id: &Pubkey,
+) -> Instruction {
+    let (program_data, from_data, mint, from_ata) = trade_accounts(admin, program_id);
+    let (receipient_data, _) = find_user_pda(receipient, program_id);
+    let receipient_ata = find_user_ata(&mint, receipient);
+    let (vesting, _) = find_vesting_pda(receipient, vesting_index, program_id);
+
+    build(
+        program_id,
+        app::accounts::DistributeToken {
+            mint,
+            user: *admin,
+            from_data,
+            from_ata,
+            receipient_info: *receipient,
+            receipient_data,
+            receipient_ata,
+            vesting,
+            program_data,
+            system_program: system_program::ID,
+            token_program: token_2022::ID,
+            associated_token_program: associated_token::ID,
+            event_authority: find_event_authority_pda(program_id).0,
+            program: *program_id,
+        },
+        app::instruction::DistributeToken {
+            amount_of_tokens,
+            update_payout_by,
+            receipient: *receipient,
+            schedule,
+        },
+    )
+}
+
+fn admin_setter_accounts(
+    admin: &Pubkey,
+    user: &Pubkey,
+    program_id: &Pubkey,
+) -> app::accounts::AdminSetter {
+    let (program_data, _) = find_program_pda(program_id);
+    let (admin_data, _) = find_user_pda(admin, program_id);
+    let (user_data, _) = find_user_pda(user, program_id);
+
+    app::accounts::AdminSetter {
+        admin: *admin,
+        admin_data,
+        user_data,
+        program_data,
+        system_program: system_program::ID,
+        event_authority: find_event_authority_pda(program_id).0,
+        program: *program_id,
+    }
+}
+
+/**
+ * Granting admin nominates `user`, who then has to send `create_accept_administrator_ix`.
+ * The program only accepts it while the timelock delay is zero.
+ */
+#[deprecated(note = "timelocked by default, propose `AdminAction::SetAdministrator` instead")]
+pub fn create_set_administrator_ix(
+    admin: &Pubkey,
+    user: &Pubkey,
+    status: bool,
+    program_id: &Pubkey,
+) -> Instruction {
+    let (program_data, _) = find_program_pda(program_id);
+    let (admin_data, _) = find_user_pda(admin, program_id);
+    let (user_data, _) = find_user_pda(user, program_id);
+
+    build(
+        program_id,
+        app::accounts::AdministratorSetter {
+            admin: *admin,
+            admin_data,
+            user_data,
+            program_data,
+            admin_registry: find_admin_registry_pda(program_id).0,
+            system_program: system_program::ID,
+            event_authority: find_event_authority_pda(program_id).0,
+            program: *program_id,
+        },
+        app::instruction::SetAdministrator {
+            user: *user,
+            status,
+        },
+    )
+}
+
+pub fn create_accept_administrator_ix(user: &Pubkey, program_id: &Pubkey) -> Instruction {
+    let (program_data, _) = find_program_pda(program_id);
+    let (user_data, _) = find_user_pda(user, program_id);
+
+    build(
+        program_id,
+        app::accounts::AcceptAdministrator {
+            user: *user,
+            user_data,
+            program_data,
+            admin_registry: find_admin_registry_pda(program_id).0,
+            event_authority: find_event_authority_pda(program_id).0,
+            program: *program_id,
+        },
+        app::instruction::AcceptAdministrator {},
+    )
+}
+
+pub fn create_set_ambassador_ix(
+    admin: &Pubkey,
+    user: &Pubkey,
+    status: bool,
+    program_id: &Pubkey,
+) -> Instruction {
+    build(
+        program_id,
+        admin_setter_accounts(admin, user, program_id),
+        app::instruction::SetAmbassador {
+            user: *user,
+            status,
+        },
+    )
+}
+
+pub fn create_revoke_vesting_ix(
+    admin: &Pubkey,
+    receipient: &Pubkey,
+    index: u64,
+    vesting_count: u64,
+    program_id: &Pubkey,
+) -> Instruction {
+    let (program_data, _) = find_program_pda(program_id);
+    let (admin_data, _) = find_user_pda(admin, program_id);
+    let (receipient_data, _) = find_user_pda(receipient, program_id);
+    let (mint, _) = find_mint_pda(program_id);
+    let receipient_ata = find_user_ata(&mint, receipient);
+    let (vesting, _) = find_vesting_pda(receipient, index, program_id);
+
+    let ix = build(
+        program_id,
+        app::accounts::RevokeVesting {
+            mint,
+            admin: *admin,
+            admin_data,
+            vesting,
+            receipient_info: *receipient,
+            receipient_data,
+            receipient_ata,
+            program_data,
+            token_program: token_2022::ID,
+            event_authority: find_event_authority_pda(program_id).0,
+            program: *program_id,
+        },
+        app::instruction::RevokeVesting {},
+    );
+    with_vesting_grants(ix, receipient, vesting_count, program_id)
+}
+
+/**
+ * Renames the token, the program state grows if the new name and symbol need more room.
+ */
+pub fn create_update_metadata_account_ix(
+    admin: &Pubkey,
+    name: String,
+    symbol: String,
+    uri: String,
+    program_id: &Pubkey,
+) -> Instruction {
+    let (program_data, _) = find_program_pda(program_id);
+    let (admin_data, _) = find_user_pda(admin, program_id);
+    let (mint, _) = find_mint_pda(program_id);
+    let (metadata, _) = find_metadata_pda(&mint);
+
+    build(
+        program_id,
+        app::accounts::UpdateMetadata {
+            admin: *admin,
+            admin_data,
+            program_data,
+            mint,
+            metadata,
+            system_program: system_program::ID,
+            sysvar_instructions: sysvar::instructions::ID,
+            token_metadata_program: mpl_token_metadata::ID,
+            event_authority: find_event_authority_pda(program_id).0,
+            program: *program_id,
+        },
+        app::instruction::UpdateMetadataAccount { name, symbol, uri },
+    )
+}
+
+/**
+ * `roles` replaces the user's roles, a combination of the `ROLE_*` constants.
+ * The program only accepts it while the timelock delay is zero.
+ */
+#[deprecated(note = "timelocked by default, propose `AdminAction::SetRoles` instead")]
+pub fn create_set_roles_ix(
+    admin: &Pubkey,
+    user: &Pubkey,
+    roles: u8,
+    program_id: &Pubkey,
+) -> Instruction {
+    build(
+        program_id,
+        admin_setter_accounts(admin, user, program_id),
+        app::instruction::SetRoles { user: *user, roles },
+    )
+}
+
+/**
+ * The program only accepts it while the timelock delay is zero.
+ */
+#[deprecated(note = "timelocked by default, propose `AdminAction::SetStakingRequirement` instead")]
+pub fn create_set_staking_requirement_ix(
+    admin: &Pubkey,
+    amount_of_tokens: u128,
+    program_id: &Pubkey,
+) -> Instruction {
+    let (program_data, _) = find_program_pda(program_id);
+    let (user_data, _) = find_user_pda(admin, program_id);
+
+    build(
+        program_id,
+        app::accounts::Admin {
+            user: *admin,
+            user_data,
+            program_data,
+            system_program: system_program::ID,
+            event_authority: find_event_authority_pda(program_id).0,
+            program: *program_id,
+        },
+        app::instruction::SetStakingRequirement { amount_of_tokens },
+    )
+}
+
+/**
+ * Only programs built with `feature-dev` accept it, production builds fail with `DevBuildOnly`.
+ */
+#[cfg(feature = "feature-dev")]
+pub fn create_set_clock_offset_ix(admin: &Pubkey, offset: i64, program_id: &Pubkey) -> Instruction {
+    let (program_data, _) = find_program_pda(program_id);
+    let (user_data, _) = find_user_pda(admin, program_id);
+
+    build(
+        program_id,
+        app::accounts::Admin {
+            user: *admin,
+            user_data,
+            program_data,
+            system_program: system_program::ID,
+            event_authority: find_event_authority_pda(program_id).0,
+            program: *program_id,
+        },
+        app::dev::instruction::SetClockOffset { offset },
+    )
+}
+
+// Multisig proposals
+
+/**
+ * `proposal_id` must be the current `App::proposal_count`, the id the new proposal gets.
+ */
+pub fn create_propose_ix(
+    admin: &Pubkey,
+    action: AdminAction,
+    proposal_id: u64,
+    program_id: &Pubkey,
+) -> Instruction {
+    let (program_data, _) = find_program_pda(program_id);
+    let (admin_data, _) = find_user_pda(admin, program_id);
+    let (proposal, _) = find_proposal_pda(proposal_id, program_id);
+
+    build(
+        program_id,
+        app::accounts::Propose {
+            admin: *admin,
+            admin_data,
+            program_data,
+            proposal,
+            admin_registry: find_admin_registry_pda(program_id).0,
+            system_program: system_program::ID,
+            event_authority: find_event_authority_pda(program_id).0,
+            program: *program_id,
+        },
+        app::instruction::Propose { action },
+    )
+}
+
+fn proposal_vote_accounts(
+    admin: &Pubkey,
+    proposal_id: u64,
+    program_id: &Pubkey,
+) -> app::accounts::ProposalVote {
+    let (program_data, _) = find_program_pda(program_id);
+    let (admin_data, _) = find_user_pda(admin, program_id);
+    let (proposal, _) = find_proposal_pda(proposal_id, program_id);
+
+    app::accounts::ProposalVote {
+        admin: *admin,
+        admin_data,
+        program_data,
+        proposal,
+        event_authority: find_event_authority_pda(program_id).0,
+        program: *program_id,
+    }
+}
+
+pub fn create_approve_proposal_ix(
+    admin: &Pubkey,
+    proposal_id: u64,
+    program_id: &Pubkey,
+) -> Instruction {
+    build(
+        program_id,
+        proposal_vote_accounts(admin, proposal_id, program_id),
+        app::instruction::ApproveProposal {},
+    )
+}
+
+pub fn create_cancel_proposal_ix(
+    admin: &Pubkey,
+    proposal_id: u64,
+    program_id: &Pubkey,
+) -> Instruction {
+    build(
+        program_id,
+        proposal_vote_accounts(admin, proposal_id, program_id),
+        app::instruction::CancelProposal {},
+    )
+}
+
+/**
+ * `action` is the action of the proposal, it tells which instruction and accounts the program
+ * needs. `vesting_count` is the `User::vesting_count` of a revocation's recipient, the other
+ * actions ignore it. Anyone can execute a queued proposal once its timelock has passed, paying
+ * for the user account when the target doesn't have one yet.
+ */
+pub fn create_execute_proposal_ix(
+    executor: &Pubkey,
+    proposal_id: u64,
+    action: &AdminAction,
+    vesting_count: u64,
+    program_id: &Pubkey,
+) -> Instruction {
+    match action {
+        AdminAction::UpdateMetadata { .. } => {
+            return create_execute_metadata_proposal_ix(executor, proposal_id, program_id)
+        }
+        AdminAction::RevokeVesting { receipient, index } => {
+            return create_execute_revocation_proposal_ix(
+                executor,
+                proposal_id,
+                receipient,
+                *index,
+                vesting_count,
+                program_id,
+            )
+        }
+        _ => {}
+    }
+
+    let (program_data, _) = find_program_pda(program_id);
+    let (proposal, _) = find_proposal_pda(proposal_id, program_id);
+    let target_data = action
+        .target()
+        .map(|user| find_user_pda(&user, program_id).0);
+
+    build(
+        program_id,
+        app::accounts::ExecuteProposal {
+            executor: *executor,
+            program_data,
+            proposal,
+            admin_registry: find_admin_registry_pda(program_id).0,
+            target_data,
+            system_program: system_program::ID,
+            event_authority: find_event_authority_pda(program_id).0,
+            program: *program_id,
+        },
+        app::instruction::ExecuteProposal {},
+    )
+}
+
+// The executor pays when the program state grows for the proposed name and symbol
+fn create_execute_metadata_proposal_ix(
+    executor: &Pubkey,
+    proposal_id: u64,
+    program_id: &Pubkey,
+) -> Instruction {
+    let (mint, _) = find_mint_pda(program_id);
+    let (metadata, _) = find_metadata_pda(&mint);
+
+    build(
+        program_id,
+        app::accounts::ExecuteMetadataProposal {
+            executor: *executor,
+            proposal: find_proposal_pda(proposal_id, program_id).0,
+            admin_registry: find_admin_registry_pda(program_id).0,
+            program_data: find_program_pda(program_id).0,
+            mint,
+            metadata,
+            system_program: system_program::ID,
+            sysvar_instructions: sysvar::instructions::ID,
+            token_metadata_program: mpl_token_metadata::ID,
+            event_authority: find_event_authority_pda(program_id).0,
+            program: *program_id,
+        },
+        app::instruction::ExecuteMetadataProposal {},
+    )
+}
+
+fn create_execute_revocation_proposal_ix(
+    executor: &Pubkey,
+    proposal_id: u64,
+    receipient: &Pubkey,
+    index: u64,
+    vesting_count: u64,
+    program_id: &Pubkey,
+) -> Instruction {
+    let (mint, _) = find_mint_pda(program_id);
+
+    let ix = build(
+        program_id,
+        app::accounts::ExecuteRevocationProposal {
+            executor: *executor,
+            proposal: find_proposal_pda(proposal_id, program_id).0,
+            admin_registry: find_admin_registry_pda(program_id).0,
+            mint,
+            vesting: find_vesting_pda(receipient, index, program_id).0,
+            receipient_info: *receipient,
+            receipient_data: find_user_pda(receipient, program_id).0,
+            receipient_ata: find_user_ata(&mint, receipient),
+            program_data: find_program_pda(program_id).0,
+            token_program: token_2022::ID,
+            event_authority: find_event_authority_pda(program_id).0,
+            program: *program_id,
+        },
+        app::instruction::ExecuteRevocationProposal {},
+    );
+    with_vesting_grants(ix, receipient, vesting_count, program_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use {anchor_lang::solana_program::hash::hash, app::constants::SET_CLOCK_OFFSET_DISCRIMINATOR};
+
+    // The program dispatches `set_clock_offset` by hand, it must keep anchor's discriminator
+    #[test]
+    fn set_clock_offset_uses_the_anchor_discriminator() {
+        let digest = hash(b"global:set_clock_offset");
+
+        assert_eq!(SET_CLOCK_OFFSET_DISCRIMINATOR, digest.to_bytes()[..8]);
+    }
+}
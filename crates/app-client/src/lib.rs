@@ -0,0 +1,15 @@
+pub mod accounts;
+pub mod errors;
+pub mod getters;
+pub mod instructions;
+pub mod pdas;
+
+pub use app::{
+    constants,
+    curves::CurveKind,
+    instructions::{InitTokenParams, TradeDeadline, VestingSchedule},
+    states::{
+        AdminAction, AdminRegistry, App, PendingAdmin, Proposal, ProposalStatus, User, Vesting,
+    },
+    ID,
+};
@@ -0,0 +1,98 @@
+use {
+    anchor_lang::prelude::Pubkey,
+    anchor_spl::{associated_token::get_associated_token_address_with_program_id, token_2022},
+    app::constants::{
+        ADMIN_REGISTRY_SEED, MINT_SEED, PROGRAM_SEED, PROPOSAL_SEED, USER_SEED, VESTING_SEED,
+    },
+};
+
+pub fn find_program_pda(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PROGRAM_SEED], program_id)
+}
+
+pub fn find_user_pda(user: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[USER_SEED, user.as_ref()], program_id)
+}
+
+pub fn find_mint_pda(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MINT_SEED], program_id)
+}
+
+pub fn find_admin_registry_pda(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ADMIN_REGISTRY_SEED], program_id)
+}
+
+pub fn find_proposal_pda(id: u64, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PROPOSAL_SEED, &id.to_le_bytes()], program_id)
+}
+
+// Vesting grant number `index` of `receipient`
+pub fn find_vesting_pda(receipient: &Pubkey, index: u64, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[VESTING_SEED, receipient.as_ref(), &index.to_le_bytes()],
+        program_id,
+    )
+}
+
+pub fn find_metadata_pda(mint: &Pubkey) -> (Pubkey, u8) {
+    mpl_token_metadata::accounts::Metadata::find_pda(mint)
+}
+
+// Token-2022 associated token account of `user` for `mint`
+pub fn find_user_ata(mint: &Pubkey, user: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(user, mint, &token_2022::ID)
+}
+
+// Signer of the self-CPI that carries the program's events
+pub fn find_event_authority_pda(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"__event_authority"], program_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::ID, std::str::FromStr};
+
+    fn key(address: &str) -> Pubkey {
+        Pubkey::from_str(address).unwrap()
+    }
+
+    // Addresses the deployed program uses, a changed seed moves them
+    #[test]
+    fn pdas_match_the_deployed_addresses() {
+        let user = key("9xQeWvG816bUx9EPjHmaT23yvVM2ZWbrrpZb9PusVFin");
+        let (mint, _) = find_mint_pda(&ID);
+
+        assert_eq!(
+            find_program_pda(&ID),
+            (key("d7CUM9CzwsWEk6BKuDkGTHhsAXiaYrSsaFHxmFY25My"), 254)
+        );
+        assert_eq!(
+            find_mint_pda(&ID),
+            (key("Gdp8dnhtxHh2vonnxbyxHDdonjEqtKKeowxHXuZjb63s"), 255)
+        );
+        assert_eq!(
+            find_admin_registry_pda(&ID),
+            (key("5UCYMuYt1cqEBH8XpToXmBFq5WSDAp6zrvkhY9HL9qL3"), 251)
+        );
+        assert_eq!(
+            find_user_pda(&user, &ID),
+            (key("Fj9UGqz8UHYX6qR8oCPbSaxb9m9UGwet6rFZjGzkhLZi"), 255)
+        );
+        assert_eq!(
+            find_proposal_pda(3, &ID),
+            (key("Ah4wTqwET7MHM1izXWVdAFuHJECqs6bhhS4Xo4S2JfWh"), 255)
+        );
+        assert_eq!(
+            find_vesting_pda(&user, 2, &ID),
+            (key("76wtXvPZ76CmgxkhjHvo3FPZtr66uNSm1tW4B5y22iXB"), 249)
+        );
+        assert_eq!(
+            find_event_authority_pda(&ID),
+            (key("D6oRVUo8T6AyN5mWQLetRdmWFQcFH5RFneLDZ5JSkNnu"), 255)
+        );
+        assert_eq!(
+            find_user_ata(&mint, &user),
+            key("A4D5HfHpm1tbRchb3wq2YCDXmfb2q5ukHSKFCsNzWcM5")
+        );
+    }
+}
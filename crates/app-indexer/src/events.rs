@@ -0,0 +1,140 @@
+use {
+    anchor_lang::{event::EVENT_IX_TAG_LE, AnchorDeserialize, Discriminator},
+    app::events::*,
+};
+
+/**
+ * Every event the program emits. They come out of the self-CPI `emit_cpi!` performs, whose
+ * instruction data is the event tag, the event discriminator and the borsh encoded event.
+ */
+pub enum AppEvent {
+    TokenPurchase(TokenPurchase),
+    TokenSell(TokenSell),
+    Reinvestment(Reinvestment),
+    Withdrawal(Withdrawal),
+    Skwizkey(Skwizkey),
+    TokenTransfer(TokenTransfer),
+    InitialStageEnd(InitialStageEnd),
+    AdminNomination(AdminNomination),
+    AdministratorUpdate(AdministratorUpdate),
+    AmbassadorUpdate(AmbassadorUpdate),
+    RolesUpdate(RolesUpdate),
+    StakingRequirementUpdate(StakingRequirementUpdate),
+    VestingGrant(VestingGrant),
+    VestingRevocation(VestingRevocation),
+    MetadataUpdate(MetadataUpdate),
+    AdminThresholdUpdate(AdminThresholdUpdate),
+    DividendFeeUpdate(DividendFeeUpdate),
+    TimelockDelayUpdate(TimelockDelayUpdate),
+    #[cfg(feature = "feature-dev")]
+    ClockOffsetUpdate(ClockOffsetUpdate),
+    ProposalCreation(ProposalCreation),
+    ProposalApproval(ProposalApproval),
+    ProposalExecution(ProposalExecution),
+    ProposalCancellation(ProposalCancellation),
+}
+
+fn decode<T: AnchorDeserialize + Discriminator>(
+    data: &[u8],
+    wrap: fn(T) -> AppEvent,
+) -> Option<AppEvent> {
+    let (discriminator, mut event) = data.split_at_checked(8)?;
+    if discriminator != T::DISCRIMINATOR {
+        return None;
+    }
+    T::deserialize(&mut event).ok().map(wrap)
+}
+
+impl AppEvent {
+    /**
+     * Decodes the data of a self-CPI instruction, `None` if it isn't one of our events.
+     */
+    pub fn from_instruction_data(data: &[u8]) -> Option<Self> {
+        let data = data.strip_prefix(&EVENT_IX_TAG_LE)?;
+
+        let event = decode(data, AppEvent::TokenPurchase)
+            .or_else(|| decode(data, AppEvent::TokenSell))
+            .or_else(|| decode(data, AppEvent::Reinvestment))
+            .or_else(|| decode(data, AppEvent::Withdrawal))
+            .or_else(|| decode(data, AppEvent::Skwizkey))
+            .or_else(|| decode(data, AppEvent::TokenTransfer))
+            .or_else(|| decode(data, AppEvent::InitialStageEnd))
+            .or_else(|| decode(data, AppEvent::AdminNomination))
+            .or_else(|| decode(data, AppEvent::AdministratorUpdate))
+            .or_else(|| decode(data, AppEvent::AmbassadorUpdate))
+            .or_else(|| decode(data, AppEvent::RolesUpdate))
+            .or_else(|| decode(data, AppEvent::StakingRequirementUpdate))
+            .or_else(|| decode(data, AppEvent::VestingGrant))
+            .or_else(|| decode(data, AppEvent::VestingRevocation))
+            .or_else(|| decode(data, AppEvent::MetadataUpdate))
+            .or_else(|| decode(data, AppEvent::AdminThresholdUpdate))
+            .or_else(|| decode(data, AppEvent::DividendFeeUpdate))
+            .or_else(|| decode(data, AppEvent::TimelockDelayUpdate))
+            .or_else(|| decode(data, AppEvent::ProposalCreation))
+            .or_else(|| decode(data, AppEvent::ProposalApproval))
+            .or_else(|| decode(data, AppEvent::ProposalExecution))
+            .or_else(|| decode(data, AppEvent::ProposalCancellation));
+        #[cfg(feature = "feature-dev")]
+        let event = event.or_else(|| decode(data, AppEvent::ClockOffsetUpdate));
+        event
+    }
+
+    // Position of the event in the program's global sequence
+    pub fn seq(&self) -> u64 {
+        match self {
+            AppEvent::TokenPurchase(e) => e.seq,
+            AppEvent::TokenSell(e) => e.seq,
+            AppEvent::Reinvestment(e) => e.seq,
+            AppEvent::Withdrawal(e) => e.seq,
+            AppEvent::Skwizkey(e) => e.seq,
+            AppEvent::TokenTransfer(e) => e.seq,
+            AppEvent::InitialStageEnd(e) => e.seq,
+            AppEvent::AdminNomination(e) => e.seq,
+            AppEvent::AdministratorUpdate(e) => e.seq,
+            AppEvent::AmbassadorUpdate(e) => e.seq,
+            AppEvent::RolesUpdate(e) => e.seq,
+            AppEvent::StakingRequirementUpdate(e) => e.seq,
+            AppEvent::VestingGrant(e) => e.seq,
+            AppEvent::VestingRevocation(e) => e.seq,
+            AppEvent::MetadataUpdate(e) => e.seq,
+            AppEvent::AdminThresholdUpdate(e) => e.seq,
+            AppEvent::DividendFeeUpdate(e) => e.seq,
+            AppEvent::TimelockDelayUpdate(e) => e.seq,
+            #[cfg(feature = "feature-dev")]
+            AppEvent::ClockOffsetUpdate(e) => e.seq,
+            AppEvent::ProposalCreation(e) => e.seq,
+            AppEvent::ProposalApproval(e) => e.seq,
+            AppEvent::ProposalExecution(e) => e.seq,
+            AppEvent::ProposalCancellation(e) => e.seq,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            AppEvent::TokenPurchase(_) => "TokenPurchase",
+            AppEvent::TokenSell(_) => "TokenSell",
+            AppEvent::Reinvestment(_) => "Reinvestment",
+            AppEvent::Withdrawal(_) => "Withdrawal",
+            AppEvent::Skwizkey(_) => "Skwizkey",
+            AppEvent::TokenTransfer(_) => "TokenTransfer",
+            AppEvent::InitialStageEnd(_) => "InitialStageEnd",
+            AppEvent::AdminNomination(_) => "AdminNomination",
+            AppEvent::AdministratorUpdate(_) => "AdministratorUpdate",
+            AppEvent::AmbassadorUpdate(_) => "AmbassadorUpdate",
+            AppEvent::RolesUpdate(_) => "RolesUpdate",
+            AppEvent::StakingRequirementUpdate(_) => "StakingRequirementUpdate",
+            AppEvent::VestingGrant(_) => "VestingGrant",
+            AppEvent::VestingRevocation(_) => "VestingRevocation",
+            AppEvent::MetadataUpdate(_) => "MetadataUpdate",
+            AppEvent::AdminThresholdUpdate(_) => "AdminThresholdUpdate",
+            AppEvent::DividendFeeUpdate(_) => "DividendFeeUpdate",
+            AppEvent::TimelockDelayUpdate(_) => "TimelockDelayUpdate",
+            #[cfg(feature = "feature-dev")]
+            AppEvent::ClockOffsetUpdate(_) => "ClockOffsetUpdate",
+            AppEvent::ProposalCreation(_) => "ProposalCreation",
+            AppEvent::ProposalApproval(_) => "ProposalApproval",
+            AppEvent::ProposalExecution(_) => "ProposalExecution",
+            AppEvent::ProposalCancellation(_) => "ProposalCancellation",
+        }
+    }
+}
@@ -0,0 +1,554 @@
+use {
+    crate::events::AppEvent,
+    anchor_lang::prelude::Pubkey,
+    app::{
+        events::*,
+        instructions::InitTokenParams,
+        states::{App, User},
+    },
+    app_math::{pool::payout_of, Holder, MathError, Pool, PurchaseSplit, SaleSplit},
+    std::{collections::BTreeMap, fmt},
+};
+
+/*==============================
+=            LEDGER            =
+==============================*/
+// Replays the program's bookkeeping from its events, with the same math and in the same order.
+
+// What the program stores for a holder, as rebuilt from the events
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HolderState {
+    pub balance: u128,
+    pub payout: i128,
+    pub referred_balance: u128,
+    pub nonce: u64,
+}
+
+impl HolderState {
+    fn holder(&self) -> Holder {
+        Holder {
+            balance: self.balance,
+            payout: self.payout,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum LedgerError {
+    Math(MathError),
+    // An event is missing, the indexer can't go on
+    SequenceGap {
+        expected: u64,
+        found: u64,
+    },
+    NonceGap {
+        user: Pubkey,
+        expected: u64,
+        found: u64,
+    },
+    // The replay disagrees with what the event reports
+    Diverged {
+        seq: u64,
+        field: &'static str,
+        ledger: u128,
+        event: u128,
+    },
+}
+
+impl fmt::Display for LedgerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LedgerError::Math(err) => write!(f, "{err}"),
+            LedgerError::SequenceGap { expected, found } => {
+                write!(f, "expected event {expected}, found event {found}")
+            }
+            LedgerError::NonceGap {
+                user,
+                expected,
+                found,
+            } => write!(f, "expected nonce {expected} for {user}, found {found}"),
+            LedgerError::Diverged {
+                seq,
+                field,
+                ledger,
+                event,
+            } => write!(
+                f,
+                "event {seq} reports {field} {event}, the replay has {ledger}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LedgerError {}
+
+impl From<MathError> for LedgerError {
+    fn from(err: MathError) -> Self {
+        LedgerError::Math(err)
+    }
+}
+
+type Result<T> = std::result::Result<T, LedgerError>;
+
+fn check(seq: u64, field: &'static str, ledger: u128, event: u128) -> Result<()> {
+    if ledger != event {
+        return Err(LedgerError::Diverged {
+            seq,
+            field,
+            ledger,
+            event,
+        });
+    }
+    Ok(())
+}
+
+pub struct Ledger {
+    pub pool: Pool,
+    pub event_seq: u64,
+    pub holders: BTreeMap<Pubkey, HolderState>,
+    // customer whose next purchase paid a referral bonus
+    referral_paid_by: Option<Pubkey>,
+}
+
+impl Ledger {
+    pub fn new(pool: Pool) -> Self {
+        Self {
+            pool,
+            event_seq: 0,
+            holders: BTreeMap::new(),
+            referral_paid_by: None,
+        }
+    }
+
+    // A freshly initialized pool
+    pub fn from_params(params: &InitTokenParams) -> Self {
+        Self::new(Pool {
+            curve: params.curve.into(),
+            token_initial_price: params.token_initial_price,
+            token_incremental_price: params.token_incremental_price,
+            token_supply: 0,
+            decimals: params.decimals,
+            dividend_fee: params.dividend_fee,
+            magnitude: params.magnitude,
+            profit_per_share: 0,
+        })
+    }
+
+    // The parameters of a live pool, for replaying it from its first event
+    pub fn from_app(app: &App) -> Self {
+        Self::new(Pool {
+            token_supply: 0,
+            profit_per_share: 0,
+            ..app.pool()
+        })
+    }
+
+    fn magnitude(&self) -> u128 {
+        self.pool.magnitude as u128
+    }
+
+    // Bumps the nonce of `user`, which the event says is now `nonce`
+    fn holder(&mut self, user: Pubkey, nonce: u64) -> Result<&mut HolderState> {
+        let holder = self.holders.entry(user).or_default();
+        let expected = holder.nonce.checked_add(1).ok_or(MathError::Overflow)?;
+        if nonce != expected {
+            return Err(LedgerError::NonceGap {
+                user,
+                expected,
+                found: nonce,
+            });
+        }
+        holder.nonce = nonce;
+        Ok(holder)
+    }
+
+    // Pool state the trade events carry, after the trade
+    fn check_pool(&self, seq: u64, token_supply: u128, profit_per_share: u128) -> Result<()> {
+        check(seq, "token_supply", self.pool.token_supply, token_supply)?;
+        check(
+            seq,
+            "profit_per_share",
+            self.pool.profit_per_share,
+            profit_per_share,
+        )
+    }
+
+    /**
+     * Applies the next event. They must come in sequence order, starting right after `event_seq`.
+     */
+    pub fn apply(&mut self, event: &AppEvent) -> Result<()> {
+        let seq = event.seq();
+        let expected = self.event_seq.checked_add(1).ok_or(MathError::Overflow)?;
+        if seq != expected {
+            return Err(LedgerError::SequenceGap {
+                expected,
+                found: seq,
+            });
+        }
+        // only the purchase right after a referral can have paid its bonus
+        let referral_paid_by = self.referral_paid_by.take();
+
+        match event {
+            AppEvent::TokenPurchase(e) => self.purchase(e, referral_paid_by == Some(e.customer))?,
+            AppEvent::TokenSell(e) => self.sell(e)?,
+            AppEvent::Reinvestment(e) => self.reinvest(e)?,
+            AppEvent::Withdrawal(e) => self.withdraw(e)?,
+            AppEvent::Skwizkey(e) => self.referral(e)?,
+            AppEvent::TokenTransfer(e) => self.transfer(e)?,
+            AppEvent::AdministratorUpdate(e) => {
+                self.holder(e.user, e.nonce)?;
+            }
+            AppEvent::AmbassadorUpdate(e) => {
+                self.holder(e.user, e.nonce)?;
+            }
+            AppEvent::RolesUpdate(e) => {
+                self.holder(e.user, e.nonce)?;
+            }
+            AppEvent::VestingGrant(e) => self.grant(e)?,
+            AppEvent::VestingRevocation(e) => self.revoke(e)?,
+            // later trades are split with the new fee
+            AppEvent::DividendFeeUpdate(e) => self.pool.dividend_fee = e.new_fee,
+            // the rest doesn't touch the ledger
+            AppEvent::InitialStageEnd(_)
+            | AppEvent::AdminNomination(_)
+            | AppEvent::StakingRequirementUpdate(_)
+            | AppEvent::MetadataUpdate(_)
+            | AppEvent::AdminThresholdUpdate(_)
+            | AppEvent::TimelockDelayUpdate(_)
+            | AppEvent::ProposalCreation(_)
+            | AppEvent::ProposalApproval(_)
+            | AppEvent::ProposalExecution(_)
+            | AppEvent::ProposalCancellation(_) => {}
+            #[cfg(feature = "feature-dev")]
+            AppEvent::ClockOffsetUpdate(_) => {}
+        }
+
+        self.event_seq = seq;
+        Ok(())
+    }
+
+    fn purchase(&mut self, e: &TokenPurchase, referral_paid: bool) -> Result<()> {
+        let magnitude = self.magnitude();
+        let split = PurchaseSplit::new(e.incoming_lamports, self.pool.dividend_fee)?;
+        check(
+            e.seq,
+            "tokens_minted",
+            self.pool.lamport_to_tokens(split.taxed_lamports)?,
+            e.tokens_minted,
+        )?;
+
+        let mut dividends = split.dividends;
+        if !referral_paid {
+            // the bonus went back to the global dividends cake
+            dividends = dividends
+                .checked_add(split.referral_bonus)
+                .ok_or(MathError::Overflow)?;
+        }
+        let mut fee = dividends
+            .checked_mul(magnitude)
+            .ok_or(MathError::Overflow)?;
+
+        if self.pool.token_supply > 0 {
+            self.pool.token_supply = self
+                .pool
+                .token_supply
+                .checked_add(e.tokens_minted)
+                .ok_or(MathError::Overflow)?;
+            let dividends_per_share = self.pool.dividends_per_share(dividends)?;
+            self.pool.profit_per_share = self
+                .pool
+                .profit_per_share
+                .checked_add(dividends_per_share)
+                .ok_or(MathError::Overflow)?;
+            // the buyer only keeps their share of their own dividends
+            fee = e
+                .tokens_minted
+                .checked_mul(dividends_per_share)
+                .ok_or(MathError::Overflow)?;
+        } else {
+            self.pool.token_supply = e.tokens_minted;
+        }
+        self.check_pool(e.seq, e.token_supply, e.profit_per_share)?;
+
+        let updated_payouts = payout_of(self.pool.profit_per_share, e.tokens_minted)?
+            .checked_sub(i128::try_from(fee).map_err(|_| MathError::Overflow)?)
+            .ok_or(MathError::Underflow)?;
+        let holder = self.holder(e.customer, e.nonce)?;
+        holder.balance = holder
+            .balance
+            .checked_add(e.tokens_minted)
+            .ok_or(MathError::Overflow)?;
+        holder.payout = holder
+            .payout
+            .checked_add(updated_payouts)
+            .ok_or(MathError::Overflow)?;
+        Ok(())
+    }
+
+    fn referral(&mut self, e: &Skwizkey) -> Result<()> {
+        let holder = self.holder(e.referrer, e.referrer_nonce)?;
+        holder.referred_balance = holder
+            .referred_balance
+            .checked_add(e.bonus_received)
+            .ok_or(MathError::Overflow)?;
+        self.referral_paid_by = Some(e.customer);
+        Ok(())
+    }
+
+    fn reinvest(&mut self, e: &Reinvestment) -> Result<()> {
+        // the tokens came with the purchase event before this one
+        let updated_payouts = payout_of(e.lamports_reinvested, self.magnitude())?;
+        let holder = self.holder(e.customer, e.nonce)?;
+        holder.payout = holder
+            .payout
+            .checked_add(updated_payouts)
+            .ok_or(MathError::Overflow)?;
+        Ok(())
+    }
+
+    fn withdraw(&mut self, e: &Withdrawal) -> Result<()> {
+        let magnitude = self.magnitude();
+        let pool = self.pool;
+        let holder = self.holder(e.customer, e.nonce)?;
+
+        let dividends = pool.dividends_of(&holder.holder())?;
+        let withdrawn = dividends
+            .checked_add(holder.referred_balance)
+            .ok_or(MathError::Overflow)?;
+        check(e.seq, "lamports_withdrawn", withdrawn, e.lamports_withdrawn)?;
+        holder.payout = holder
+            .payout
+            .checked_add(payout_of(dividends, magnitude)?)
+            .ok_or(MathError::Overflow)?;
+        holder.referred_balance = 0;
+        Ok(())
+    }
+
+    fn sell(&mut self, e: &TokenSell) -> Result<()> {
+        let magnitude = self.magnitude();
+        let lamports = self.pool.tokens_to_lamport(e.tokens_burned)?;
+        let split = SaleSplit::new(lamports, self.pool.dividend_fee)?;
+        check(e.seq, "lamports_earned", split.taxed, e.lamports_earned)?;
+
+        self.pool.token_supply = self
+            .pool
+            .token_supply
+            .checked_sub(e.tokens_burned)
+            .ok_or(MathError::Underflow)?;
+        let updated_payouts = payout_of(self.pool.profit_per_share, e.tokens_burned)?
+            .checked_add(payout_of(split.taxed, magnitude)?)
+            .ok_or(MathError::Overflow)?;
+        if self.pool.token_supply > 0 {
+            self.pool.profit_per_share = self
+                .pool
+                .profit_per_share
+                .checked_add(self.pool.dividends_per_share(split.dividends)?)
+                .ok_or(MathError::Overflow)?;
+        }
+        self.check_pool(e.seq, e.token_supply, e.profit_per_share)?;
+
+        let holder = self.holder(e.customer, e.nonce)?;
+        holder.balance = holder
+            .balance
+            .checked_sub(e.tokens_burned)
+            .ok_or(MathError::Underflow)?;
+        holder.payout = holder
+            .payout
+            .checked_sub(updated_payouts)
+            .ok_or(MathError::Underflow)?;
+        Ok(())
+    }
+
+    fn transfer(&mut self, e: &TokenTransfer) -> Result<()> {
+        let split = SaleSplit::new(e.tokens, self.pool.dividend_fee)?;
+        let dividends = self.pool.tokens_to_lamport(split.dividends)?;
+
+        // the fee tokens are burned
+        self.pool.token_supply = self
+            .pool
+            .token_supply
+            .checked_sub(split.dividends)
+            .ok_or(MathError::Underflow)?;
+        let from_payouts = payout_of(self.pool.profit_per_share, e.tokens)?;
+        let to_payouts = payout_of(self.pool.profit_per_share, split.taxed)?;
+        self.pool.profit_per_share = self
+            .pool
+            .profit_per_share
+            .checked_add(self.pool.dividends_per_share(dividends)?)
+            .ok_or(MathError::Overflow)?;
+        self.check_pool(e.seq, e.token_supply, e.profit_per_share)?;
+
+        let from = self.holder(e.from, e.from_nonce)?;
+        from.balance = from
+            .balance
+            .checked_sub(e.tokens)
+            .ok_or(MathError::Underflow)?;
+        from.payout = from
+            .payout
+            .checked_sub(from_payouts)
+            .ok_or(MathError::Underflow)?;
+
+        let to = self.holder(e.to, e.to_nonce)?;
+        to.balance = to
+            .balance
+            .checked_add(split.taxed)
+            .ok_or(MathError::Overflow)?;
+        to.payout = to
+            .payout
+            .checked_add(to_payouts)
+            .ok_or(MathError::Overflow)?;
+        Ok(())
+    }
+
+    fn grant(&mut self, e: &VestingGrant) -> Result<()> {
+        let admin = self.holder(e.admin, e.admin_nonce)?;
+        admin.balance = admin
+            .balance
+            .checked_sub(e.amount)
+            .ok_or(MathError::Underflow)?;
+        admin.payout = admin
+            .payout
+            .checked_sub(e.payout_adjustment)
+            .ok_or(MathError::Underflow)?;
+
+        let receipient = self.holder(e.receipient, e.receipient_nonce)?;
+        receipient.balance = receipient
+            .balance
+            .checked_add(e.amount)
+            .ok_or(MathError::Overflow)?;
+        receipient.payout = receipient
+            .payout
+            .checked_add(e.payout_adjustment)
+            .ok_or(MathError::Overflow)?;
+        Ok(())
+    }
+
+    fn revoke(&mut self, e: &VestingRevocation) -> Result<()> {
+        // a sale whose proceeds all go to dividends
+        let dividends = self.pool.tokens_to_lamport(e.tokens_burned)?;
+        check(
+            e.seq,
+            "lamports_redistributed",
+            dividends,
+            e.lamports_redistributed,
+        )?;
+
+        self.pool.token_supply = self
+            .pool
+            .token_supply
+            .checked_sub(e.tokens_burned)
+            .ok_or(MathError::Underflow)?;
+        let updated_payouts = payout_of(self.pool.profit_per_share, e.tokens_burned)?;
+        if self.pool.token_supply > 0 {
+            self.pool.profit_per_share = self
+                .pool
+                .profit_per_share
+                .checked_add(self.pool.dividends_per_share(dividends)?)
+                .ok_or(MathError::Overflow)?;
+        }
+        self.check_pool(e.seq, e.token_supply, e.profit_per_share)?;
+
+        let holder = self.holder(e.receipient, e.nonce)?;
+        holder.balance = holder
+            .balance
+            .checked_sub(e.tokens_burned)
+            .ok_or(MathError::Underflow)?;
+        holder.payout = holder
+            .payout
+            .checked_sub(updated_payouts)
+            .ok_or(MathError::Underflow)?;
+        Ok(())
+    }
+}
+
+/*==============================
+=         CROSS-CHECKS         =
+==============================*/
+
+// A field where the replayed ledger and the fetched account disagree
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Divergence {
+    // the user's wallet, or the program state PDA
+    pub account: Pubkey,
+    pub field: &'static str,
+    pub ledger: String,
+    pub on_chain: String,
+}
+
+fn compare<T: PartialEq + ToString>(
+    divergences: &mut Vec<Divergence>,
+    account: Pubkey,
+    field: &'static str,
+    ledger: T,
+    on_chain: T,
+) {
+    if ledger != on_chain {
+        divergences.push(Divergence {
+            account,
+            field,
+            ledger: ledger.to_string(),
+            on_chain: on_chain.to_string(),
+        });
+    }
+}
+
+impl Ledger {
+    pub fn check_app(&self, program_data: &Pubkey, app: &App) -> Vec<Divergence> {
+        let mut divergences = vec![];
+        let account = *program_data;
+        compare(
+            &mut divergences,
+            account,
+            "token_supply",
+            self.pool.token_supply,
+            app.token_supply,
+        );
+        compare(
+            &mut divergences,
+            account,
+            "profit_per_share",
+            self.pool.profit_per_share,
+            app.profit_per_share,
+        );
+        compare(
+            &mut divergences,
+            account,
+            "event_seq",
+            self.event_seq,
+            app.event_seq,
+        );
+        divergences
+    }
+
+    /**
+     * Compares a holder with its fetched `User` account. Holders the ledger never saw count as empty.
+     */
+    pub fn check_user(&self, authority: &Pubkey, user: &User) -> Vec<Divergence> {
+        let mut divergences = vec![];
+        let account = *authority;
+        let holder = self.holders.get(authority).copied().unwrap_or_default();
+        compare(
+            &mut divergences,
+            account,
+            "balance",
+            holder.balance,
+            user.balance,
+        );
+        compare(
+            &mut divergences,
+            account,
+            "payout",
+            holder.payout,
+            user.payout,
+        );
+        compare(
+            &mut divergences,
+            account,
+            "referred_balance",
+            holder.referred_balance,
+            user.referred_balance,
+        );
+        compare(&mut divergences, account, "nonce", holder.nonce, user.nonce);
+        divergences
+    }
+}
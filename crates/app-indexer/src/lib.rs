@@ -0,0 +1,9 @@
+pub mod events;
+pub mod ledger;
+pub mod transaction;
+
+pub use {
+    events::AppEvent,
+    ledger::{Divergence, HolderState, Ledger, LedgerError},
+    transaction::{extract_records, Record, TransactionError},
+};
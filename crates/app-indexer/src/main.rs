@@ -0,0 +1,331 @@
+use {
+    anyhow::{anyhow, bail, Context, Result},
+    app_client::{
+        accounts::{deserialize_app, deserialize_user},
+        pdas::{find_program_pda, find_user_pda},
+    },
+    app_indexer::{extract_records, AppEvent, Divergence, Ledger, Record},
+    clap::{Parser, Subcommand},
+    solana_client::{
+        rpc_client::{GetConfirmedSignaturesForAddress2Config, RpcClient},
+        rpc_config::RpcTransactionConfig,
+    },
+    solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Signature},
+    solana_transaction_status::{EncodedConfirmedTransactionWithStatusMeta, UiTransactionEncoding},
+    std::{fs, str::FromStr},
+};
+
+#[derive(Parser)]
+#[command(
+    name = "app-indexer",
+    about = "Rebuild the app program's holder ledger from its events"
+)]
+struct Cli {
+    /// RPC URL, or one of the monikers localhost, devnet, testnet, mainnet-beta
+    #[arg(
+        short,
+        long,
+        global = true,
+        env = "APP_RPC_URL",
+        default_value = "localhost"
+    )]
+    url: String,
+    /// Program to index, defaults to the deployed address
+    #[arg(long, global = true)]
+    program_id: Option<Pubkey>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Replay transactions saved as JSON, as returned by `getTransaction`
+    Replay {
+        /// Files holding one transaction or an array of them
+        #[arg(required = true)]
+        files: Vec<String>,
+        /// Cross-check the ledger against the accounts fetched from --url
+        #[arg(long)]
+        check: bool,
+    },
+    /// Fetch every transaction of the program, replay them and cross-check the accounts
+    Sync {
+        /// Also save the fetched transactions, for replaying them later
+        #[arg(long)]
+        save: Option<String>,
+    },
+}
+
+fn rpc_url(url: &str) -> &str {
+    match url {
+        "localhost" | "l" => "http://127.0.0.1:8899",
+        "devnet" | "d" => "https://api.devnet.solana.com",
+        "testnet" | "t" => "https://api.testnet.solana.com",
+        "mainnet-beta" | "m" => "https://api.mainnet-beta.solana.com",
+        url => url,
+    }
+}
+
+fn load_fixture(path: &str) -> Result<Vec<EncodedConfirmedTransactionWithStatusMeta>> {
+    let json = fs::read_to_string(path).with_context(|| format!("failed to read {path}"))?;
+    let value: serde_json::Value =
+        serde_json::from_str(&json).with_context(|| format!("{path} isn't JSON"))?;
+    // a bare transaction or an array of them, with or without the JSON-RPC envelope
+    let value = match value.get("result") {
+        Some(result) => result.clone(),
+        None => value,
+    };
+    let txs = match value {
+        serde_json::Value::Array(txs) => txs,
+        tx => vec![tx],
+    };
+    txs.into_iter()
+        .map(|tx| serde_json::from_value(tx).with_context(|| format!("bad transaction in {path}")))
+        .collect()
+}
+
+// Every transaction that touched the program, oldest first
+fn fetch_transactions(
+    rpc: &RpcClient,
+    program_id: &Pubkey,
+) -> Result<Vec<EncodedConfirmedTransactionWithStatusMeta>> {
+    let mut signatures = vec![];
+    let mut before = None;
+    loop {
+        let page = rpc.get_signatures_for_address_with_config(
+            program_id,
+            GetConfirmedSignaturesForAddress2Config {
+                before,
+                until: None,
+                limit: None,
+                commitment: Some(CommitmentConfig::confirmed()),
+            },
+        )?;
+        let Some(last) = page.last() else {
+            break;
+        };
+        before = Some(Signature::from_str(&last.signature)?);
+        signatures.extend(
+            page.into_iter()
+                .filter(|status| status.err.is_none())
+                .map(|status| status.signature),
+        );
+    }
+
+    signatures
+        .iter()
+        .rev()
+        .map(|signature| {
+            Ok(rpc.get_transaction_with_config(
+                &Signature::from_str(signature)?,
+                RpcTransactionConfig {
+                    encoding: Some(UiTransactionEncoding::Json),
+                    commitment: Some(CommitmentConfig::confirmed()),
+                    max_supported_transaction_version: Some(0),
+                },
+            )?)
+        })
+        .collect()
+}
+
+/**
+ * Replays the events in sequence order. Without an `initialize` among the transactions
+ * the pool parameters come from `fetch_app`.
+ */
+fn replay(
+    txs: &[EncodedConfirmedTransactionWithStatusMeta],
+    program_id: &Pubkey,
+    fetch_app: impl FnOnce() -> Result<app_client::App>,
+) -> Result<Ledger> {
+    let mut params = None;
+    let mut events: Vec<AppEvent> = vec![];
+    for tx in txs {
+        for record in extract_records(tx, program_id)? {
+            match record {
+                Record::Initialize(init) => params = Some(init),
+                Record::Event(event) => events.push(event),
+            }
+        }
+    }
+    events.sort_by_key(AppEvent::seq);
+
+    let mut ledger = match params {
+        Some(params) => Ledger::from_params(&params),
+        None => Ledger::from_app(&fetch_app()?),
+    };
+    for event in &events {
+        ledger
+            .apply(event)
+            .map_err(|err| anyhow!("{} {}: {err}", event.name(), event.seq()))?;
+    }
+    Ok(ledger)
+}
+
+fn fetch_app(rpc: &RpcClient, program_id: &Pubkey) -> Result<app_client::App> {
+    let (pda, _) = find_program_pda(program_id);
+    let data = rpc
+        .get_account_data(&pda)
+        .with_context(|| format!("program state {pda} not found, is it initialized?"))?;
+    Ok(deserialize_app(&data)?)
+}
+
+// Compares the ledger with the `App` account and the `User` account of every holder
+fn cross_check(rpc: &RpcClient, program_id: &Pubkey, ledger: &Ledger) -> Result<Vec<Divergence>> {
+    let (program_data, _) = find_program_pda(program_id);
+    let mut divergences = ledger.check_app(&program_data, &fetch_app(rpc, program_id)?);
+
+    let holders: Vec<&Pubkey> = ledger.holders.keys().collect();
+    for chunk in holders.chunks(100) {
+        let pdas: Vec<Pubkey> = chunk
+            .iter()
+            .map(|holder| find_user_pda(holder, program_id).0)
+            .collect();
+        for (holder, account) in chunk.iter().zip(rpc.get_multiple_accounts(&pdas)?) {
+            let account = account.with_context(|| format!("user account of {holder} not found"))?;
+            divergences.extend(ledger.check_user(holder, &deserialize_user(&account.data)?));
+        }
+    }
+    Ok(divergences)
+}
+
+fn print_ledger(ledger: &Ledger) {
+    println!("event_seq:        {}", ledger.event_seq);
+    println!("token_supply:     {}", ledger.pool.token_supply);
+    println!("profit_per_share: {}", ledger.pool.profit_per_share);
+    for (authority, holder) in &ledger.holders {
+        println!(
+            "{authority} balance {} payout {} referred_balance {} nonce {}",
+            holder.balance, holder.payout, holder.referred_balance, holder.nonce
+        );
+    }
+}
+
+fn report(divergences: &[Divergence]) -> Result<()> {
+    if divergences.is_empty() {
+        println!("ledger matches the on-chain accounts");
+        return Ok(());
+    }
+    for divergence in divergences {
+        println!(
+            "{} {}: ledger {}, on-chain {}",
+            divergence.account, divergence.field, divergence.ledger, divergence.on_chain
+        );
+    }
+    bail!("{} divergences", divergences.len())
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let rpc = RpcClient::new_with_commitment(rpc_url(&cli.url), CommitmentConfig::confirmed());
+    let program_id = cli.program_id.unwrap_or(app_client::ID);
+
+    match cli.command {
+        Command::Replay { files, check } => {
+            let mut txs = vec![];
+            for file in &files {
+                txs.extend(load_fixture(file)?);
+            }
+            let ledger = replay(&txs, &program_id, || fetch_app(&rpc, &program_id))?;
+            print_ledger(&ledger);
+            if check {
+                report(&cross_check(&rpc, &program_id, &ledger)?)?;
+            }
+        }
+        Command::Sync { save } => {
+            let txs = fetch_transactions(&rpc, &program_id)?;
+            if let Some(path) = save {
+                fs::write(&path, serde_json::to_string_pretty(&txs)?)
+                    .with_context(|| format!("failed to write {path}"))?;
+            }
+            let ledger = replay(&txs, &program_id, || fetch_app(&rpc, &program_id))?;
+            print_ledger(&ledger);
+            report(&cross_check(&rpc, &program_id, &ledger)?)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, app_indexer::HolderState};
+
+    // `getTransaction` responses of a linear pool going through every event that moves balances:
+    // an ambassador buy, a vesting grant, the end of the initial phase, a referred buy, a buy,
+    // a sell, a reinvestment, a withdrawal, a transfer that withdraws first and a revocation
+    const FIXTURE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/replay.json");
+
+    fn key(address: &str) -> Pubkey {
+        Pubkey::from_str(address).unwrap()
+    }
+
+    fn replay_fixture(skip: Option<usize>) -> Result<Ledger> {
+        let mut txs = load_fixture(FIXTURE)?;
+        if let Some(index) = skip {
+            txs.remove(index);
+        }
+        // the fixture starts with `initialize`, the state is never fetched
+        replay(&txs, &app_client::ID, || unreachable!())
+    }
+
+    #[test]
+    fn fixture_replays_without_divergence() {
+        let ledger = replay_fixture(None).unwrap();
+
+        assert_eq!(ledger.event_seq, 13);
+        assert_eq!(ledger.pool.token_supply, 5_960_493_478_145);
+        assert_eq!(ledger.pool.profit_per_share, 709_439);
+        let expected = [
+            // admin, granted and referred
+            (
+                "AT7MMgHMVAvAni3ipu6X6HYs4gXPXJ1kS9sKrGPG1Jtw",
+                HolderState {
+                    balance: 2_511_406_716_006,
+                    payout: 671_183_489_683_034_334,
+                    referred_balance: 0,
+                    nonce: 6,
+                },
+            ),
+            // referred buyer, sold half and received the transfer
+            (
+                "9jLkNAaW9E47LQMHvjohy2uAAyr1331bAxgJKFRU7wF6",
+                HolderState {
+                    balance: 2_020_405_767_091,
+                    payout: -5_025_594_552_156_081_142,
+                    referred_balance: 0,
+                    nonce: 3,
+                },
+            ),
+            // transferred a third of their tokens
+            (
+                "68GLr8rYqhXTRgYuH5MN7BeswuPxjeEZRLMzunr9JQCt",
+                HolderState {
+                    balance: 1_092_791_100_694,
+                    payout: 292_052_790_740_819_090,
+                    referred_balance: 0,
+                    nonce: 3,
+                },
+            ),
+            // kept the vested part of a revoked grant
+            (
+                "7bDXTe5fFehXPtVMMh9cL5hxcjNenk8g34eCNRTiuBTs",
+                HolderState {
+                    balance: 335_889_894_354,
+                    payout: -230_646_969_278_936_714,
+                    referred_balance: 0,
+                    nonce: 2,
+                },
+            ),
+        ];
+        assert_eq!(ledger.holders.len(), expected.len());
+        for (address, holder) in expected {
+            assert_eq!(ledger.holders[&key(address)], holder, "{address}");
+        }
+    }
+
+    #[test]
+    fn missing_transaction_stops_the_replay() {
+        // the sell, event 7
+        let err = replay_fixture(Some(6)).err().unwrap();
+        assert!(err.to_string().contains("expected event 7"), "{err}");
+    }
+}
@@ -0,0 +1,159 @@
+use {
+    crate::events::AppEvent,
+    anchor_lang::{prelude::Pubkey, AnchorDeserialize, Discriminator},
+    app::instructions::InitTokenParams,
+    solana_transaction_status::{
+        option_serializer::OptionSerializer, EncodedConfirmedTransactionWithStatusMeta,
+        EncodedTransaction, UiInstruction, UiMessage,
+    },
+    std::{fmt, str::FromStr},
+};
+
+// What the indexer takes out of a transaction
+pub enum Record {
+    // The pool parameters, from the top level `initialize` instruction
+    Initialize(InitTokenParams),
+    Event(AppEvent),
+}
+
+#[derive(Debug)]
+pub enum TransactionError {
+    // Without the status meta there are no inner instructions, hence no events
+    MissingMeta,
+    // `jsonParsed` and `accounts` encodings don't carry the raw instructions
+    UnsupportedEncoding,
+    InvalidAccountKey(String),
+    InvalidInstructionData(String),
+    InvalidProgramIndex(u8),
+}
+
+impl fmt::Display for TransactionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransactionError::MissingMeta => f.write_str("transaction has no status meta"),
+            TransactionError::UnsupportedEncoding => {
+                f.write_str("transaction must be fetched with the json, base58 or base64 encoding")
+            }
+            TransactionError::InvalidAccountKey(key) => write!(f, "invalid account key {key}"),
+            TransactionError::InvalidInstructionData(data) => {
+                write!(f, "instruction data {data} isn't base58")
+            }
+            TransactionError::InvalidProgramIndex(index) => {
+                write!(f, "program index {index} is out of the account keys")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TransactionError {}
+
+type Result<T> = std::result::Result<T, TransactionError>;
+
+fn parse_key(key: &str) -> Result<Pubkey> {
+    Pubkey::from_str(key).map_err(|_| TransactionError::InvalidAccountKey(key.to_string()))
+}
+
+// Program index and data of an instruction
+type RawInstruction = (u8, Vec<u8>);
+
+// The account keys and top level instructions of the message
+fn message(tx: &EncodedTransaction) -> Result<(Vec<Pubkey>, Vec<RawInstruction>)> {
+    if let EncodedTransaction::Json(tx) = tx {
+        let UiMessage::Raw(message) = &tx.message else {
+            return Err(TransactionError::UnsupportedEncoding);
+        };
+        let keys = message
+            .account_keys
+            .iter()
+            .map(|key| parse_key(key))
+            .collect::<Result<_>>()?;
+        let instructions = message
+            .instructions
+            .iter()
+            .map(|ix| Ok((ix.program_id_index, decode_data(&ix.data)?)))
+            .collect::<Result<_>>()?;
+        return Ok((keys, instructions));
+    }
+
+    let tx = tx.decode().ok_or(TransactionError::UnsupportedEncoding)?;
+    let keys = tx.message.static_account_keys().to_vec();
+    let instructions = tx
+        .message
+        .instructions()
+        .iter()
+        .map(|ix| (ix.program_id_index, ix.data.clone()))
+        .collect();
+    Ok((keys, instructions))
+}
+
+fn decode_data(data: &str) -> Result<Vec<u8>> {
+    bs58::decode(data)
+        .into_vec()
+        .map_err(|_| TransactionError::InvalidInstructionData(data.to_string()))
+}
+
+/**
+ * Pulls the records of `program_id` out of a fetched transaction, in execution order.
+ * Events are read from the self-CPI inner instructions rather than the log messages,
+ * which the runtime truncates. Failed transactions yield nothing.
+ */
+pub fn extract_records(
+    tx: &EncodedConfirmedTransactionWithStatusMeta,
+    program_id: &Pubkey,
+) -> Result<Vec<Record>> {
+    let meta = tx
+        .transaction
+        .meta
+        .as_ref()
+        .ok_or(TransactionError::MissingMeta)?;
+    if meta.err.is_some() {
+        return Ok(vec![]);
+    }
+
+    let (mut keys, instructions) = message(&tx.transaction.transaction)?;
+    // address lookup tables of v0 transactions come after the static keys
+    if let OptionSerializer::Some(loaded) = &meta.loaded_addresses {
+        for key in loaded.writable.iter().chain(&loaded.readonly) {
+            keys.push(parse_key(key)?);
+        }
+    }
+    let program_at = |index: u8| {
+        keys.get(index as usize)
+            .ok_or(TransactionError::InvalidProgramIndex(index))
+    };
+
+    let inner = match &meta.inner_instructions {
+        OptionSerializer::Some(inner) => inner.as_slice(),
+        _ => &[],
+    };
+
+    let mut records = vec![];
+    for (index, (program_index, data)) in instructions.iter().enumerate() {
+        if program_at(*program_index)? == program_id {
+            if let Some(ix) = data
+                .strip_prefix(&app::instruction::Initialize::DISCRIMINATOR)
+                .and_then(|mut args| InitTokenParams::deserialize(&mut args).ok())
+            {
+                records.push(Record::Initialize(ix));
+            }
+        }
+
+        for ix in inner
+            .iter()
+            .filter(|inner| inner.index as usize == index)
+            .flat_map(|inner| &inner.instructions)
+        {
+            let UiInstruction::Compiled(ix) = ix else {
+                return Err(TransactionError::UnsupportedEncoding);
+            };
+            if program_at(ix.program_id_index)? != program_id {
+                continue;
+            }
+            if let Some(event) = AppEvent::from_instruction_data(&decode_data(&ix.data)?) {
+                records.push(Record::Event(event));
+            }
+        }
+    }
+
+    Ok(records)
+}
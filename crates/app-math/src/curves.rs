@@ -0,0 +1,467 @@
+use crate::{
+    error::{MathError, Result},
+    fixed::*,
+    LAMPORTS_IN_SOL,
+};
+
+/*==============================
+=        BONDING CURVES        =
+==============================*/
+
+/**
+ * Pricing model used to convert between lamports and tokens.
+ * Every quote is taken against the current token supply, prices are in lamports per whole token.
+ */
+pub trait BondingCurve {
+    // Tokens received for spending `lamports` at the current `supply`
+    fn buy_quote(&self, supply: u128, lamports: u128) -> Result<u128>;
+    // Lamports received for selling the last `tokens` of the current `supply`
+    fn sell_quote(&self, supply: u128, tokens: u128) -> Result<u128>;
+    // Price of the next whole token at the current `supply`
+    fn spot_price(&self, supply: u128) -> Result<u128>;
+    // Largest supply, in base units, the curve still quotes at
+    fn max_supply(&self) -> Result<u128>;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CurveKind {
+    Linear,
+    Exponential,
+    ConstantProduct,
+}
+
+impl CurveKind {
+    /**
+     * Builds the curve described by this kind.
+     * All curves start at `initial_price` and rise by `incremental_price` per whole token at zero supply,
+     * so the same economic parameters give comparable launches whichever curve is picked.
+     */
+    pub fn curve(&self, initial_price: u128, incremental_price: u128) -> Curve {
+        match self {
+            CurveKind::Linear => Curve::Linear(LinearCurve {
+                initial_price,
+                incremental_price,
+            }),
+            CurveKind::Exponential => Curve::Exponential(ExponentialCurve {
+                initial_price,
+                incremental_price,
+            }),
+            CurveKind::ConstantProduct => Curve::ConstantProduct(ConstantProductCurve {
+                initial_price,
+                incremental_price,
+            }),
+        }
+    }
+}
+
+/**
+ * A configured curve of any kind, dispatching statically so no allocator is needed.
+ */
+pub enum Curve {
+    Linear(LinearCurve),
+    Exponential(ExponentialCurve),
+    ConstantProduct(ConstantProductCurve),
+}
+
+impl Curve {
+    fn inner(&self) -> &dyn BondingCurve {
+        match self {
+            Curve::Linear(curve) => curve,
+            Curve::Exponential(curve) => curve,
+            Curve::ConstantProduct(curve) => curve,
+        }
+    }
+}
+
+impl BondingCurve for Curve {
+    fn buy_quote(&self, supply: u128, lamports: u128) -> Result<u128> {
+        self.inner().buy_quote(supply, lamports)
+    }
+
+    fn sell_quote(&self, supply: u128, tokens: u128) -> Result<u128> {
+        self.inner().sell_quote(supply, tokens)
+    }
+
+    fn spot_price(&self, supply: u128) -> Result<u128> {
+        self.inner().spot_price(supply)
+    }
+
+    fn max_supply(&self) -> Result<u128> {
+        self.inner().max_supply()
+    }
+}
+
+/**
+ * price = initial_price + incremental_price * supply
+ * Supports the whole `MAX_SUPPLY` whatever the prices.
+ */
+pub struct LinearCurve {
+    pub initial_price: u128,
+    pub incremental_price: u128,
+}
+
+impl BondingCurve for LinearCurve {
+    /**
+     * Calculate Token price based on an amount of incoming lamport
+     * It's an algorithm, hopefully we gave you the whitepaper with it in scientific notation;
+     * Some conversions occurred to prevent decimal errors or underflows / overflows in solidity code.
+     */
+    fn buy_quote(&self, supply: u128, lamport: u128) -> Result<u128> {
+        let token_price_initial = U256::from(self.initial_price); // 100
+        let token_price_incremental = U256::from(self.incremental_price); // 1000
+        let token_supply = U256::from(supply); // 0
+        let lamports_in_sol = U256::from(LAMPORTS_IN_SOL);
+
+        let token_price_initial_expanded = mul(token_price_initial, lamports_in_sol)?; //100 * 1e9  = 100e9
+        let token_price_incremental_expanded = mul(token_price_incremental, lamports_in_sol)?; //1000 * 1e9  = 1000e9
+        let lamport_expanded = mul(U256::from(lamport), lamports_in_sol)?; //1e9*1e9 =  1e18
+
+        let first = mul(token_price_initial_expanded, token_price_initial_expanded)?;
+        let second = mul(
+            mul(token_price_incremental_expanded, lamport_expanded)?,
+            U256::from(2),
+        )?;
+        let third = mul(
+            mul(token_price_incremental, token_price_incremental)?,
+            mul(token_supply, token_supply)?,
+        )?;
+        let fourth = mul(
+            mul(token_price_incremental, token_price_initial_expanded)?,
+            mul(token_supply, U256::from(2))?,
+        )?;
+
+        // rounding the root down hands out fewer tokens
+        let square_root = sqrt(
+            add(add(first, second)?, add(third, fourth)?)?,
+            Rounding::Down,
+        );
+
+        to_u128(sub(
+            div(
+                sub(square_root, token_price_initial_expanded)?,
+                token_price_incremental,
+                Rounding::Down,
+            )?,
+            token_supply,
+        )?)
+    }
+
+    /**
+     * Calculate token sell value.
+     * It's an algorithm, hopefully we gave you the whitepaper with it in scientific notation;
+     * Some conversions occurred to prevent decimal errors or underflows / overflows in solidity code.
+     */
+    fn sell_quote(&self, supply: u128, tokens: u128) -> Result<u128> {
+        let lamports_in_sol = U256::from(LAMPORTS_IN_SOL);
+        let tokens = add(U256::from(tokens), lamports_in_sol)?;
+        let token_supply = add(U256::from(supply), lamports_in_sol)?;
+
+        let token_initial_price = U256::from(self.initial_price);
+        let token_incremental_price = U256::from(self.incremental_price);
+
+        let first = sub(
+            add(
+                mul(
+                    token_incremental_price,
+                    div(token_supply, lamports_in_sol, Rounding::Down)?,
+                )?,
+                token_initial_price,
+            )?,
+            token_incremental_price,
+        )?;
+
+        let second = sub(tokens, lamports_in_sol)?;
+        // rounding the discount up pays out less
+        let third = mul_div_u256(
+            sub(mul(tokens, tokens)?, tokens)?,
+            token_incremental_price,
+            mul(lamports_in_sol, U256::from(2))?,
+            Rounding::Up,
+        )?;
+
+        // underflow attempts BTFO
+        to_u128(div(
+            sub(mul(first, second)?, third)?,
+            lamports_in_sol,
+            Rounding::Down,
+        )?)
+    }
+
+    fn spot_price(&self, supply: u128) -> Result<u128> {
+        self.incremental_price
+            .checked_mul(supply / LAMPORTS_IN_SOL)
+            .and_then(|v| v.checked_add(self.initial_price))
+            .ok_or(MathError::Overflow)
+    }
+
+    fn max_supply(&self) -> Result<u128> {
+        Ok(MAX_SUPPLY)
+    }
+}
+
+/**
+ * price = initial_price * e^(rate * supply), with rate = incremental_price / initial_price
+ * Quotes integrate the price, rounding so the pool never pays out more than it took in.
+ * The exponent can't pass `MAX_EXP_WAD` (about 47.1), which caps the supply at
+ * 47.1 * initial_price / incremental_price whole tokens: 47,100 for a 100,000 / 100 pool.
+ */
+pub struct ExponentialCurve {
+    pub initial_price: u128,
+    pub incremental_price: u128,
+}
+
+impl ExponentialCurve {
+    // rate * supply, as a WAD
+    fn exponent(&self, supply: u128, rounding: Rounding) -> Result<u128> {
+        to_u128(mul_div_u256(
+            U256::from(supply),
+            mul(
+                U256::from(self.incremental_price),
+                U256::from(WAD / LAMPORTS_IN_SOL),
+            )?,
+            U256::from(self.initial_price),
+            rounding,
+        )?)
+    }
+
+    // Lamports needed to move the exponent from `from` to `to`: initial_price / rate * (e^to - e^from)
+    fn cost(&self, from: u128, to: u128) -> Result<u128> {
+        let growth = exp_wad(to)?
+            .checked_sub(exp_wad(from)?)
+            .ok_or(MathError::Underflow)?;
+        let initial_price = U256::from(self.initial_price);
+
+        to_u128(mul_div_u256(
+            mul(U256::from(growth), initial_price)?,
+            initial_price,
+            mul(U256::from(self.incremental_price), U256::from(WAD))?,
+            Rounding::Down,
+        )?)
+    }
+}
+
+impl BondingCurve for ExponentialCurve {
+    fn buy_quote(&self, supply: u128, lamports: u128) -> Result<u128> {
+        let from = self.exponent(supply, Rounding::Up)?;
+        // e^to = e^from + lamports * rate / initial_price
+        let growth = to_u128(mul_div_u256(
+            mul(U256::from(lamports), U256::from(WAD))?,
+            U256::from(self.incremental_price),
+            mul(
+                U256::from(self.initial_price),
+                U256::from(self.initial_price),
+            )?,
+            Rounding::Down,
+        )?)?;
+        let to = ln_wad(
+            exp_wad(from)?
+                .checked_add(growth)
+                .ok_or(MathError::Overflow)?,
+        )?;
+
+        // a purchase too small to move the exponent buys nothing
+        mul_div(
+            to.saturating_sub(from),
+            self.initial_price,
+            self.incremental_price
+                .checked_mul(WAD / LAMPORTS_IN_SOL)
+                .ok_or(MathError::Overflow)?,
+            Rounding::Down,
+        )
+    }
+
+    fn sell_quote(&self, supply: u128, tokens: u128) -> Result<u128> {
+        let remaining = supply.checked_sub(tokens).ok_or(MathError::Underflow)?;
+        self.cost(
+            self.exponent(remaining, Rounding::Up)?,
+            self.exponent(supply, Rounding::Down)?,
+        )
+    }
+
+    fn spot_price(&self, supply: u128) -> Result<u128> {
+        mul_div(
+            self.initial_price,
+            exp_wad(self.exponent(supply, Rounding::Down)?)?,
+            WAD,
+            Rounding::Down,
+        )
+    }
+
+    // the supply whose exponent, rounded up, is still `MAX_EXP_WAD`
+    fn max_supply(&self) -> Result<u128> {
+        let max_supply = mul_div(
+            MAX_EXP_WAD,
+            self.initial_price,
+            self.incremental_price
+                .checked_mul(WAD / LAMPORTS_IN_SOL)
+                .ok_or(MathError::Overflow)?,
+            Rounding::Down,
+        )?;
+        Ok(max_supply.min(MAX_SUPPLY))
+    }
+}
+
+/**
+ * x * y = k over virtual reserves.
+ * The virtual token reserve is sized so the curve starts at initial_price with a slope of incremental_price,
+ * which also caps the supply just below that reserve, 2 * initial_price / incremental_price whole tokens
+ * (2,000 for a 100,000 / 100 pool): the last tokens become infinitely expensive.
+ */
+pub struct ConstantProductCurve {
+    pub initial_price: u128,
+    pub incremental_price: u128,
+}
+
+impl ConstantProductCurve {
+    // Virtual token reserve at zero supply
+    fn token_reserve(&self) -> Result<u128> {
+        mul_div(
+            self.initial_price,
+            2 * LAMPORTS_IN_SOL,
+            self.incremental_price,
+            Rounding::Down,
+        )
+    }
+
+    // Virtual token reserve left at the current supply
+    fn token_reserve_at(&self, supply: u128) -> Result<U256> {
+        let token_reserve = self
+            .token_reserve()?
+            .checked_sub(supply)
+            .ok_or(MathError::Underflow)?;
+        if token_reserve == 0 {
+            return Err(MathError::DivisionByZero);
+        }
+        Ok(U256::from(token_reserve))
+    }
+
+    // Reserve invariant, lamports * tokens
+    fn invariant(&self) -> Result<U256> {
+        let token_reserve = U256::from(self.token_reserve()?);
+        mul_div_u256(
+            mul(U256::from(self.initial_price), token_reserve)?,
+            token_reserve,
+            U256::from(LAMPORTS_IN_SOL),
+            Rounding::Down,
+        )
+    }
+}
+
+impl BondingCurve for ConstantProductCurve {
+    fn buy_quote(&self, supply: u128, lamports: u128) -> Result<u128> {
+        let k = self.invariant()?;
+        let token_reserve = self.token_reserve_at(supply)?;
+        // the lamport side rounds down and the token side up, both leave more in the pool
+        let lamport_reserve = div(k, token_reserve, Rounding::Down)?;
+        let new_token_reserve = div(k, add(lamport_reserve, U256::from(lamports))?, Rounding::Up)?;
+
+        to_u128(token_reserve.saturating_sub(new_token_reserve))
+    }
+
+    fn sell_quote(&self, supply: u128, tokens: u128) -> Result<u128> {
+        let k = self.invariant()?;
+        let token_reserve = self.token_reserve_at(supply)?;
+        let lamport_reserve = div(k, token_reserve, Rounding::Down)?;
+        let new_lamport_reserve = div(k, add(token_reserve, U256::from(tokens))?, Rounding::Up)?;
+
+        to_u128(lamport_reserve.saturating_sub(new_lamport_reserve))
+    }
+
+    // one base unit of the virtual reserve has to stay
+    fn max_supply(&self) -> Result<u128> {
+        let max_supply = self
+            .token_reserve()?
+            .checked_sub(1)
+            .ok_or(MathError::Underflow)?;
+        Ok(max_supply.min(MAX_SUPPLY))
+    }
+
+    fn spot_price(&self, supply: u128) -> Result<u128> {
+        let token_reserve = self.token_reserve_at(supply)?;
+        to_u128(mul_div_u256(
+            self.invariant()?,
+            U256::from(LAMPORTS_IN_SOL),
+            mul(token_reserve, token_reserve)?,
+            Rounding::Down,
+        )?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::MAX_TOKEN_PRICE};
+
+    const ONE_TOKEN: u128 = LAMPORTS_IN_SOL;
+    // the default launch parameters
+    const INITIAL_PRICE: u128 = 100_000;
+    const INCREMENTAL_PRICE: u128 = 100;
+
+    fn curve(kind: CurveKind) -> Curve {
+        kind.curve(INITIAL_PRICE, INCREMENTAL_PRICE)
+    }
+
+    // a buy at `supply` followed by selling what it got back
+    fn assert_round_trip_keeps_lamports(kind: CurveKind, supply: u128, lamports: u128) {
+        let curve = curve(kind);
+        let tokens = curve.buy_quote(supply, lamports).unwrap();
+        let refund = curve.sell_quote(supply + tokens, tokens).unwrap();
+        assert!(
+            refund <= lamports,
+            "{kind:?} at {supply} paid {refund} back for {lamports}"
+        );
+    }
+
+    #[test]
+    fn round_trips_never_pay_out_more_than_they_took_in() {
+        let kinds = [
+            CurveKind::Linear,
+            CurveKind::Exponential,
+            CurveKind::ConstantProduct,
+        ];
+        for kind in kinds {
+            for supply in [0, ONE_TOKEN, 250 * ONE_TOKEN + 123, 1_500 * ONE_TOKEN] {
+                // the linear sell quote underflows on dust, below about a thousandth of a token
+                for lamports in [100_003, LAMPORTS_IN_SOL, 10 * LAMPORTS_IN_SOL + 7] {
+                    assert_round_trip_keeps_lamports(kind, supply, lamports);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn linear_supports_the_whole_supply() {
+        let curve = CurveKind::Linear.curve(MAX_TOKEN_PRICE, MAX_TOKEN_PRICE - 1);
+        assert_eq!(curve.max_supply(), Ok(MAX_SUPPLY));
+        assert!(curve.spot_price(MAX_SUPPLY).is_ok());
+        assert!(curve.sell_quote(MAX_SUPPLY, MAX_SUPPLY).is_ok());
+        assert!(curve.buy_quote(MAX_SUPPLY, MAX_LAMPORTS).is_ok());
+    }
+
+    #[test]
+    fn exponential_supply_ends_with_its_exponent() {
+        let curve = curve(CurveKind::Exponential);
+        let max_supply = curve.max_supply().unwrap();
+        // 47.1 * initial / incremental whole tokens
+        assert_eq!(max_supply / ONE_TOKEN, 47_134);
+
+        assert!(curve.spot_price(max_supply).is_ok());
+        assert!(curve.sell_quote(max_supply, ONE_TOKEN).is_ok());
+        assert!(curve.buy_quote(max_supply, 1).is_ok());
+        assert_eq!(
+            curve.spot_price(max_supply + ONE_TOKEN),
+            Err(MathError::Overflow)
+        );
+    }
+
+    #[test]
+    fn constant_product_supply_ends_below_its_reserve() {
+        let curve = curve(CurveKind::ConstantProduct);
+        let max_supply = curve.max_supply().unwrap();
+        // 2 * initial / incremental whole tokens
+        assert_eq!(max_supply, 2_000 * ONE_TOKEN - 1);
+
+        assert!(curve.spot_price(max_supply).is_ok());
+        assert!(curve.sell_quote(max_supply, ONE_TOKEN).is_ok());
+        assert!(curve.spot_price(max_supply + 1).is_err());
+    }
+}
@@ -0,0 +1,22 @@
+use core::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MathError {
+    Overflow,
+    Underflow,
+    DivisionByZero,
+    AmountTooLarge,
+}
+
+impl fmt::Display for MathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            MathError::Overflow => "Arithmetic overflow",
+            MathError::Underflow => "Arithmetic underflow",
+            MathError::DivisionByZero => "Division by zero",
+            MathError::AmountTooLarge => "Amount doesn't fit in a u64",
+        })
+    }
+}
+
+pub type Result<T> = core::result::Result<T, MathError>;
@@ -0,0 +1,259 @@
+use crate::error::{MathError, Result};
+
+#[allow(clippy::assign_op_pattern, clippy::manual_div_ceil)]
+mod uint {
+    ::uint::construct_uint! {
+        pub struct U256(4);
+    }
+}
+
+pub use uint::U256;
+
+/*==============================
+=        FIXED-POINT MATH      =
+==============================*/
+
+/**
+ * Token supply no curve goes past, in base units. Tokens are minted through the u64 SPL interface
+ * (about 18.4 billion whole tokens at 9 decimals). Only the linear curve reaches it, the others
+ * stop earlier depending on the prices, see `BondingCurve::max_supply`.
+ */
+pub const MAX_SUPPLY: u128 = u64::MAX as u128;
+
+/**
+ * Largest lamport amount a single quote supports, lamports move through the u64 system program.
+ * With prices capped at MAX_TOKEN_PRICE, the linear curve intermediates at these bounds stay below
+ * 2^200; the exponential and constant product curves are bounded by their own supply limits.
+ */
+pub const MAX_LAMPORTS: u128 = u64::MAX as u128;
+
+// Direction a result is rounded in, every call site picks the one that favours the pool
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rounding {
+    Down,
+    Up,
+}
+
+pub fn to_u128(x: U256) -> Result<u128> {
+    u128::try_from(x).map_err(|_| MathError::Overflow)
+}
+
+pub fn add(a: U256, b: U256) -> Result<U256> {
+    a.checked_add(b).ok_or(MathError::Overflow)
+}
+
+pub fn sub(a: U256, b: U256) -> Result<U256> {
+    a.checked_sub(b).ok_or(MathError::Underflow)
+}
+
+pub fn mul(a: U256, b: U256) -> Result<U256> {
+    a.checked_mul(b).ok_or(MathError::Overflow)
+}
+
+pub fn div(a: U256, b: U256, rounding: Rounding) -> Result<U256> {
+    if b.is_zero() {
+        return Err(MathError::DivisionByZero);
+    }
+    let (quotient, remainder) = a.div_mod(b);
+
+    if rounding == Rounding::Up && !remainder.is_zero() {
+        add(quotient, U256::one())
+    } else {
+        Ok(quotient)
+    }
+}
+
+/**
+ * a * b / denominator without losing the intermediate product.
+ */
+pub fn mul_div_u256(a: U256, b: U256, denominator: U256, rounding: Rounding) -> Result<U256> {
+    div(mul(a, b)?, denominator, rounding)
+}
+
+/**
+ * a * b / denominator for u128 operands, the product can't overflow in 256 bits.
+ */
+pub fn mul_div(a: u128, b: u128, denominator: u128, rounding: Rounding) -> Result<u128> {
+    to_u128(mul_div_u256(
+        U256::from(a),
+        U256::from(b),
+        U256::from(denominator),
+        rounding,
+    )?)
+}
+
+/**
+ * Integer square root, exact for perfect squares and rounded in the requested direction otherwise.
+ */
+pub fn sqrt(x: U256, rounding: Rounding) -> U256 {
+    if x.is_zero() {
+        return x;
+    }
+
+    // 2^ceil(bits / 2) is never below the root, so Newton's method only walks down from there
+    let mut y = U256::one() << x.bits().div_ceil(2);
+    loop {
+        let z = (y + x / y) >> 1;
+        if z >= y {
+            break;
+        }
+        y = z;
+    }
+
+    if rounding == Rounding::Up && y * y < x {
+        y + 1
+    } else {
+        y
+    }
+}
+
+// Fixed-point scale used by the exponential curve
+pub const WAD: u128 = 1_000_000_000_000_000_000;
+const LN_2_WAD: u128 = 693_147_180_559_945_309;
+// Largest exponent `exp_wad` takes, about 47.1: e^x must fit a u128 WAD
+pub const MAX_EXP_WAD: u128 = 68 * LN_2_WAD - 1;
+
+/**
+ * e^x for a WAD `x`, rounded down. Fails with `Overflow` above `MAX_EXP_WAD`.
+ */
+pub fn exp_wad(x: u128) -> Result<u128> {
+    if x > MAX_EXP_WAD {
+        return Err(MathError::Overflow);
+    }
+    let wad = U256::from(WAD);
+    let ln_2 = U256::from(LN_2_WAD);
+    let x = U256::from(x);
+
+    // e^x = 2^n * e^r with x = n * ln(2) + r, then a Taylor series for e^r (r < ln(2))
+    let n = div(x, ln_2, Rounding::Down)?;
+    let r = sub(x, mul(n, ln_2)?)?;
+
+    let mut term = wad;
+    let mut sum = wad;
+    let mut i = U256::one();
+    while !term.is_zero() {
+        term = mul_div_u256(term, r, mul(i, wad)?, Rounding::Down)?;
+        sum = add(sum, term)?;
+        i = add(i, U256::one())?;
+    }
+
+    to_u128(mul(sum, U256::one() << n.as_usize())?)
+}
+
+/**
+ * ln(x) for a WAD `x`, rounded down.
+ */
+pub fn ln_wad(x: u128) -> Result<u128> {
+    // only defined for x >= 1, which is all the curves ever need
+    if x < WAD {
+        return Err(MathError::Underflow);
+    }
+    let wad = U256::from(WAD);
+
+    // ln(x) = n * ln(2) + ln(m) with x = 2^n * m, then ln(m) = 2 * atanh((m - 1) / (m + 1)) for m in [1, 2)
+    let n = (x / WAD).ilog2();
+    let m = U256::from(x >> n);
+
+    let z = mul_div_u256(sub(m, wad)?, wad, add(m, wad)?, Rounding::Down)?;
+    let z_squared = mul_div_u256(z, z, wad, Rounding::Down)?;
+
+    let mut term = z;
+    let mut sum = U256::zero();
+    let mut i = U256::one();
+    while !term.is_zero() {
+        sum = add(sum, div(term, i, Rounding::Down)?)?;
+        term = mul_div_u256(term, z_squared, wad, Rounding::Down)?;
+        i = add(i, U256::from(2))?;
+    }
+
+    to_u128(add(
+        mul(U256::from(n), U256::from(LN_2_WAD))?,
+        mul(sum, U256::from(2))?,
+    )?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // within `tolerance` of `expected`, both WADs
+    fn assert_close(value: u128, expected: u128, tolerance: u128) {
+        assert!(
+            value.abs_diff(expected) <= tolerance,
+            "{value} isn't within {tolerance} of {expected}"
+        );
+    }
+
+    #[test]
+    fn sqrt_of_zero_and_one() {
+        for rounding in [Rounding::Down, Rounding::Up] {
+            assert_eq!(sqrt(U256::zero(), rounding), U256::zero());
+            assert_eq!(sqrt(U256::one(), rounding), U256::one());
+        }
+    }
+
+    #[test]
+    fn sqrt_of_perfect_squares_is_exact() {
+        for root in [2u128, 3, 1_000, 1 << 64, u128::MAX] {
+            let square = U256::from(root) * U256::from(root);
+            assert_eq!(sqrt(square, Rounding::Down), U256::from(root));
+            assert_eq!(sqrt(square, Rounding::Up), U256::from(root));
+        }
+    }
+
+    #[test]
+    fn sqrt_rounds_in_the_requested_direction() {
+        assert_eq!(sqrt(U256::from(8), Rounding::Down), U256::from(2));
+        assert_eq!(sqrt(U256::from(8), Rounding::Up), U256::from(3));
+        assert_eq!(sqrt(U256::from(10), Rounding::Down), U256::from(3));
+        assert_eq!(sqrt(U256::from(10), Rounding::Up), U256::from(4));
+    }
+
+    #[test]
+    fn sqrt_of_u256_max() {
+        assert_eq!(sqrt(U256::MAX, Rounding::Down), U256::from(u128::MAX));
+        assert_eq!(sqrt(U256::MAX, Rounding::Up), U256::one() << 128);
+    }
+
+    #[test]
+    fn exp_wad_matches_known_values() {
+        assert_eq!(exp_wad(0), Ok(WAD));
+        // e
+        assert_close(exp_wad(WAD).unwrap(), 2_718_281_828_459_045_235, 100);
+        // e^ln(2) = 2
+        assert_close(exp_wad(LN_2_WAD).unwrap(), 2 * WAD, 100);
+        // e^10
+        assert_close(
+            exp_wad(10 * WAD).unwrap(),
+            22_026_465_794_806_716_516_957,
+            1_000_000,
+        );
+    }
+
+    #[test]
+    fn exp_wad_stops_at_its_maximum() {
+        assert!(exp_wad(MAX_EXP_WAD).is_ok());
+        assert_eq!(exp_wad(MAX_EXP_WAD + 1), Err(MathError::Overflow));
+    }
+
+    #[test]
+    fn ln_wad_matches_known_values() {
+        assert_eq!(ln_wad(WAD), Ok(0));
+        assert_close(ln_wad(2 * WAD).unwrap(), LN_2_WAD, 100);
+        assert_close(ln_wad(2_718_281_828_459_045_235).unwrap(), WAD, 100);
+        // ln(1000)
+        assert_close(
+            ln_wad(1_000 * WAD).unwrap(),
+            6_907_755_278_982_137_052,
+            1_000,
+        );
+        assert_eq!(ln_wad(WAD - 1), Err(MathError::Underflow));
+    }
+
+    #[test]
+    fn ln_wad_inverts_exp_wad() {
+        for x in [WAD / 2, WAD, 5 * WAD, 40 * WAD] {
+            assert_close(ln_wad(exp_wad(x).unwrap()).unwrap(), x, 1_000);
+        }
+    }
+}
@@ -0,0 +1,20 @@
+#![no_std]
+
+pub mod curves;
+pub mod error;
+pub mod fixed;
+pub mod pool;
+pub mod vesting;
+
+pub use {
+    curves::{BondingCurve, Curve, CurveKind},
+    error::{MathError, Result},
+    pool::{Holder, Pool, PurchaseSplit, SaleSplit},
+    vesting::Grant,
+};
+
+pub const LAMPORTS_IN_SOL: u128 = 1_000_000_000;
+// Decimals the curves price in, a whole token is `LAMPORTS_IN_SOL` base units
+pub const TOKEN_DECIMALS: u8 = 9;
+// Highest initial price a pool may launch with, one SOL per whole token
+pub const MAX_TOKEN_PRICE: u128 = LAMPORTS_IN_SOL;
@@ -0,0 +1,272 @@
+use crate::{
+    curves::{BondingCurve, Curve, CurveKind},
+    error::{MathError, Result},
+    fixed::*,
+};
+
+/*==============================
+=          POOL MATH           =
+==============================*/
+
+/**
+ * Pricing and dividend state of a pool, mirroring the fields stored on the program account.
+ * Every quote the program makes goes through here, so off-chain callers get bit-identical results.
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Pool {
+    pub curve: CurveKind,
+    pub token_initial_price: u128,
+    pub token_incremental_price: u128,
+    pub token_supply: u128,
+    pub decimals: u8,
+    pub dividend_fee: u8,
+    pub magnitude: u64,
+    pub profit_per_share: u128,
+}
+
+// A holder's position on the dividend ledger
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Holder {
+    pub balance: u128,
+    pub payout: i128,
+}
+
+// How the lamports of a purchase are divided
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PurchaseSplit {
+    // shared among holders
+    pub dividends: u128,
+    // paid to a qualifying referrer, or added back to the dividends
+    pub referral_bonus: u128,
+    // spent on the curve
+    pub taxed_lamports: u128,
+}
+
+impl PurchaseSplit {
+    pub fn new(lamports: u128, dividend_fee: u8) -> Result<Self> {
+        let undivided_dividends = lamports
+            .checked_div(dividend_fee as u128)
+            .ok_or(MathError::DivisionByZero)?;
+        let referral_bonus = undivided_dividends / 3;
+
+        Ok(Self {
+            dividends: undivided_dividends - referral_bonus,
+            referral_bonus,
+            taxed_lamports: lamports - undivided_dividends,
+        })
+    }
+}
+
+// How the lamports of a sale, or the tokens of a transfer, are divided
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SaleSplit {
+    // shared among holders
+    pub dividends: u128,
+    // what the seller or recipient keeps
+    pub taxed: u128,
+}
+
+impl SaleSplit {
+    pub fn new(amount: u128, dividend_fee: u8) -> Result<Self> {
+        let dividends = amount
+            .checked_div(dividend_fee as u128)
+            .ok_or(MathError::DivisionByZero)?;
+
+        Ok(Self {
+            dividends,
+            taxed: amount - dividends,
+        })
+    }
+}
+
+// `a * b` as a signed amount on the payout ledger
+pub fn payout_of(a: u128, b: u128) -> Result<i128> {
+    a.checked_mul(b)
+        .and_then(|v| i128::try_from(v).ok())
+        .ok_or(MathError::Overflow)
+}
+
+impl Pool {
+    // The bonding curve picked at initialize time
+    pub fn curve(&self) -> Curve {
+        self.curve
+            .curve(self.token_initial_price, self.token_incremental_price)
+    }
+
+    // Largest supply the pool's curve supports, in base units
+    pub fn max_supply(&self) -> Result<u128> {
+        self.curve().max_supply()
+    }
+
+    /**
+     * Calculate Token price based on an amount of incoming lamport
+     * Fails with `AmountTooLarge` when the purchase would take the supply past `max_supply`.
+     */
+    pub fn lamport_to_tokens(&self, lamport: u128) -> Result<u128> {
+        let max_supply = self.max_supply()?;
+        if self.token_supply > max_supply || lamport > MAX_LAMPORTS {
+            return Err(MathError::AmountTooLarge);
+        }
+        let tokens = self.curve().buy_quote(self.token_supply, lamport)?;
+        if tokens > max_supply - self.token_supply {
+            return Err(MathError::AmountTooLarge);
+        }
+        Ok(tokens)
+    }
+
+    /**
+     * Calculate token sell value.
+     */
+    pub fn tokens_to_lamport(&self, tokens: u128) -> Result<u128> {
+        if self.token_supply > self.max_supply()? {
+            return Err(MathError::AmountTooLarge);
+        }
+        self.curve().sell_quote(self.token_supply, tokens)
+    }
+
+    /**
+     * Spread `dividends` lamports over the current supply, rounding down so the pool keeps the dust.
+     */
+    pub fn dividends_per_share(&self, dividends: u128) -> Result<u128> {
+        mul_div(
+            dividends,
+            self.magnitude as u128,
+            self.token_supply,
+            Rounding::Down,
+        )
+    }
+
+    /**
+     * Retrieve the dividend balance of any single address.
+     */
+    pub fn dividends_of(&self, holder: &Holder) -> Result<u128> {
+        let owed = payout_of(self.profit_per_share, holder.balance)?
+            .checked_sub(holder.payout)
+            .ok_or(MathError::Overflow)?
+            .checked_div(self.magnitude as i128)
+            .ok_or(MathError::DivisionByZero)?;
+
+        u128::try_from(owed).map_err(|_| MathError::Underflow)
+    }
+
+    /**
+     * Return the buy price of 1 individual token.
+     */
+    pub fn sell_price(&self) -> Result<u128> {
+        // our calculation relies on the token supply, so we need supply. Doh.
+        if self.token_supply == 0 {
+            self.curve()
+                .spot_price(0)?
+                .checked_sub(self.token_incremental_price)
+                .ok_or(MathError::Underflow)
+        } else {
+            let lamport = self.tokens_to_lamport(self.one_token()?)?;
+            Ok(SaleSplit::new(lamport, self.dividend_fee)?.taxed)
+        }
+    }
+
+    /**
+     * Return the sell price of 1 individual token.
+     */
+    pub fn buy_price(&self) -> Result<u128> {
+        // our calculation relies on the token supply, so we need supply. Doh.
+        if self.token_supply == 0 {
+            self.curve()
+                .spot_price(0)?
+                .checked_add(self.token_incremental_price)
+                .ok_or(MathError::Overflow)
+        } else {
+            let lamport = self.tokens_to_lamport(self.one_token()?)?;
+            Ok(SaleSplit::new(lamport, self.dividend_fee)?.taxed)
+        }
+    }
+
+    /**
+     * Function for the frontend to dynamically retrieve the price scaling of buy orders.
+     */
+    pub fn calculate_tokens_received(&self, lamport_to_spend: u128) -> Result<u128> {
+        if lamport_to_spend == 0 {
+            return Ok(0);
+        }
+        let taxed_lamport = SaleSplit::new(lamport_to_spend, self.dividend_fee)?.taxed;
+        self.lamport_to_tokens(taxed_lamport)
+    }
+
+    /**
+     * Function for the frontend to dynamically retrieve the price scaling of sell orders.
+     */
+    pub fn calculate_lamports_received(&self, token_to_sell: u128) -> Result<u128> {
+        if token_to_sell > self.token_supply || token_to_sell == 0 {
+            return Ok(0);
+        }
+        let lamport = self.tokens_to_lamport(token_to_sell)?;
+        Ok(SaleSplit::new(lamport, self.dividend_fee)?.taxed)
+    }
+
+    // Price of the next whole token at the current supply, before fees
+    pub fn spot_price(&self) -> Result<u128> {
+        self.curve().spot_price(self.token_supply)
+    }
+
+    // 1 whole token in base units
+    pub fn one_token(&self) -> Result<u128> {
+        10u128
+            .checked_pow(self.decimals as u32)
+            .ok_or(MathError::Overflow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool(curve: CurveKind, token_supply: u128) -> Pool {
+        Pool {
+            curve,
+            token_initial_price: 100_000,
+            token_incremental_price: 100,
+            token_supply,
+            decimals: 9,
+            dividend_fee: 10,
+            magnitude: 1 << 32,
+            profit_per_share: 0,
+        }
+    }
+
+    #[test]
+    fn purchases_stop_at_the_curve_supply() {
+        let max_supply = pool(CurveKind::Exponential, 0).max_supply().unwrap();
+        let near_max = pool(CurveKind::Exponential, max_supply - 100);
+
+        assert!(near_max.lamport_to_tokens(1_000).is_ok());
+        assert_eq!(
+            near_max.lamport_to_tokens(MAX_LAMPORTS),
+            Err(MathError::AmountTooLarge)
+        );
+    }
+
+    #[test]
+    fn constant_product_purchases_never_reach_the_reserve() {
+        let max_supply = pool(CurveKind::ConstantProduct, 0).max_supply().unwrap();
+        let near_max = pool(CurveKind::ConstantProduct, max_supply - 1_000_000_000);
+
+        let tokens = near_max.lamport_to_tokens(MAX_LAMPORTS).unwrap();
+        assert!(near_max.token_supply + tokens <= max_supply);
+    }
+
+    #[test]
+    fn quotes_past_the_curve_supply_are_rejected() {
+        for curve in [CurveKind::Exponential, CurveKind::ConstantProduct] {
+            let past_max = pool(curve, pool(curve, 0).max_supply().unwrap() + 1);
+
+            assert_eq!(
+                past_max.lamport_to_tokens(1),
+                Err(MathError::AmountTooLarge)
+            );
+            assert_eq!(
+                past_max.tokens_to_lamport(1),
+                Err(MathError::AmountTooLarge)
+            );
+        }
+    }
+}
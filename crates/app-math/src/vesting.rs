@@ -0,0 +1,63 @@
+use crate::{
+    error::{MathError, Result},
+    fixed::{mul_div, Rounding},
+};
+
+/*==============================
+=         VESTING MATH         =
+==============================*/
+
+/**
+ * Schedule of a vesting grant, mirroring the fields stored on its program account.
+ * Times are unix timestamps in seconds, so any clock can drive it.
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Grant {
+    pub total: u128,
+    pub start_time: i64,
+    // nothing unlocks before it
+    pub cliff_time: i64,
+    pub end_time: i64,
+    // seconds between releases, zero releases every second
+    pub release_interval: i64,
+}
+
+impl Grant {
+    /**
+     * Tokens unlocked at `now`: none before the cliff, all of them from the end, and in between
+     * the share of the seconds elapsed since the start, rounded down to whole release intervals.
+     */
+    pub fn vested_at(&self, now: i64) -> Result<u128> {
+        if now >= self.end_time {
+            return Ok(self.total);
+        }
+        if now < self.cliff_time || now <= self.start_time {
+            return Ok(0);
+        }
+
+        let mut elapsed = now
+            .checked_sub(self.start_time)
+            .ok_or(MathError::Underflow)?;
+        if self.release_interval > 0 {
+            elapsed -= elapsed % self.release_interval;
+        }
+        let duration = self
+            .end_time
+            .checked_sub(self.start_time)
+            .ok_or(MathError::Underflow)?;
+
+        mul_div(
+            self.total,
+            u128::try_from(elapsed).map_err(|_| MathError::Underflow)?,
+            u128::try_from(duration).map_err(|_| MathError::Underflow)?,
+            Rounding::Down,
+        )
+    }
+
+    // Tokens still locked at `now`
+    pub fn locked_at(&self, now: i64) -> Result<u128> {
+        self.total
+            .checked_sub(self.vested_at(now)?)
+            .ok_or(MathError::Underflow)
+    }
+}
@@ -3,4 +3,41 @@ use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
 pub const PROGRAM_SEED: &[u8] = b"program";
 pub const USER_SEED: &[u8] = b"users";
 pub const MINT_SEED: &[u8] = b"mint";
+pub const PROPOSAL_SEED: &[u8] = b"proposal";
+pub const ADMIN_REGISTRY_SEED: &[u8] = b"admins";
+pub const VESTING_SEED: &[u8] = b"vesting";
 pub const LAMPORTS_IN_SOL: u128 = LAMPORTS_PER_SOL as u128;
+pub use app_math::{MAX_TOKEN_PRICE, TOKEN_DECIMALS};
+// Supply, in base units, a curve must still quote at for its prices to be accepted
+pub const MIN_CURVE_SUPPLY: u128 = 1_000 * LAMPORTS_IN_SOL;
+
+// Most admins that can approve a single proposal, which bounds the threshold
+pub const MAX_APPROVALS: usize = 10;
+
+// Bounds of the admin registry
+pub const MAX_ADMINS: usize = 10;
+pub const MAX_PENDING_ADMINS: usize = 4;
+
+// Roles, as bits of `User::roles`. Admins grant them, holding one doesn't make you an admin
+pub const ROLE_PHASE_MANAGER: u8 = 1 << 0;
+pub const ROLE_DISTRIBUTOR: u8 = 1 << 1;
+pub const ROLE_PARAMETER_MANAGER: u8 = 1 << 2;
+pub const ROLE_METADATA_MANAGER: u8 = 1 << 3;
+pub const ALL_ROLES: u8 =
+    ROLE_PHASE_MANAGER | ROLE_DISTRIBUTOR | ROLE_PARAMETER_MANAGER | ROLE_METADATA_MANAGER;
+
+// Length of `distribute_token` grants that don't pick a schedule, released every second
+pub const DEFAULT_VESTING_DURATION: i64 = 100 * 24 * 60 * 60;
+// Grants a user can hold, all of them are passed along until the last one ends
+pub const MAX_VESTING_GRANTS: u64 = 16;
+
+// Longest metadata strings Metaplex accepts, which bound a metadata update proposal
+pub use mpl_token_metadata::{MAX_NAME_LENGTH, MAX_SYMBOL_LENGTH, MAX_URI_LENGTH};
+
+// `set_clock_offset`, the first 8 bytes of sha256("global:set_clock_offset") like anchor's own.
+// Only `feature-dev` builds handle it, see `dev`
+pub const SET_CLOCK_OFFSET_DISCRIMINATOR: [u8; 8] = [49, 111, 245, 49, 163, 96, 212, 248];
+
+// Seconds a queued staking requirement, fee or admin change waits before it can be executed
+pub const DEFAULT_TIMELOCK_DELAY: i64 = 2 * 24 * 60 * 60;
+pub const MAX_TIMELOCK_DELAY: i64 = 30 * 24 * 60 * 60;
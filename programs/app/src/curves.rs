@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+/*==============================
+=        BONDING CURVES        =
+==============================*/
+
+/**
+ * Pricing model picked at initialize time, the curves themselves live in the `app-math` crate.
+ */
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CurveKind {
+    Linear,
+    Exponential,
+    ConstantProduct,
+}
+
+impl From<CurveKind> for app_math::CurveKind {
+    fn from(kind: CurveKind) -> Self {
+        match kind {
+            CurveKind::Linear => app_math::CurveKind::Linear,
+            CurveKind::Exponential => app_math::CurveKind::Exponential,
+            CurveKind::ConstantProduct => app_math::CurveKind::ConstantProduct,
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use {
+    crate::{instructions::Admin, states::App},
+    anchor_lang::prelude::*,
+};
+
+// `#[program]` dispatches every instruction it lists in every build, so the clock override of
+// `feature-dev` builds is dispatched from the program's fallback instead, the way anchor would.
+pub mod instruction {
+    use {
+        crate::constants::SET_CLOCK_OFFSET_DISCRIMINATOR,
+        anchor_lang::{prelude::*, Discriminator, InstructionData},
+    };
+
+    #[derive(AnchorSerialize, AnchorDeserialize)]
+    pub struct SetClockOffset {
+        pub offset: i64,
+    }
+
+    impl Discriminator for SetClockOffset {
+        const DISCRIMINATOR: [u8; 8] = SET_CLOCK_OFFSET_DISCRIMINATOR;
+    }
+
+    impl InstructionData for SetClockOffset {}
+}
+
+// `data` is the instruction data past the discriminator
+pub fn set_clock_offset<'info>(
+    program_id: &Pubkey,
+    accounts: &'info [AccountInfo<'info>],
+    data: &[u8],
+) -> Result<()> {
+    msg!("Instruction: SetClockOffset");
+    let instruction::SetClockOffset { offset } =
+        instruction::SetClockOffset::deserialize(&mut &data[..])
+            .map_err(|_| ErrorCode::InstructionDidNotDeserialize)?;
+
+    let mut bumps = <Admin as anchor_lang::Bumps>::Bumps::default();
+    let mut reallocs = std::collections::BTreeSet::new();
+    let mut remaining_accounts: &[AccountInfo] = accounts;
+    let mut accounts = Admin::try_accounts(
+        program_id,
+        &mut remaining_accounts,
+        data,
+        &mut bumps,
+        &mut reallocs,
+    )?;
+
+    _set_clock_offset(
+        Context::new(program_id, &mut accounts, remaining_accounts, bumps),
+        offset,
+    )?;
+    accounts.exit(program_id)
+}
+
+fn _set_clock_offset(ctx: Context<Admin>, offset: i64) -> Result<()> {
+    let user_data_account = &mut ctx.accounts.user_data;
+    let program = &mut ctx.accounts.program_data;
+    let clock_offset_update = App::set_clock_offset(program, user_data_account, offset)?;
+
+    emit_cpi!(clock_offset_update);
+    Ok(())
+}
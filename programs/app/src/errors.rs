@@ -26,4 +26,99 @@ pub enum ProgramError {
     SelfReferral,
     #[msg("You can't transfer to yourself")]
     SelfTransfer,
+    #[msg("Dividend fee divisor must be greater than zero")]
+    InvalidDividendFee,
+    #[msg("Token prices are out of bounds")]
+    InvalidTokenPrice,
+    #[msg("Magnitude must be greater than zero")]
+    InvalidMagnitude,
+    #[msg("You would receive fewer tokens than the minimum requested")]
+    TokensBelowMinimum,
+    #[msg("You would receive fewer lamports than the minimum requested")]
+    LamportsBelowMinimum,
+    #[msg("Transaction is past its deadline")]
+    DeadlineExceeded,
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+    #[msg("Arithmetic underflow")]
+    MathUnderflow,
+    #[msg("Division by zero")]
+    DivisionByZero,
+    #[msg("Amount doesn't fit in a u64")]
+    AmountTooLarge,
+    #[msg("Admin changes need an approved proposal")]
+    ProposalRequired,
+    #[msg("Threshold must be between one and the approval limit")]
+    InvalidThreshold,
+    #[msg("Proposal was already executed or cancelled")]
+    ProposalNotActive,
+    #[msg("You already approved this proposal")]
+    AlreadyApproved,
+    #[msg("Proposal can't take more approvals")]
+    ApprovalLimitReached,
+    #[msg("Proposal doesn't have enough approvals")]
+    NotEnoughApprovals,
+    #[msg("Target account doesn't match the proposal")]
+    InvalidTargetAccount,
+    #[msg("This change is timelocked, it needs a proposal")]
+    TimelockRequired,
+    #[msg("Timelock delay must be between zero and thirty days")]
+    InvalidTimelockDelay,
+    #[msg("Proposal isn't queued yet")]
+    ProposalNotQueued,
+    #[msg("Proposal is still in its timelock")]
+    TimelockNotElapsed,
+    #[msg("The last admin can't be removed")]
+    LastAdmin,
+    #[msg("Removing this admin would leave fewer admins than the threshold")]
+    AdminCountBelowThreshold,
+    #[msg("User is already an admin")]
+    AlreadyAdmin,
+    #[msg("User was already granted admin")]
+    AlreadyNominated,
+    #[msg("You weren't granted admin")]
+    NotNominated,
+    #[msg("Admin registry is full")]
+    AdminRegistryFull,
+    #[msg("You don't have the role this needs")]
+    MissingRole,
+    #[msg("Unknown role")]
+    InvalidRoles,
+    #[msg("Vesting needs a positive duration, with the cliff and release interval within it")]
+    InvalidVestingSchedule,
+    #[msg("The user's vesting grants must be passed in grant order")]
+    MissingVestingGrants,
+    #[msg("User can't receive more vesting grants")]
+    VestingGrantLimit,
+    #[msg("Grant has nothing left to revoke")]
+    NothingToRevoke,
+    #[msg("Only feature-dev builds can move the clock")]
+    DevBuildOnly,
+    #[msg("Purchase exceeds what an ambassador can buy during the initial phase")]
+    AmbassadorCapExceeded,
+    #[msg("Token prices leave the curve too little supply")]
+    CurveSupplyTooLow,
+    #[msg("The ambassador cap must be positive and the quota at least the cap")]
+    InvalidAmbassadorQuota,
+    #[msg("The token must use 9 decimals, the unit its prices are quoted in")]
+    InvalidDecimals,
+    #[msg("Purchase would take the supply past what the curve can price")]
+    SupplyLimitExceeded,
+    #[msg("Name, symbol or uri is longer than the metadata allows")]
+    MetadataTooLong,
+    #[msg("This proposal is executed by the instruction made for its action")]
+    WrongExecuteInstruction,
+    #[msg("Purchase would take ambassadors past the initial phase quota")]
+    AmbassadorQuotaExceeded,
+}
+
+impl From<app_math::MathError> for ProgramError {
+    fn from(error: app_math::MathError) -> Self {
+        match error {
+            app_math::MathError::Overflow => ProgramError::MathOverflow,
+            app_math::MathError::Underflow => ProgramError::MathUnderflow,
+            app_math::MathError::DivisionByZero => ProgramError::DivisionByZero,
+            app_math::MathError::AmountTooLarge => ProgramError::AmountTooLarge,
+        }
+    }
 }
@@ -1,86 +1,253 @@
-use anchor_lang::prelude::*;
+use {crate::states::AdminAction, anchor_lang::prelude::*};
 
 /*==============================
 =            EVENTS            =
 ==============================*/
-pub fn on_token_purchase(
-    customer_address: Pubkey,
-    incoming_lamports: u128,
-    tokens_minted: u128,
-    referred_by: Pubkey,
-) -> bool {
-    // Emit a message for TokenPurchase event
-    msg!(
-        "TokenPurchase: Customer={}, Lamports={}, Tokens={}, ReferredBy={}",
-        customer_address,
-        incoming_lamports,
-        tokens_minted,
-        referred_by
-    );
-
-    true
-}
-
-pub fn on_token_sell(customer_address: Pubkey, tokens_burned: u128, lamports_earned: u128) -> bool {
-    // Emit a message for TokenSell event
-    msg!(
-        "TokenSell: Customer={}, Tokens={}, Lamports={}",
-        customer_address,
-        tokens_burned,
-        lamports_earned
-    );
-
-    true
-}
-
-pub fn on_reinvestment(
-    customer_address: Pubkey,
-    lamports_reinvested: u128,
-    tokens_minted: u128,
-) -> bool {
-    // Emit a message for Reinvestment event
-    msg!(
-        "Reinvestment: Customer={}, Lamports={}, Tokens={}",
-        customer_address,
-        lamports_reinvested,
-        tokens_minted
-    );
-
-    true
-}
-
-pub fn on_withdraw(customer_address: Pubkey, lamports_withdrawn: u128) -> bool {
-    // Emit a message for Withdraw event
-    msg!(
-        "Withdraw: Customer={}, Lamports={}",
-        customer_address,
-        lamports_withdrawn
-    );
-
-    true
-}
-
-pub fn on_skwizkey(
-    customer_address: Pubkey,
-    consumer_address: Pubkey,
-    lamport_used: u128,
-    bonus_received: u128,
-) -> bool {
-    // Emit a message for Skwizkey event
-    msg!(
-        "Skwizkey: Customer={}, Consumer={}, Lamports={}, Bonus={}",
-        customer_address,
-        consumer_address,
-        lamport_used,
-        bonus_received
-    );
-
-    true
-}
-
-pub fn on_transfer(from: Pubkey, to: Pubkey, tokens: u128) -> bool {
-    // Emit a message for Transfer event
-    msg!("Transfer: From={}, To={}, Tokens={}", from, to, tokens);
-
-    true
+// Emitted through `emit_cpi!` by the instruction handlers, so they survive truncated logs.
+// Trade events carry the pool state right after the trade, `price` being the spot price of the next whole token.
+// `seq` counts every event the program ever emitted and each `*nonce` counts the events that touched
+// that user's account, so an indexer can tell when it missed one.
+
+#[event]
+pub struct TokenPurchase {
+    pub customer: Pubkey,
+    pub incoming_lamports: u128,
+    pub tokens_minted: u128,
+    pub referred_by: Pubkey,
+    pub token_supply: u128,
+    pub profit_per_share: u128,
+    pub price: u128,
+    pub seq: u64,
+    pub nonce: u64,
+}
+
+#[event]
+pub struct TokenSell {
+    pub customer: Pubkey,
+    pub tokens_burned: u128,
+    pub lamports_earned: u128,
+    pub token_supply: u128,
+    pub profit_per_share: u128,
+    pub price: u128,
+    pub seq: u64,
+    pub nonce: u64,
+}
+
+#[event]
+pub struct Reinvestment {
+    pub customer: Pubkey,
+    pub lamports_reinvested: u128,
+    pub tokens_minted: u128,
+    pub token_supply: u128,
+    pub profit_per_share: u128,
+    pub price: u128,
+    pub seq: u64,
+    pub nonce: u64,
+}
+
+#[event]
+pub struct Withdrawal {
+    pub customer: Pubkey,
+    pub lamports_withdrawn: u128,
+    pub seq: u64,
+    pub nonce: u64,
+}
+
+// A referrer earned a bonus on someone else's purchase
+#[event]
+pub struct Skwizkey {
+    pub referrer: Pubkey,
+    pub customer: Pubkey,
+    pub lamports_used: u128,
+    pub bonus_received: u128,
+    pub seq: u64,
+    pub referrer_nonce: u64,
+}
+
+#[event]
+pub struct TokenTransfer {
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub tokens: u128,
+    pub token_supply: u128,
+    pub profit_per_share: u128,
+    pub price: u128,
+    pub seq: u64,
+    pub from_nonce: u64,
+    pub to_nonce: u64,
+}
+
+/*==============================
+=         ADMIN EVENTS         =
+==============================*/
+// Privileged state changes, with the value they replaced so monitoring can alert on them.
+
+// `admin` is the ambassador whose purchase filled the quota when the phase ends on its own
+#[event]
+pub struct InitialStageEnd {
+    pub admin: Pubkey,
+    pub was_initial_phase: bool,
+    pub seq: u64,
+}
+
+// Demoting an admin also clears every role they held
+#[event]
+pub struct AdministratorUpdate {
+    pub admin: Pubkey,
+    pub user: Pubkey,
+    pub old_status: bool,
+    pub new_status: bool,
+    // admins in the registry after the change
+    pub admin_count: u8,
+    pub seq: u64,
+    pub nonce: u64,
+}
+
+// Admin granted to `user`, who still has to accept it
+#[event]
+pub struct AdminNomination {
+    pub admin: Pubkey,
+    pub user: Pubkey,
+    pub seq: u64,
+}
+
+#[event]
+pub struct RolesUpdate {
+    pub admin: Pubkey,
+    pub user: Pubkey,
+    pub old_roles: u8,
+    pub new_roles: u8,
+    pub seq: u64,
+    pub nonce: u64,
+}
+
+#[event]
+pub struct AmbassadorUpdate {
+    pub admin: Pubkey,
+    pub user: Pubkey,
+    pub old_status: bool,
+    pub new_status: bool,
+    pub seq: u64,
+    pub nonce: u64,
+}
+
+#[event]
+pub struct StakingRequirementUpdate {
+    pub admin: Pubkey,
+    pub old_amount: u128,
+    pub new_amount: u128,
+    pub seq: u64,
+}
+
+// Locked tokens handed out by `distribute_token`, as the recipient's grant number `index`
+#[event]
+pub struct VestingGrant {
+    pub admin: Pubkey,
+    pub receipient: Pubkey,
+    pub amount: u128,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub cliff_time: i64,
+    pub release_interval: i64,
+    pub payout_adjustment: i128,
+    pub index: u64,
+    pub seq: u64,
+    pub admin_nonce: u64,
+    pub receipient_nonce: u64,
+}
+
+// Unvested tokens of a grant burned by `revoke_vesting`, their value is shared among the holders
+#[event]
+pub struct VestingRevocation {
+    pub admin: Pubkey,
+    pub receipient: Pubkey,
+    pub index: u64,
+    pub tokens_burned: u128,
+    pub lamports_redistributed: u128,
+    pub token_supply: u128,
+    pub profit_per_share: u128,
+    pub seq: u64,
+    pub nonce: u64,
+}
+
+#[event]
+pub struct MetadataUpdate {
+    pub admin: Pubkey,
+    pub old_name: String,
+    pub old_symbol: String,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seq: u64,
+}
+
+#[event]
+pub struct AdminThresholdUpdate {
+    pub admin: Pubkey,
+    pub old_threshold: u8,
+    pub new_threshold: u8,
+    pub seq: u64,
+}
+
+#[event]
+pub struct DividendFeeUpdate {
+    pub admin: Pubkey,
+    pub old_fee: u8,
+    pub new_fee: u8,
+    pub seq: u64,
+}
+
+#[event]
+pub struct TimelockDelayUpdate {
+    pub admin: Pubkey,
+    pub old_delay: i64,
+    pub new_delay: i64,
+    pub seq: u64,
+}
+
+// Only `feature-dev` builds can move the clock
+#[cfg(feature = "feature-dev")]
+#[event]
+pub struct ClockOffsetUpdate {
+    pub admin: Pubkey,
+    pub old_offset: i64,
+    pub new_offset: i64,
+    pub seq: u64,
+}
+
+/*==============================
+=        PROPOSAL EVENTS       =
+==============================*/
+
+#[event]
+pub struct ProposalCreation {
+    pub id: u64,
+    pub proposer: Pubkey,
+    pub action: AdminAction,
+    // when it can be executed, 0 until it has enough approvals
+    pub eta: i64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct ProposalApproval {
+    pub id: u64,
+    pub admin: Pubkey,
+    pub approvals: u8,
+    pub eta: i64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct ProposalExecution {
+    pub id: u64,
+    pub executor: Pubkey,
+    pub seq: u64,
+}
+
+#[event]
+pub struct ProposalCancellation {
+    pub id: u64,
+    pub admin: Pubkey,
+    pub seq: u64,
 }
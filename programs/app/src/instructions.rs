@@ -1,5 +1,5 @@
 use {
-    crate::{constants::*, states::*},
+    crate::{constants::*, curves::CurveKind, events::MetadataUpdate, states::*},
     anchor_lang::{prelude::*, solana_program::program::invoke_signed},
     anchor_spl::{
         associated_token::{AssociatedToken, ID as ASSOCIATED_TOKEN_ID},
@@ -8,9 +8,8 @@ use {
             spl_token_2022::{
                 extension::ExtensionType,
                 instruction::{
-                    initialize_mint2,
-                    initialize_non_transferable_mint,
-                    // initialize_permanent_delegate,
+                    initialize_mint2, initialize_non_transferable_mint,
+                    initialize_permanent_delegate,
                 },
                 state::Mint as MainMint,
             },
@@ -18,8 +17,8 @@ use {
         },
     },
     mpl_token_metadata::{
-        instructions::CreateCpiBuilder,
-        types::{CreateArgs, TokenStandard},
+        instructions::{CreateCpiBuilder, UpdateV1CpiBuilder},
+        types::{CreateArgs, Data, TokenStandard},
         ID as MPL_TOKEN_METADATA_ID,
     },
     std::str::FromStr,
@@ -44,7 +43,7 @@ pub struct Initialize<'info> {
         bump,
 		payer = admin,
 		space = ExtensionType::try_calculate_account_len::<MainMint>(&[
-        // ExtensionType::PermanentDelegate,
+        ExtensionType::PermanentDelegate,
         ExtensionType::NonTransferable,
         // ExtensionType::DefaultAccountState,
     ])
@@ -55,6 +54,8 @@ pub struct Initialize<'info> {
 
     #[account(init, seeds=[PROGRAM_SEED], bump, payer = admin, space = 8 + App::MAXIMUM_SIZE + 10)]
     pub program_data: Account<'info, App>,
+    #[account(init, seeds=[ADMIN_REGISTRY_SEED], bump, payer = admin, space = 8 + AdminRegistry::MAXIMUM_SIZE)]
+    pub admin_registry: Box<Account<'info, AdminRegistry>>,
     pub rent: Sysvar<'info, Rent>,
     /// CHECK: Sysvar instruction
     pub sysvar_instructions: UncheckedAccount<'info>,
@@ -66,8 +67,9 @@ pub struct Initialize<'info> {
     pub token_metadata_program: UncheckedAccount<'info>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
-#[instruction(lamports_to_send: u128, referred_by: Option<Pubkey>,)]
+#[instruction(lamports_to_send: u64, referred_by: Option<Pubkey>,)]
 pub struct Buy<'info> {
     #[account(
     	mut,
@@ -102,6 +104,7 @@ pub struct Buy<'info> {
     pub associated_token_program: Program<'info, AssociatedToken>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(amount_of_tokens: u128, update_payout_by: i128, receipient: Pubkey)]
 pub struct DistributeToken<'info> {
@@ -141,6 +144,14 @@ pub struct DistributeToken<'info> {
 		associated_token::token_program = token_program
 	)]
     pub receipient_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init,
+        payer = user,
+        space = 8 + Vesting::MAXIMUM_SIZE,
+        seeds = [VESTING_SEED, receipient.as_ref(), &receipient_data.vesting_count.to_le_bytes()],
+        bump,
+    )]
+    pub vesting: Box<Account<'info, Vesting>>,
     #[account(mut, seeds=[PROGRAM_SEED], bump)]
     pub program_data: Account<'info, App>,
 
@@ -151,6 +162,48 @@ pub struct DistributeToken<'info> {
     pub associated_token_program: Program<'info, AssociatedToken>,
 }
 
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RevokeVesting<'info> {
+    #[account(
+    	mut,
+        seeds = [MINT_SEED],
+        bump,
+    	mint::token_program = TOKEN_2022_ID,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub admin: Signer<'info>,
+    #[account(seeds=[USER_SEED, admin.key().as_ref()], bump)]
+    pub admin_data: Box<Account<'info, User>>,
+
+    #[account(
+        mut,
+        seeds = [VESTING_SEED, vesting.receipient.as_ref(), &vesting.index.to_le_bytes()],
+        bump,
+    )]
+    pub vesting: Box<Account<'info, Vesting>>,
+
+    /// CHECK: To Authority
+    #[account(address = vesting.receipient)]
+    pub receipient_info: AccountInfo<'info>,
+    #[account(mut, seeds=[USER_SEED, vesting.receipient.as_ref()], bump)]
+    pub receipient_data: Box<Account<'info, User>>,
+    #[account(
+		mut,
+		associated_token::mint = mint,
+		associated_token::authority = receipient_info,
+		associated_token::token_program = token_program
+	)]
+    pub receipient_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, seeds=[PROGRAM_SEED], bump)]
+    pub program_data: Account<'info, App>,
+
+    #[account(address = TOKEN_2022_ID)]
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[event_cpi]
 #[derive(Accounts)]
 pub struct Reinvest<'info> {
     #[account(
@@ -185,6 +238,7 @@ pub struct Reinvest<'info> {
     pub associated_token_program: Program<'info, AssociatedToken>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct Exit<'info> {
     #[account(
@@ -219,6 +273,7 @@ pub struct Exit<'info> {
     pub associated_token_program: Program<'info, AssociatedToken>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(to: Pubkey)]
 pub struct Transfer<'info> {
@@ -269,6 +324,7 @@ pub struct Transfer<'info> {
     pub associated_token_program: Program<'info, AssociatedToken>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct Withdraw<'info> {
     #[account(
@@ -301,6 +357,7 @@ pub struct Withdraw<'info> {
     pub associated_token_program: Program<'info, AssociatedToken>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct Sell<'info> {
     #[account(
@@ -333,6 +390,7 @@ pub struct Sell<'info> {
     pub associated_token_program: Program<'info, AssociatedToken>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct Admin<'info> {
     #[account(mut)]
@@ -345,6 +403,45 @@ pub struct Admin<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(name: String, symbol: String)]
+pub struct UpdateMetadata<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(seeds=[USER_SEED, admin.key().as_ref()], bump)]
+    pub admin_data: Box<Account<'info, User>>,
+
+    // grows when the new name and symbol don't fit, never shrinks
+    #[account(
+        mut,
+        seeds=[PROGRAM_SEED],
+        bump,
+        realloc = App::space_for(&name, &symbol).max(program_data.to_account_info().data_len()),
+        realloc::payer = admin,
+        realloc::zero = false,
+    )]
+    pub program_data: Account<'info, App>,
+    #[account(
+        seeds = [MINT_SEED],
+        bump,
+        mint::token_program = TOKEN_2022_ID,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    /// CHECK: Metaplex checks it is the metadata of the mint
+    #[account(mut)]
+    pub metadata: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+    /// CHECK: Sysvar instruction
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub sysvar_instructions: UncheckedAccount<'info>,
+    /// CHECK: account constraint checked in account trait
+    #[account(address = MPL_TOKEN_METADATA_ID)]
+    pub token_metadata_program: UncheckedAccount<'info>,
+}
+
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(user: Pubkey)]
 pub struct AdminSetter<'info> {
@@ -360,6 +457,168 @@ pub struct AdminSetter<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct AdministratorSetter<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(init_if_needed, seeds=[USER_SEED, admin.key().as_ref()], bump, payer = admin, space = 8 + User::MAXIMUM_SIZE)]
+    pub admin_data: Box<Account<'info, User>>,
+    #[account(init_if_needed, seeds=[USER_SEED, user.key().as_ref()], bump, payer = admin, space = 8 + User::MAXIMUM_SIZE)]
+    pub user_data: Box<Account<'info, User>>,
+
+    #[account(mut, seeds=[PROGRAM_SEED], bump)]
+    pub program_data: Account<'info, App>,
+    #[account(mut, seeds=[ADMIN_REGISTRY_SEED], bump)]
+    pub admin_registry: Box<Account<'info, AdminRegistry>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AcceptAdministrator<'info> {
+    pub user: Signer<'info>,
+    #[account(mut, seeds=[USER_SEED, user.key().as_ref()], bump)]
+    pub user_data: Box<Account<'info, User>>,
+
+    #[account(mut, seeds=[PROGRAM_SEED], bump)]
+    pub program_data: Account<'info, App>,
+    #[account(mut, seeds=[ADMIN_REGISTRY_SEED], bump)]
+    pub admin_registry: Box<Account<'info, AdminRegistry>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Propose<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(seeds=[USER_SEED, admin.key().as_ref()], bump)]
+    pub admin_data: Box<Account<'info, User>>,
+
+    #[account(mut, seeds=[PROGRAM_SEED], bump)]
+    pub program_data: Account<'info, App>,
+    #[account(init, seeds=[PROPOSAL_SEED, &program_data.proposal_count.to_le_bytes()], bump, payer = admin, space = 8 + Proposal::MAXIMUM_SIZE)]
+    pub proposal: Box<Account<'info, Proposal>>,
+    #[account(seeds=[ADMIN_REGISTRY_SEED], bump)]
+    pub admin_registry: Box<Account<'info, AdminRegistry>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ProposalVote<'info> {
+    pub admin: Signer<'info>,
+    #[account(seeds=[USER_SEED, admin.key().as_ref()], bump)]
+    pub admin_data: Box<Account<'info, User>>,
+
+    #[account(mut, seeds=[PROGRAM_SEED], bump)]
+    pub program_data: Account<'info, App>,
+    #[account(mut, seeds=[PROPOSAL_SEED, &proposal.id.to_le_bytes()], bump)]
+    pub proposal: Box<Account<'info, Proposal>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    #[account(mut)]
+    pub executor: Signer<'info>,
+
+    #[account(mut, seeds=[PROGRAM_SEED], bump)]
+    pub program_data: Account<'info, App>,
+    #[account(mut, seeds=[PROPOSAL_SEED, &proposal.id.to_le_bytes()], bump)]
+    pub proposal: Box<Account<'info, Proposal>>,
+    #[account(mut, seeds=[ADMIN_REGISTRY_SEED], bump)]
+    pub admin_registry: Box<Account<'info, AdminRegistry>>,
+    /// The user an administrator, ambassador or roles proposal applies to, created if they have
+    /// no account yet
+    #[account(init_if_needed, seeds=[USER_SEED, proposal.action.target().unwrap_or_default().as_ref()], bump, payer = executor, space = 8 + User::MAXIMUM_SIZE)]
+    pub target_data: Option<Box<Account<'info, User>>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteMetadataProposal<'info> {
+    #[account(mut)]
+    pub executor: Signer<'info>,
+
+    #[account(mut, seeds=[PROPOSAL_SEED, &proposal.id.to_le_bytes()], bump)]
+    pub proposal: Box<Account<'info, Proposal>>,
+    #[account(seeds=[ADMIN_REGISTRY_SEED], bump)]
+    pub admin_registry: Box<Account<'info, AdminRegistry>>,
+    // grows when the proposed name and symbol don't fit, never shrinks
+    #[account(
+        mut,
+        seeds=[PROGRAM_SEED],
+        bump,
+        realloc = proposal.action.app_space().max(program_data.to_account_info().data_len()),
+        realloc::payer = executor,
+        realloc::zero = false,
+    )]
+    pub program_data: Account<'info, App>,
+    #[account(
+        seeds = [MINT_SEED],
+        bump,
+        mint::token_program = TOKEN_2022_ID,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    /// CHECK: Metaplex checks it is the metadata of the mint
+    #[account(mut)]
+    pub metadata: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+    /// CHECK: Sysvar instruction
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub sysvar_instructions: UncheckedAccount<'info>,
+    /// CHECK: account constraint checked in account trait
+    #[account(address = MPL_TOKEN_METADATA_ID)]
+    pub token_metadata_program: UncheckedAccount<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteRevocationProposal<'info> {
+    pub executor: Signer<'info>,
+
+    #[account(mut, seeds=[PROPOSAL_SEED, &proposal.id.to_le_bytes()], bump)]
+    pub proposal: Box<Account<'info, Proposal>>,
+    #[account(seeds=[ADMIN_REGISTRY_SEED], bump)]
+    pub admin_registry: Box<Account<'info, AdminRegistry>>,
+    #[account(
+        mut,
+        seeds = [MINT_SEED],
+        bump,
+        mint::token_program = TOKEN_2022_ID,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    /// The grant the proposal revokes
+    #[account(
+        mut,
+        seeds = [VESTING_SEED, vesting.receipient.as_ref(), &vesting.index.to_le_bytes()],
+        bump,
+    )]
+    pub vesting: Box<Account<'info, Vesting>>,
+
+    /// CHECK: To Authority
+    #[account(address = vesting.receipient)]
+    pub receipient_info: AccountInfo<'info>,
+    #[account(mut, seeds=[USER_SEED, vesting.receipient.as_ref()], bump)]
+    pub receipient_data: Box<Account<'info, User>>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = receipient_info,
+        associated_token::token_program = token_program
+    )]
+    pub receipient_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, seeds=[PROGRAM_SEED], bump)]
+    pub program_data: Account<'info, App>,
+
+    #[account(address = TOKEN_2022_ID)]
+    pub token_program: Program<'info, Token2022>,
+}
+
 #[derive(Accounts)]
 pub struct ProgramReadOnly<'info> {
     pub program_data: Account<'info, App>,
@@ -377,6 +636,46 @@ pub struct InitTokenParams {
     pub symbol: String,
     pub uri: String,
     pub decimals: u8,
+    pub dividend_fee: u8,
+    pub token_initial_price: u128,
+    pub token_incremental_price: u128,
+    pub magnitude: u64,
+    pub staking_requirement: u128,
+    pub curve: CurveKind,
+    // lamports each ambassador can spend during the initial phase, and all of them before it ends
+    pub ambassador_max_purchase: u128,
+    pub ambassador_quota: u128,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Default)]
+pub struct TradeDeadline {
+    pub valid_until_slot: Option<u64>,
+    pub valid_until_ts: Option<i64>,
+}
+
+/**
+ * Unlock schedule of a `distribute_token` grant. Nothing unlocks until `cliff` seconds after
+ * the start and everything has after `duration` seconds. In between the unlocked part grows
+ * linearly, in steps of `release_interval` seconds or every second when it is zero.
+ */
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone)]
+pub struct VestingSchedule {
+    // the time of the grant when unset
+    pub start_time: Option<i64>,
+    pub cliff: i64,
+    pub duration: i64,
+    pub release_interval: i64,
+}
+
+impl Default for VestingSchedule {
+    fn default() -> Self {
+        VestingSchedule {
+            start_time: None,
+            cliff: 0,
+            duration: DEFAULT_VESTING_DURATION,
+            release_interval: 0,
+        }
+    }
 }
 
 pub fn _initialize(ctx: Context<Initialize>, params: InitTokenParams) -> Result<()> {
@@ -396,7 +695,8 @@ pub fn _initialize(ctx: Context<Initialize>, params: InitTokenParams) -> Result<
 
     let non_tf_ix = initialize_non_transferable_mint(&TOKEN_2022_ID, &mint)?;
 
-    // let pd_ix = initialize_permanent_delegate(&TOKEN_2022_ID, &mint, &mint)?;
+    // lets `revoke_vesting` burn unvested tokens without the holder's signature
+    let pd_ix = initialize_permanent_delegate(&TOKEN_2022_ID, &mint, &mint)?;
 
     // let das_ix = initialize_default_account_state(
     //     &TOKEN_2022_ID,
@@ -406,7 +706,7 @@ pub fn _initialize(ctx: Context<Initialize>, params: InitTokenParams) -> Result<
 
     let init = initialize_mint2(&TOKEN_2022_ID, &mint, &mint, Some(&mint), params.decimals)?;
 
-    // invoke_signed(&pd_ix, &[ctx.accounts.mint.to_account_info()], &signer)?;
+    invoke_signed(&pd_ix, &[ctx.accounts.mint.to_account_info()], &signer)?;
     invoke_signed(&non_tf_ix, &[ctx.accounts.mint.to_account_info()], &signer)?;
     // invoke_signed(&das_ix, &[ctx.accounts.mint.to_account_info()], &signer)?;
     invoke_signed(&init, &[ctx.accounts.mint.to_account_info()], &signer)?;
@@ -424,7 +724,7 @@ pub fn _initialize(ctx: Context<Initialize>, params: InitTokenParams) -> Result<
         .create_args(CreateArgs::V1 {
             name: params.name.clone(),
             symbol: params.symbol.clone(),
-            uri: params.uri,
+            uri: params.uri.clone(),
             is_mutable: true,
             token_standard: TokenStandard::Fungible,
             decimals: Some(params.decimals),
@@ -440,18 +740,19 @@ pub fn _initialize(ctx: Context<Initialize>, params: InitTokenParams) -> Result<
         })
         .invoke_signed(&signer)?;
 
-    App::initialize(
-        program,
-        admin_account,
-        admin_data_account,
-        params.name,
-        params.symbol,
-        params.decimals,
-    )?;
+    let registry = &mut ctx.accounts.admin_registry;
+    App::initialize(program, admin_account, admin_data_account, registry, params)?;
     Ok(())
 }
 
-pub fn _buy(ctx: Context<Buy>, lamports_to_send: u128, referred_by: Option<Pubkey>) -> Result<()> {
+pub fn _buy(
+    ctx: Context<Buy>,
+    lamports_to_send: u64,
+    referred_by: Option<Pubkey>,
+    min_tokens_out: u64,
+    deadline: TradeDeadline,
+) -> Result<()> {
+    ctx.accounts.program_data.check_deadline(&deadline)?;
     let program = &mut ctx.accounts.program_data;
     let buyer = &mut ctx.accounts.user;
     let token_balance = ctx.accounts.user_ata.amount;
@@ -463,13 +764,14 @@ pub fn _buy(ctx: Context<Buy>, lamports_to_send: u128, referred_by: Option<Pubke
     let token_program = ctx.accounts.token_program.to_account_info();
     let bump = ctx.bumps.mint;
 
-    App::buy(
+    let (purchase, skwizkey, stage_end) = App::buy(
         program,
         buyer,
         buyer_data_account,
         referred_by_data_account,
-        lamports_to_send,
+        lamports_to_send.into(),
         referred_by,
+        min_tokens_out.into(),
         system_program,
         token_program,
         mint,
@@ -477,10 +779,23 @@ pub fn _buy(ctx: Context<Buy>, lamports_to_send: u128, referred_by: Option<Pubke
         bump,
         token_balance,
     )?;
+
+    if let Some(skwizkey) = skwizkey {
+        emit_cpi!(skwizkey);
+    }
+    emit_cpi!(purchase);
+    if let Some(stage_end) = stage_end {
+        emit_cpi!(stage_end);
+    }
     Ok(())
 }
 
-pub fn _reinvest(ctx: Context<Reinvest>) -> Result<()> {
+pub fn _reinvest(
+    ctx: Context<Reinvest>,
+    min_tokens_out: Option<u64>,
+    deadline: TradeDeadline,
+) -> Result<()> {
+    ctx.accounts.program_data.check_deadline(&deadline)?;
     let program = &mut ctx.accounts.program_data;
     let user = &mut ctx.accounts.user;
     let user_data_account = &mut ctx.accounts.user_data;
@@ -490,20 +805,25 @@ pub fn _reinvest(ctx: Context<Reinvest>) -> Result<()> {
     let token_program = ctx.accounts.token_program.to_account_info();
     let bump = ctx.bumps.mint;
 
-    App::reinvest(
+    let (purchase, reinvestment) = App::reinvest(
         program,
         user,
         user_data_account,
+        min_tokens_out.unwrap_or_default().into(),
         system_program.clone(),
         token_program,
         mint,
         user_ata,
         bump,
     )?;
+
+    emit_cpi!(purchase);
+    emit_cpi!(reinvestment);
     Ok(())
 }
 
-pub fn _exit(ctx: Context<Exit>) -> Result<()> {
+pub fn _exit(ctx: Context<Exit>, min_lamports_out: u64, deadline: TradeDeadline) -> Result<()> {
+    ctx.accounts.program_data.check_deadline(&deadline)?;
     let program = &mut ctx.accounts.program_data;
     let user = &mut ctx.accounts.user;
     let user_data_account = &mut ctx.accounts.user_data;
@@ -512,19 +832,32 @@ pub fn _exit(ctx: Context<Exit>) -> Result<()> {
     let token_program = ctx.accounts.token_program.to_account_info();
     let bump = ctx.bumps.mint;
 
-    App::leave(
+    let (sale, withdrawal) = App::leave(
         program,
         user,
         user_data_account,
+        min_lamports_out.into(),
         token_program,
         mint,
         user_ata,
         bump,
+        ctx.remaining_accounts,
     )?;
+
+    if let Some(sale) = sale {
+        emit_cpi!(sale);
+    }
+    emit_cpi!(withdrawal);
     Ok(())
 }
 
-pub fn _transfer(ctx: Context<Transfer>, to: Pubkey, tokens_to_send: u128) -> Result<()> {
+pub fn _transfer(
+    ctx: Context<Transfer>,
+    to: Pubkey,
+    tokens_to_send: u64,
+    deadline: TradeDeadline,
+) -> Result<()> {
+    ctx.accounts.program_data.check_deadline(&deadline)?;
     let program = &mut ctx.accounts.program_data;
     let user = &mut ctx.accounts.user;
     let user_data_account = &mut ctx.accounts.user_data;
@@ -535,7 +868,7 @@ pub fn _transfer(ctx: Context<Transfer>, to: Pubkey, tokens_to_send: u128) -> Re
     let token_program = ctx.accounts.token_program.to_account_info();
     let bump = ctx.bumps.mint;
 
-    App::transfer(
+    let (withdrawal, token_transfer) = App::transfer(
         program,
         user,
         user_data_account,
@@ -543,11 +876,17 @@ pub fn _transfer(ctx: Context<Transfer>, to: Pubkey, tokens_to_send: u128) -> Re
         to,
         to_data_account,
         to_ata,
-        tokens_to_send,
+        tokens_to_send.into(),
         token_program,
         mint,
         bump,
+        ctx.remaining_accounts,
     )?;
+
+    if let Some(withdrawal) = withdrawal {
+        emit_cpi!(withdrawal);
+    }
+    emit_cpi!(token_transfer);
     Ok(())
 }
 
@@ -556,11 +895,19 @@ pub fn _withdraw(ctx: Context<Withdraw>) -> Result<()> {
     let user = &mut ctx.accounts.user;
     let user_data_account = &mut ctx.accounts.user_data;
 
-    App::withdraw(program, user, user_data_account, true)?;
+    let withdrawal = App::withdraw(program, user, user_data_account, true)?;
+
+    emit_cpi!(withdrawal);
     Ok(())
 }
 
-pub fn _sell(ctx: Context<Sell>, lamports_to_send: u128) -> Result<()> {
+pub fn _sell(
+    ctx: Context<Sell>,
+    lamports_to_send: u64,
+    min_lamports_out: u64,
+    deadline: TradeDeadline,
+) -> Result<()> {
+    ctx.accounts.program_data.check_deadline(&deadline)?;
     let program = &mut ctx.accounts.program_data;
     let user = &mut ctx.accounts.user;
     let user_data_account = &mut ctx.accounts.user_data;
@@ -569,16 +916,20 @@ pub fn _sell(ctx: Context<Sell>, lamports_to_send: u128) -> Result<()> {
     let token_program = ctx.accounts.token_program.to_account_info();
     let bump = ctx.bumps.mint;
 
-    App::sell(
+    let sale = App::sell(
         program,
         user,
         user_data_account,
-        lamports_to_send,
+        lamports_to_send.into(),
+        min_lamports_out.into(),
         token_program,
         mint,
         user_ata,
         bump,
+        ctx.remaining_accounts,
     )?;
+
+    emit_cpi!(sale);
     Ok(())
 }
 
@@ -587,8 +938,7 @@ pub fn _distribute_token(
     amount_of_tokens: u128,
     update_payout_by: i128,
     receipient: Pubkey,
-    s_timestamp: i64,
-    e_timestamp: i64,
+    schedule: VestingSchedule,
 ) -> Result<()> {
     let program = &mut ctx.accounts.program_data;
     let user = &mut ctx.accounts.user;
@@ -596,58 +946,348 @@ pub fn _distribute_token(
     let from_ata = ctx.accounts.from_ata.to_account_info();
     let receipient_data = &mut ctx.accounts.receipient_data;
     let receipient_ata = ctx.accounts.receipient_ata.to_account_info();
+    let vesting = &mut ctx.accounts.vesting;
     let mint = ctx.accounts.mint.to_account_info();
     let token_program = ctx.accounts.token_program.to_account_info();
     let bump = ctx.bumps.mint;
 
-    App::distribute_token(
+    let grant = App::distribute_token(
         program,
-        &user,
+        user,
         receipient,
         from_data_account,
         from_ata,
         receipient_data,
         receipient_ata,
+        vesting,
         amount_of_tokens,
         update_payout_by,
         token_program,
         mint,
         bump,
-        s_timestamp,
-        e_timestamp,
+        schedule,
+    )?;
+
+    emit_cpi!(grant);
+    Ok(())
+}
+
+pub fn _revoke_vesting(ctx: Context<RevokeVesting>) -> Result<()> {
+    let program = &mut ctx.accounts.program_data;
+    let admin_data_account = &mut ctx.accounts.admin_data;
+    let receipient_data = &mut ctx.accounts.receipient_data;
+    let receipient_ata = ctx.accounts.receipient_ata.to_account_info();
+    let vesting = &mut ctx.accounts.vesting;
+    let mint = ctx.accounts.mint.to_account_info();
+    let token_program = ctx.accounts.token_program.to_account_info();
+    let bump = ctx.bumps.mint;
+
+    let revocation = App::revoke_vesting(
+        program,
+        admin_data_account,
+        receipient_data,
+        receipient_ata,
+        vesting,
+        token_program,
+        mint,
+        bump,
+        ctx.remaining_accounts,
     )?;
 
+    emit_cpi!(revocation);
     Ok(())
 }
 
 // ADMIN FUNCTIONS
+
+// Emits the event of an admin action, which depends on the action applied
+macro_rules! emit_admin_action {
+    ($ctx:ident, $action:expr) => {{
+        let ctx = &$ctx;
+        match $action {
+            AdminActionEvent::InitialStageEnd(event) => emit_cpi!(event),
+            AdminActionEvent::AdminNomination(event) => emit_cpi!(event),
+            AdminActionEvent::AdministratorUpdate(event) => emit_cpi!(event),
+            AdminActionEvent::AmbassadorUpdate(event) => emit_cpi!(event),
+            AdminActionEvent::RolesUpdate(event) => emit_cpi!(event),
+            AdminActionEvent::StakingRequirementUpdate(event) => emit_cpi!(event),
+            AdminActionEvent::AdminThresholdUpdate(event) => emit_cpi!(event),
+            AdminActionEvent::DividendFeeUpdate(event) => emit_cpi!(event),
+            AdminActionEvent::TimelockDelayUpdate(event) => emit_cpi!(event),
+        }
+    }};
+}
+
 pub fn _disable_initial_stage(ctx: Context<Admin>) -> Result<()> {
     let user_data_account = &mut ctx.accounts.user_data;
     let program = &mut ctx.accounts.program_data;
-    App::disable_initial_stage(program, user_data_account)?;
+    let stage_end = App::disable_initial_stage(program, user_data_account)?;
+
+    emit_cpi!(stage_end);
     Ok(())
 }
 
-pub fn _set_administrator(ctx: Context<AdminSetter>, _user: Pubkey, status: bool) -> Result<()> {
+pub fn _set_administrator(
+    ctx: Context<AdministratorSetter>,
+    user: Pubkey,
+    status: bool,
+) -> Result<()> {
     let admin_data_account = &mut ctx.accounts.admin_data;
     let user_data_account = &mut ctx.accounts.user_data;
+    let registry = &mut ctx.accounts.admin_registry;
+    let program = &mut ctx.accounts.program_data;
+    let action = App::set_administrator(
+        program,
+        registry,
+        admin_data_account,
+        user_data_account,
+        user,
+        status,
+    )?;
+
+    emit_admin_action!(ctx, action);
+    Ok(())
+}
+
+pub fn _accept_administrator(ctx: Context<AcceptAdministrator>) -> Result<()> {
+    let user = &ctx.accounts.user;
+    let user_data_account = &mut ctx.accounts.user_data;
+    let registry = &mut ctx.accounts.admin_registry;
+    let program = &mut ctx.accounts.program_data;
+    let administrator_update =
+        App::accept_administrator(program, registry, user, user_data_account)?;
+
+    emit_cpi!(administrator_update);
+    Ok(())
+}
+
+pub fn _set_ambassador(ctx: Context<AdminSetter>, user: Pubkey, status: bool) -> Result<()> {
+    let admin_data_account = &mut ctx.accounts.admin_data;
+    let user_data_account = &mut ctx.accounts.user_data;
+    let program = &mut ctx.accounts.program_data;
+    let ambassador_update =
+        App::set_ambassador(program, admin_data_account, user_data_account, user, status)?;
+
+    emit_cpi!(ambassador_update);
+    Ok(())
+}
+
+pub fn _update_metadata_account(
+    ctx: Context<UpdateMetadata>,
+    name: String,
+    symbol: String,
+    uri: String,
+) -> Result<()> {
+    let admin_data_account = &mut ctx.accounts.admin_data;
     let program = &mut ctx.accounts.program_data;
-    App::set_administrator(program, admin_data_account, user_data_account, status)?;
+    let metadata_update = App::update_metadata_account(
+        program,
+        admin_data_account,
+        name.clone(),
+        symbol.clone(),
+        uri.clone(),
+    )?;
+
+    update_metadata(
+        &ctx.accounts.token_metadata_program,
+        &ctx.accounts.mint.to_account_info(),
+        ctx.bumps.mint,
+        &ctx.accounts.metadata,
+        &ctx.accounts.admin,
+        &ctx.accounts.system_program,
+        &ctx.accounts.sysvar_instructions,
+        &metadata_update,
+    )?;
+
+    emit_cpi!(metadata_update);
+    Ok(())
+}
+
+// Writes the name, symbol and uri of `update` to the metadata account
+fn update_metadata<'info>(
+    token_metadata_program: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    mint_bump: u8,
+    metadata: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    sysvar_instructions: &AccountInfo<'info>,
+    update: &MetadataUpdate,
+) -> Result<()> {
+    // the mint PDA is the update authority set in `_initialize`
+    let seeds = &[MINT_SEED, &[mint_bump]];
+    let signer = [&seeds[..]];
+
+    UpdateV1CpiBuilder::new(token_metadata_program)
+        .authority(mint)
+        .mint(mint)
+        .metadata(metadata)
+        .payer(payer)
+        .system_program(system_program)
+        .sysvar_instructions(sysvar_instructions)
+        .data(Data {
+            name: update.name.clone(),
+            symbol: update.symbol.clone(),
+            uri: update.uri.clone(),
+            // as set in `_initialize`
+            seller_fee_basis_points: 0,
+            creators: None,
+        })
+        .invoke_signed(&signer)?;
     Ok(())
 }
 
-pub fn _set_ambassador(ctx: Context<AdminSetter>, _user: Pubkey, status: bool) -> Result<()> {
+pub fn _set_roles(ctx: Context<AdminSetter>, user: Pubkey, roles: u8) -> Result<()> {
     let admin_data_account = &mut ctx.accounts.admin_data;
     let user_data_account = &mut ctx.accounts.user_data;
     let program = &mut ctx.accounts.program_data;
-    App::set_ambassador(program, admin_data_account, user_data_account, status)?;
+    let roles_update = App::set_roles(program, admin_data_account, user_data_account, user, roles)?;
+
+    emit_cpi!(roles_update);
     Ok(())
 }
 
 pub fn _set_staking_requirement(ctx: Context<Admin>, amount_of_tokens: u128) -> Result<()> {
     let user_data_account = &mut ctx.accounts.user_data;
     let program = &mut ctx.accounts.program_data;
-    App::set_staking_requirement(program, user_data_account, amount_of_tokens)?;
+    let requirement_update =
+        App::set_staking_requirement(program, user_data_account, amount_of_tokens)?;
+
+    emit_cpi!(requirement_update);
+    Ok(())
+}
+
+// Shifts the clock vesting, deadlines and proposals are checked against
+#[cfg(feature = "feature-dev")]
+pub fn _fallback<'info>(
+    program_id: &Pubkey,
+    accounts: &'info [AccountInfo<'info>],
+    data: &[u8],
+) -> Result<()> {
+    match data.strip_prefix(&SET_CLOCK_OFFSET_DISCRIMINATOR) {
+        Some(data) => crate::dev::set_clock_offset(program_id, accounts, data),
+        None => Err(ErrorCode::InstructionFallbackNotFound.into()),
+    }
+}
+
+// Production builds don't have the clock override, they tell its callers why it fails
+#[cfg(not(feature = "feature-dev"))]
+pub fn _fallback(_program_id: &Pubkey, _accounts: &[AccountInfo], data: &[u8]) -> Result<()> {
+    require!(
+        !data.starts_with(&SET_CLOCK_OFFSET_DISCRIMINATOR),
+        crate::errors::ProgramError::DevBuildOnly
+    );
+    Err(ErrorCode::InstructionFallbackNotFound.into())
+}
+
+// PROPOSAL INSTRUCTIONS
+pub fn _propose(ctx: Context<Propose>, action: AdminAction) -> Result<()> {
+    let admin = &ctx.accounts.admin;
+    let admin_data_account = &mut ctx.accounts.admin_data;
+    let proposal = &mut ctx.accounts.proposal;
+    let registry = &ctx.accounts.admin_registry;
+    let program = &mut ctx.accounts.program_data;
+    let creation = App::propose(
+        program,
+        registry,
+        admin,
+        admin_data_account,
+        proposal,
+        action,
+    )?;
+
+    emit_cpi!(creation);
+    Ok(())
+}
+
+pub fn _approve_proposal(ctx: Context<ProposalVote>) -> Result<()> {
+    let admin = &ctx.accounts.admin;
+    let admin_data_account = &mut ctx.accounts.admin_data;
+    let proposal = &mut ctx.accounts.proposal;
+    let program = &mut ctx.accounts.program_data;
+    let approval = App::approve_proposal(program, admin, admin_data_account, proposal)?;
+
+    emit_cpi!(approval);
+    Ok(())
+}
+
+pub fn _execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
+    let executor = &ctx.accounts.executor;
+    let proposal = &mut ctx.accounts.proposal;
+    let target_data = ctx.accounts.target_data.as_deref_mut();
+    let registry = &mut ctx.accounts.admin_registry;
+    let program = &mut ctx.accounts.program_data;
+    let (execution, action) =
+        App::execute_proposal(program, registry, executor, proposal, target_data)?;
+
+    emit_cpi!(execution);
+    emit_admin_action!(ctx, action);
+    Ok(())
+}
+
+pub fn _execute_metadata_proposal(ctx: Context<ExecuteMetadataProposal>) -> Result<()> {
+    let executor = &ctx.accounts.executor;
+    let proposal = &mut ctx.accounts.proposal;
+    let registry = &ctx.accounts.admin_registry;
+    let program = &mut ctx.accounts.program_data;
+    let (execution, metadata_update) =
+        App::execute_metadata_proposal(program, registry, executor, proposal)?;
+
+    update_metadata(
+        &ctx.accounts.token_metadata_program,
+        &ctx.accounts.mint.to_account_info(),
+        ctx.bumps.mint,
+        &ctx.accounts.metadata,
+        &ctx.accounts.executor,
+        &ctx.accounts.system_program,
+        &ctx.accounts.sysvar_instructions,
+        &metadata_update,
+    )?;
+
+    emit_cpi!(execution);
+    emit_cpi!(metadata_update);
+    Ok(())
+}
+
+pub fn _execute_revocation_proposal(ctx: Context<ExecuteRevocationProposal>) -> Result<()> {
+    let executor = &ctx.accounts.executor;
+    let proposal = &mut ctx.accounts.proposal;
+    let registry = &ctx.accounts.admin_registry;
+    let program = &mut ctx.accounts.program_data;
+    let receipient_data = &mut ctx.accounts.receipient_data;
+    let receipient_ata = ctx.accounts.receipient_ata.to_account_info();
+    let vesting = &mut ctx.accounts.vesting;
+    let mint = ctx.accounts.mint.to_account_info();
+    let token_program = ctx.accounts.token_program.to_account_info();
+    let bump = ctx.bumps.mint;
+
+    let (execution, revocation) = App::execute_revocation_proposal(
+        program,
+        registry,
+        executor,
+        proposal,
+        receipient_data,
+        receipient_ata,
+        vesting,
+        token_program,
+        mint,
+        bump,
+        ctx.remaining_accounts,
+    )?;
+
+    emit_cpi!(execution);
+    emit_cpi!(revocation);
+    Ok(())
+}
+
+pub fn _cancel_proposal(ctx: Context<ProposalVote>) -> Result<()> {
+    let admin = &ctx.accounts.admin;
+    let admin_data_account = &mut ctx.accounts.admin_data;
+    let proposal = &mut ctx.accounts.proposal;
+    let program = &mut ctx.accounts.program_data;
+    let cancellation = App::cancel_proposal(program, admin, admin_data_account, proposal)?;
+
+    emit_cpi!(cancellation);
     Ok(())
 }
 
@@ -655,33 +1295,35 @@ pub fn _set_staking_requirement(ctx: Context<Admin>, amount_of_tokens: u128) ->
 pub fn _my_dividends(ctx: Context<ReadOnly>, including_ref: bool) -> Result<u128> {
     let user_data_account = &ctx.accounts.user_data;
     let program = &mut ctx.accounts.program_data;
-    let value = App::my_dividends(program, user_data_account, including_ref);
-    Ok(value)
+    App::my_dividends(program, user_data_account, including_ref)
+}
+
+// `at` defaults to the current time
+pub fn _vested_balance(ctx: Context<ReadOnly>, at: Option<i64>) -> Result<u128> {
+    let user_data_account = &ctx.accounts.user_data;
+    let now = match at {
+        Some(at) => at,
+        None => ctx.accounts.program_data.now()?,
+    };
+    App::vested_balance(user_data_account, ctx.remaining_accounts, now)
 }
 
 pub fn _sell_price(ctx: Context<ProgramReadOnly>) -> Result<u128> {
-    let value = ctx.accounts.program_data.sell_price();
-    Ok(value)
+    ctx.accounts.program_data.sell_price()
 }
 
 pub fn _buy_price(ctx: Context<ProgramReadOnly>) -> Result<u128> {
-    let value = ctx.accounts.program_data.buy_price();
-    Ok(value)
+    ctx.accounts.program_data.buy_price()
 }
 
 pub fn _calculate_lamports_received(ctx: Context<ProgramReadOnly>, tokens: u128) -> Result<u128> {
-    let value = ctx
-        .accounts
+    ctx.accounts
         .program_data
-        .calculate_lamports_received(tokens);
-
-    Ok(value)
+        .calculate_lamports_received(tokens)
 }
 
 pub fn _calculate_tokens_received(ctx: Context<ProgramReadOnly>, lamports: u128) -> Result<u128> {
-    let value = ctx
-        .accounts
+    ctx.accounts
         .program_data
-        .calculate_tokens_received(lamports);
-    Ok(value)
+        .calculate_tokens_received(lamports)
 }
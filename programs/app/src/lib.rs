@@ -1,4 +1,9 @@
+#![allow(clippy::too_many_arguments)]
+
 pub mod constants;
+pub mod curves;
+#[cfg(feature = "feature-dev")]
+pub mod dev;
 pub mod errors;
 pub mod events;
 pub mod instructions;
@@ -6,7 +11,7 @@ pub mod states;
 pub mod utils;
 
 use {
-    crate::instructions::*,
+    crate::{instructions::*, states::AdminAction},
     anchor_lang::{prelude::*, AnchorDeserialize, AnchorSerialize},
 };
 
@@ -22,30 +27,46 @@ pub mod app {
 
     pub fn buy(
         ctx: Context<Buy>,
-        lamports_to_send: u128,
+        lamports_to_send: u64,
         referred_by: Option<Pubkey>,
+        min_tokens_out: u64,
+        deadline: TradeDeadline,
     ) -> Result<()> {
-        _buy(ctx, lamports_to_send, referred_by)
+        _buy(ctx, lamports_to_send, referred_by, min_tokens_out, deadline)
     }
 
-    pub fn reinvest(ctx: Context<Reinvest>) -> Result<()> {
-        _reinvest(ctx)
+    pub fn reinvest(
+        ctx: Context<Reinvest>,
+        min_tokens_out: Option<u64>,
+        deadline: TradeDeadline,
+    ) -> Result<()> {
+        _reinvest(ctx, min_tokens_out, deadline)
     }
 
-    pub fn exit(ctx: Context<Exit>) -> Result<()> {
-        _exit(ctx)
+    pub fn exit(ctx: Context<Exit>, min_lamports_out: u64, deadline: TradeDeadline) -> Result<()> {
+        _exit(ctx, min_lamports_out, deadline)
     }
 
-    pub fn transfer(ctx: Context<Transfer>, to: Pubkey, lamports_to_send: u128) -> Result<()> {
-        _transfer(ctx, to, lamports_to_send)
+    pub fn transfer(
+        ctx: Context<Transfer>,
+        to: Pubkey,
+        lamports_to_send: u64,
+        deadline: TradeDeadline,
+    ) -> Result<()> {
+        _transfer(ctx, to, lamports_to_send, deadline)
     }
 
     pub fn withdraw(ctx: Context<Withdraw>) -> Result<()> {
         _withdraw(ctx)
     }
 
-    pub fn sell(ctx: Context<Sell>, lamports_to_send: u128) -> Result<()> {
-        _sell(ctx, lamports_to_send)
+    pub fn sell(
+        ctx: Context<Sell>,
+        lamports_to_send: u64,
+        min_lamports_out: u64,
+        deadline: TradeDeadline,
+    ) -> Result<()> {
+        _sell(ctx, lamports_to_send, min_lamports_out, deadline)
     }
 
     // Admin instructions
@@ -58,38 +79,99 @@ pub mod app {
         amount_of_tokens: u128,
         update_payout_by: i128,
         receipient: Pubkey,
+        schedule: VestingSchedule,
     ) -> Result<()> {
-        let clock: Clock = Clock::get()?;
-        let a_day = 86400;
-        let s_timestamp: i64 = clock.unix_timestamp;
-        let e_timestamp: i64 = s_timestamp + (a_day * 100);
         _distribute_token(
             ctx,
             amount_of_tokens,
             update_payout_by,
             receipient,
-            s_timestamp,
-            e_timestamp,
+            schedule,
         )
     }
 
-    pub fn set_administrator(ctx: Context<AdminSetter>, user: Pubkey, status: bool) -> Result<()> {
+    pub fn revoke_vesting(ctx: Context<RevokeVesting>) -> Result<()> {
+        _revoke_vesting(ctx)
+    }
+
+    pub fn set_administrator(
+        ctx: Context<AdministratorSetter>,
+        user: Pubkey,
+        status: bool,
+    ) -> Result<()> {
         _set_administrator(ctx, user, status)
     }
 
+    pub fn accept_administrator(ctx: Context<AcceptAdministrator>) -> Result<()> {
+        _accept_administrator(ctx)
+    }
+
     pub fn set_ambassador(ctx: Context<AdminSetter>, user: Pubkey, status: bool) -> Result<()> {
         _set_ambassador(ctx, user, status)
     }
 
+    pub fn update_metadata_account(
+        ctx: Context<UpdateMetadata>,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        _update_metadata_account(ctx, name, symbol, uri)
+    }
+
+    pub fn set_roles(ctx: Context<AdminSetter>, user: Pubkey, roles: u8) -> Result<()> {
+        _set_roles(ctx, user, roles)
+    }
+
     pub fn set_staking_requirement(ctx: Context<Admin>, amount_of_tokens: u128) -> Result<()> {
         _set_staking_requirement(ctx, amount_of_tokens)
     }
 
+    // Multisig proposal instructions
+    pub fn propose(ctx: Context<Propose>, action: AdminAction) -> Result<()> {
+        _propose(ctx, action)
+    }
+
+    pub fn approve_proposal(ctx: Context<ProposalVote>) -> Result<()> {
+        _approve_proposal(ctx)
+    }
+
+    pub fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
+        _execute_proposal(ctx)
+    }
+
+    // Executes a metadata update proposal, which also needs the metadata accounts
+    pub fn execute_metadata_proposal(ctx: Context<ExecuteMetadataProposal>) -> Result<()> {
+        _execute_metadata_proposal(ctx)
+    }
+
+    // Executes a revocation proposal, which also needs the token accounts
+    pub fn execute_revocation_proposal(ctx: Context<ExecuteRevocationProposal>) -> Result<()> {
+        _execute_revocation_proposal(ctx)
+    }
+
+    pub fn cancel_proposal(ctx: Context<ProposalVote>) -> Result<()> {
+        _cancel_proposal(ctx)
+    }
+
+    // Instructions `#[program]` doesn't list, the clock override of `feature-dev` builds
+    pub fn fallback<'info>(
+        program_id: &Pubkey,
+        accounts: &'info [AccountInfo<'info>],
+        data: &[u8],
+    ) -> Result<()> {
+        _fallback(program_id, accounts, data)
+    }
+
     // Read only instructions
     pub fn my_dividends(ctx: Context<ReadOnly>, including_ref: bool) -> Result<u128> {
         _my_dividends(ctx, including_ref)
     }
 
+    pub fn vested_balance(ctx: Context<ReadOnly>, at: Option<i64>) -> Result<u128> {
+        _vested_balance(ctx, at)
+    }
+
     pub fn sell_price(ctx: Context<ProgramReadOnly>) -> Result<u128> {
         _sell_price(ctx)
     }
@@ -1,91 +1,179 @@
 use {
-    crate::constants::{LAMPORTS_IN_SOL, MINT_SEED},
+    crate::constants::{
+        ALL_ROLES, DEFAULT_TIMELOCK_DELAY, MAX_ADMINS, MAX_APPROVALS, MAX_NAME_LENGTH,
+        MAX_PENDING_ADMINS, MAX_SYMBOL_LENGTH, MAX_TIMELOCK_DELAY, MAX_TOKEN_PRICE, MAX_URI_LENGTH,
+        MAX_VESTING_GRANTS, MINT_SEED, MIN_CURVE_SUPPLY, ROLE_DISTRIBUTOR, ROLE_METADATA_MANAGER,
+        ROLE_PARAMETER_MANAGER, ROLE_PHASE_MANAGER, TOKEN_DECIMALS,
+    },
+    crate::curves::CurveKind,
     crate::errors::ProgramError,
     crate::events::*,
-    crate::utils::*,
+    crate::instructions::{InitTokenParams, TradeDeadline, VestingSchedule},
+    crate::utils::to_u64,
     anchor_lang::{
         prelude::*,
         solana_program::{program::invoke, system_instruction::transfer},
         AnchorDeserialize, AnchorSerialize,
     },
     anchor_spl::token_2022::{burn, mint_to, Burn, MintTo},
+    app_math::{BondingCurve, Grant, Holder, Pool, PurchaseSplit, SaleSplit},
 };
 
 #[account]
 pub struct User {
-    authority: Pubkey,
-    balance: u128,
-    referred_balance: u128,
-    is_admin: bool,
-    is_amb: bool,
-    referred_by: Pubkey,
-    payout: i128,
-    usable_locked: u128,
-    total_locked: u128,
-    locked_starttime: i64,
-    locked_endtime: i64,
+    pub authority: Pubkey,
+    pub balance: u128,
+    pub referred_balance: u128,
+    pub is_admin: bool,
+    pub is_amb: bool,
+    pub referred_by: Pubkey,
+    pub payout: i128,
+    // grants are numbered from zero, the next one gets this index
+    pub vesting_count: u64,
+    // latest end of the user's grants, after which none of them lock anything
+    pub vesting_end_time: i64,
+    pub nonce: u64,
+    // bits of the `ROLE_*` constants
+    pub roles: u8,
+    // lamports spent as an ambassador during the initial phase
+    pub ambassador_purchased: u128,
 }
 
 // Helper functions
 impl User {
-    fn actually_locked(&mut self) -> u128 {
-        let ten_days = 86400 * 10;
-        let clock: Clock = Clock::get().unwrap();
-        let now = clock.unix_timestamp;
-        if now >= self.locked_endtime {
-            self.usable_locked = self.total_locked;
-        } else {
-            let days_gone_in_tens = (now - self.locked_starttime) / ten_days;
-            let amount = (self.total_locked / 10) * days_gone_in_tens as u128;
-            if amount > self.usable_locked && amount <= self.total_locked {
-                self.usable_locked = amount;
-            }
+    /**
+     * Tokens the user's vesting grants, passed in grant order, still lock at `now`.
+     * Once every grant has ended they aren't needed.
+     */
+    fn locked_balance(&self, grants: &[AccountInfo], now: i64) -> Result<u128> {
+        if now >= self.vesting_end_time {
+            return Ok(0);
+        }
+
+        let mut locked: u128 = 0;
+        for grant in self.load_grants(grants)? {
+            locked = locked
+                .checked_add(grant.locked_at(now)?)
+                .ok_or(ProgramError::MathOverflow)?;
         }
+        Ok(locked)
+    }
 
-        self.total_locked - self.usable_locked
+    // Reads the user's grants, which must be passed in grant order
+    fn load_grants(&self, grants: &[AccountInfo]) -> Result<Vec<Vesting>> {
+        let grants = usize::try_from(self.vesting_count)
+            .ok()
+            .and_then(|count| grants.get(..count))
+            .ok_or(ProgramError::MissingVestingGrants)?;
+        grants
+            .iter()
+            .enumerate()
+            .map(|(index, info)| {
+                require_keys_eq!(*info.owner, crate::ID, ProgramError::MissingVestingGrants);
+                let grant = Vesting::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+                require!(
+                    grant.receipient == self.authority && grant.index == index as u64,
+                    ProgramError::MissingVestingGrants
+                );
+                Ok(grant)
+            })
+            .collect()
+    }
+
+    /**
+     * Recomputes `vesting_end_time` once `revoked` was cut short. Its account isn't written back
+     * yet, so the copy among `grants` is skipped for `revoked` itself.
+     */
+    fn update_vesting_end_time(&mut self, grants: &[AccountInfo], revoked: &Vesting) -> Result<()> {
+        let mut end_time = revoked.end_time;
+        for grant in self.load_grants(grants)? {
+            if grant.index != revoked.index {
+                end_time = end_time.max(grant.end_time);
+            }
+        }
+        self.vesting_end_time = end_time;
+        Ok(())
     }
 
     fn has_admin_rights(&mut self) -> bool {
         self.is_admin
     }
 
+    fn has_role(&mut self, role: u8) -> bool {
+        self.roles & role == role
+    }
+
     fn has_balance(&mut self) -> bool {
         self.balance > 0
     }
 
-    fn usable_balance(&mut self) -> u128 {
-        self.balance - self.actually_locked()
+    fn usable_balance(&self, grants: &[AccountInfo], now: i64) -> Result<u128> {
+        // a balance below the locked amount leaves nothing usable
+        Ok(self
+            .balance
+            .saturating_sub(self.locked_balance(grants, now)?))
     }
 
-    fn has_balance_upto(&mut self, amount: u128, include_locked: bool) -> bool {
-        if include_locked {
-            return self.balance > amount;
-        }
-        self.balance - self.actually_locked() >= amount
+    // Locked tokens included
+    fn has_balance_upto(&mut self, amount: u128) -> bool {
+        self.balance > amount
     }
 
-    fn increase_balance_by(&mut self, amount: u128) {
-        self.balance += amount;
+    fn has_usable_balance_upto(
+        &self,
+        amount: u128,
+        grants: &[AccountInfo],
+        now: i64,
+    ) -> Result<bool> {
+        Ok(self.usable_balance(grants, now)? >= amount)
+    }
+
+    fn increase_balance_by(&mut self, amount: u128) -> Result<()> {
+        self.balance = self
+            .balance
+            .checked_add(amount)
+            .ok_or(ProgramError::MathOverflow)?;
+        Ok(())
     }
 
-    fn decrease_balance_by(&mut self, amount: u128) {
-        self.balance -= amount;
+    fn decrease_balance_by(&mut self, amount: u128) -> Result<()> {
+        self.balance = self
+            .balance
+            .checked_sub(amount)
+            .ok_or(ProgramError::InsufficientBalance)?;
+        Ok(())
     }
 
-    fn increase_payout_by(&mut self, amount: i128) {
-        self.payout += amount;
+    fn increase_payout_by(&mut self, amount: i128) -> Result<()> {
+        self.payout = self
+            .payout
+            .checked_add(amount)
+            .ok_or(ProgramError::MathOverflow)?;
+        Ok(())
     }
 
-    fn decrease_payout_by(&mut self, amount: i128) {
-        self.payout -= amount;
+    fn decrease_payout_by(&mut self, amount: i128) -> Result<()> {
+        self.payout = self
+            .payout
+            .checked_sub(amount)
+            .ok_or(ProgramError::MathUnderflow)?;
+        Ok(())
     }
 
-    fn increase_referred_balance_by(&mut self, amount: u128) {
-        self.referred_balance += amount;
+    fn increase_referred_balance_by(&mut self, amount: u128) -> Result<()> {
+        self.referred_balance = self
+            .referred_balance
+            .checked_add(amount)
+            .ok_or(ProgramError::MathOverflow)?;
+        Ok(())
     }
 
-    fn decrease_referred_balance_by(&mut self, amount: u128) {
-        self.referred_balance -= amount;
+    fn decrease_referred_balance_by(&mut self, amount: u128) -> Result<()> {
+        self.referred_balance = self
+            .referred_balance
+            .checked_sub(amount)
+            .ok_or(ProgramError::MathUnderflow)?;
+        Ok(())
     }
 
     fn update_admin_status(&mut self, status: bool) {
@@ -95,27 +183,253 @@ impl User {
     fn update_amb_status(&mut self, status: bool) {
         self.is_amb = status;
     }
+
+    fn update_roles(&mut self, roles: u8) {
+        self.roles = roles;
+    }
+
+    // Counts the events that touched this account
+    fn next_nonce(&mut self) -> Result<u64> {
+        self.nonce = self
+            .nonce
+            .checked_add(1)
+            .ok_or(ProgramError::MathOverflow)?;
+        Ok(self.nonce)
+    }
 }
 
 impl User {
-    pub const MAXIMUM_SIZE: usize = 32 + 16 + 16 + 1 + 1 + 32 + 16 + 16 + 16 + 8 + 8;
+    pub const MAXIMUM_SIZE: usize = 32 + 16 + 16 + 1 + 1 + 32 + 16 + 8 + 8 + 8 + 1 + 16;
+}
+
+/**
+ * Tokens locked by one `distribute_token` grant, a PDA of `[vesting, receipient, index]`.
+ */
+#[account]
+pub struct Vesting {
+    pub receipient: Pubkey,
+    pub index: u64,
+    pub total_locked: u128,
+    pub start_time: i64,
+    pub end_time: i64,
+    // nothing unlocks before it
+    pub cliff_time: i64,
+    // seconds between releases, zero releases every second
+    pub release_interval: i64,
+}
+
+impl Vesting {
+    pub const MAXIMUM_SIZE: usize = 32 + 8 + 16 + 8 + 8 + 8 + 8;
+
+    // The schedule as the shared vesting math sees it
+    pub fn grant(&self) -> Grant {
+        Grant {
+            total: self.total_locked,
+            start_time: self.start_time,
+            cliff_time: self.cliff_time,
+            end_time: self.end_time,
+            release_interval: self.release_interval,
+        }
+    }
+
+    // Part of the grant still locked at `now`
+    fn locked_at(&self, now: i64) -> Result<u128> {
+        Ok(self.grant().locked_at(now).map_err(ProgramError::from)?)
+    }
 }
 
 #[account]
 pub struct App {
-    name: String,
-    symbol: String,
-    decimals: u8,
-    dividend_fee: u8,
-    token_initial_price: u128,
-    token_incremental_price: u128,
-    contract_balance: u128,
-    token_supply: u128,
-    magnitude: u64,
-    staking_requirement: u128,
-    profit_per_share: u128,
-    is_initialized: bool,
-    is_initial_phase: bool,
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+    pub dividend_fee: u8,
+    pub token_initial_price: u128,
+    pub token_incremental_price: u128,
+    pub curve: CurveKind,
+    pub contract_balance: u128,
+    pub token_supply: u128,
+    pub magnitude: u64,
+    pub staking_requirement: u128,
+    pub profit_per_share: u128,
+    pub is_initialized: bool,
+    pub is_initial_phase: bool,
+    pub event_seq: u64,
+    pub admin_threshold: u8,
+    pub proposal_count: u64,
+    pub timelock_delay: i64,
+    // lamports each ambassador can spend during the initial phase
+    pub ambassador_max_purchase: u128,
+    // lamports all ambassadors can spend before the initial phase ends on its own
+    pub ambassador_quota: u128,
+    // lamports all ambassadors spent during the initial phase, which ends at `ambassador_quota`
+    pub ambassador_purchased: u128,
+    // seconds added to the clock. Production builds leave these last 8 bytes unused, anchor's IDL
+    // is parsed from source and still lists the field, which reads 0 there
+    #[cfg(feature = "feature-dev")]
+    pub clock_offset: i64,
+}
+
+// Privileged changes that go through a multisig proposal
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum AdminAction {
+    DisableInitialStage,
+    SetAdministrator {
+        user: Pubkey,
+        status: bool,
+    },
+    SetAmbassador {
+        user: Pubkey,
+        status: bool,
+    },
+    SetRoles {
+        user: Pubkey,
+        roles: u8,
+    },
+    SetStakingRequirement {
+        amount_of_tokens: u128,
+    },
+    SetAdminThreshold {
+        threshold: u8,
+    },
+    SetDividendFee {
+        dividend_fee: u8,
+    },
+    SetTimelockDelay {
+        delay: i64,
+    },
+    // executed with `execute_metadata_proposal`, which also updates the metadata account
+    UpdateMetadata {
+        name: String,
+        symbol: String,
+        uri: String,
+    },
+    // executed with `execute_revocation_proposal`, which also burns the locked tokens
+    RevokeVesting {
+        receipient: Pubkey,
+        index: u64,
+    },
+}
+
+impl AdminAction {
+    // The user whose account the action changes
+    pub fn target(&self) -> Option<Pubkey> {
+        match self {
+            AdminAction::SetAdministrator { user, .. }
+            | AdminAction::SetAmbassador { user, .. }
+            | AdminAction::SetRoles { user, .. } => Some(*user),
+            _ => None,
+        }
+    }
+
+    // Whether the action waits `timelock_delay` once approved
+    pub fn is_timelocked(&self) -> bool {
+        !matches!(
+            self,
+            AdminAction::DisableInitialStage
+                | AdminAction::SetAmbassador { .. }
+                | AdminAction::UpdateMetadata { .. }
+                | AdminAction::RevokeVesting { .. }
+        )
+    }
+
+    // Size `App` needs for the name and symbol the action sets, 0 when it sets none
+    pub fn app_space(&self) -> usize {
+        match self {
+            AdminAction::UpdateMetadata { name, symbol, .. } => App::space_for(name, symbol),
+            _ => 0,
+        }
+    }
+
+    // The largest action, a metadata update with the longest strings Metaplex accepts
+    const MAXIMUM_SIZE: usize =
+        1 + (4 + MAX_NAME_LENGTH) + (4 + MAX_SYMBOL_LENGTH) + (4 + MAX_URI_LENGTH);
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProposalStatus {
+    Active,
+    Executed,
+    Cancelled,
+}
+
+#[account]
+pub struct Proposal {
+    pub id: u64,
+    pub proposer: Pubkey,
+    pub action: AdminAction,
+    pub approvals: Vec<Pubkey>,
+    pub status: ProposalStatus,
+    pub created_at: i64,
+    // when it can be executed, 0 until it has enough approvals
+    pub eta: i64,
+}
+
+impl Proposal {
+    pub const MAXIMUM_SIZE: usize =
+        8 + 32 + AdminAction::MAXIMUM_SIZE + (4 + 32 * MAX_APPROVALS) + 1 + 8 + 8;
+
+    fn only_active(&self) -> Result<()> {
+        require!(
+            self.status == ProposalStatus::Active,
+            ProgramError::ProposalNotActive
+        );
+        Ok(())
+    }
+
+    /**
+     * Starts the timelock once the proposal has `threshold` approvals.
+     */
+    fn queue(&mut self, threshold: u8, delay: i64, now: i64) -> Result<()> {
+        if self.eta == 0 && self.approvals.len() >= threshold as usize {
+            let delay = if self.action.is_timelocked() {
+                delay
+            } else {
+                0
+            };
+            self.eta = now.checked_add(delay).ok_or(ProgramError::MathOverflow)?;
+        }
+        Ok(())
+    }
+}
+
+// Every admin key, kept in step with `User::is_admin`
+#[account]
+pub struct AdminRegistry {
+    pub admins: Vec<Pubkey>,
+    // keys granted admin that haven't accepted it yet
+    pub pending: Vec<PendingAdmin>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PendingAdmin {
+    pub user: Pubkey,
+    pub nominated_by: Pubkey,
+}
+
+impl AdminRegistry {
+    pub const MAXIMUM_SIZE: usize = (4 + 32 * MAX_ADMINS) + (4 + 64 * MAX_PENDING_ADMINS);
+
+    pub fn admin_count(&self) -> u8 {
+        self.admins.len() as u8
+    }
+
+    pub fn is_admin(&self, key: &Pubkey) -> bool {
+        self.admins.contains(key)
+    }
+}
+
+// Event of the action an executed proposal applied
+pub enum AdminActionEvent {
+    InitialStageEnd(InitialStageEnd),
+    AdminNomination(AdminNomination),
+    AdministratorUpdate(AdministratorUpdate),
+    AmbassadorUpdate(AmbassadorUpdate),
+    RolesUpdate(RolesUpdate),
+    StakingRequirementUpdate(StakingRequirementUpdate),
+    AdminThresholdUpdate(AdminThresholdUpdate),
+    DividendFeeUpdate(DividendFeeUpdate),
+    TimelockDelayUpdate(TimelockDelayUpdate),
 }
 
 // Modifiers helper functions
@@ -129,7 +443,7 @@ impl App {
     // only people with profits
     fn only_stronghands(program: &mut Account<App>, user: &mut Account<User>) -> Result<()> {
         require!(
-            App::my_dividends(program, user, true) > 0,
+            App::my_dividends(program, user, true)? > 0,
             ProgramError::NoPofit
         );
         Ok(())
@@ -151,24 +465,208 @@ impl App {
         Ok(())
     }
 
-    fn is_post_initial_phase_or_admin(&mut self, user: &mut Account<User>) -> Result<()> {
-        require!(
-            !self.is_initial_phase || (self.is_initial_phase && user.is_admin),
-            ProgramError::IsInitialPhase
+    // operations are gated on roles rather than on admin rights
+    fn check_role(&mut self, user: &mut Account<User>, role: u8) -> Result<()> {
+        require!(user.has_role(role), ProgramError::MissingRole);
+        Ok(())
+    }
+
+    fn validate_roles(roles: u8) -> Result<()> {
+        require!(roles & !ALL_ROLES == 0, ProgramError::InvalidRoles);
+        Ok(())
+    }
+
+    // once more than one admin must sign off, changes go through proposals
+    fn only_single_signer(&mut self) -> Result<()> {
+        require_gte!(1, self.admin_threshold, ProgramError::ProposalRequired);
+        Ok(())
+    }
+
+    // timelocked changes can only be made directly while the delay is zero
+    fn only_without_timelock(&mut self) -> Result<()> {
+        require_gte!(0, self.timelock_delay, ProgramError::TimelockRequired);
+        Ok(())
+    }
+
+    fn validate_timelock_delay(delay: i64) -> Result<()> {
+        require_gte!(delay, 0, ProgramError::InvalidTimelockDelay);
+        require_gte!(
+            MAX_TIMELOCK_DELAY,
+            delay,
+            ProgramError::InvalidTimelockDelay
         );
+        Ok(())
+    }
+
+    fn validate_threshold(threshold: u8, registry: &AdminRegistry) -> Result<()> {
+        require_gt!(threshold, 0, ProgramError::InvalidThreshold);
+        require_gte!(
+            registry.admin_count(),
+            threshold,
+            ProgramError::InvalidThreshold
+        );
+        require_gte!(
+            MAX_APPROVALS,
+            threshold as usize,
+            ProgramError::InvalidThreshold
+        );
+        Ok(())
+    }
+
+    /**
+     * During the initial phase only admins and ambassadors can buy. Ambassadors spend up to
+     * `ambassador_max_purchase` lamports each, counted towards the ambassador quota. Admins
+     * aren't capped nor counted, they buy the tokens `distribute_token` hands out before the
+     * phase ends. Reinvested dividends aren't new lamports, so they don't count either.
+     */
+    fn check_ambassador_purchase(
+        &mut self,
+        user: &mut Account<User>,
+        lamports: u128,
+        direct_buy: bool,
+    ) -> Result<()> {
+        if !self.is_initial_phase {
+            return Ok(());
+        }
+        require!(user.is_amb || user.is_admin, ProgramError::IsInitialPhase);
+        if user.is_admin || !direct_buy {
+            return Ok(());
+        }
+
+        let purchased = user
+            .ambassador_purchased
+            .checked_add(lamports)
+            .ok_or(ProgramError::MathOverflow)?;
+        require_gte!(
+            self.ambassador_max_purchase,
+            purchased,
+            ProgramError::AmbassadorCapExceeded
+        );
+        // the purchase that reaches the quota ends the phase, none may go past it
+        let total_purchased = self
+            .ambassador_purchased
+            .checked_add(lamports)
+            .ok_or(ProgramError::MathOverflow)?;
+        require_gte!(
+            self.ambassador_quota,
+            total_purchased,
+            ProgramError::AmbassadorQuotaExceeded
+        );
+        user.ambassador_purchased = purchased;
+        self.ambassador_purchased = total_purchased;
 
         Ok(())
     }
 
+    // The initial phase ends on its own once ambassadors have bought the whole quota
+    fn end_initial_stage_on_quota(
+        &mut self,
+        ambassador: Pubkey,
+    ) -> Result<Option<InitialStageEnd>> {
+        if !self.is_initial_phase || self.ambassador_purchased < self.ambassador_quota {
+            return Ok(None);
+        }
+        self.end_initial_stage(ambassador).map(Some)
+    }
+
     // Check that user has enough funds to use
-    fn has_enough(&mut self, user: &mut Account<User>, amount: u128) -> Result<()> {
+    fn has_enough(
+        &mut self,
+        user: &mut Account<User>,
+        amount: u128,
+        grants: &[AccountInfo],
+    ) -> Result<()> {
         require!(
-            user.has_balance_upto(amount, false),
+            user.has_usable_balance_upto(amount, grants, self.now()?)?,
             ProgramError::InsufficientBalance
         );
         Ok(())
     }
 
+    // Check that the economic parameters of a new token are usable
+    fn validate_params(params: &InitTokenParams) -> Result<()> {
+        // the curves quote prices per `LAMPORTS_IN_SOL` base units, any other unit misprices trades
+        require_eq!(
+            params.decimals,
+            TOKEN_DECIMALS,
+            ProgramError::InvalidDecimals
+        );
+        require_gt!(params.dividend_fee, 0, ProgramError::InvalidDividendFee);
+        require_gt!(params.magnitude, 0, ProgramError::InvalidMagnitude);
+        require_gt!(
+            params.token_incremental_price,
+            0,
+            ProgramError::InvalidTokenPrice
+        );
+        // the sell price at zero supply is `initial - incremental`, so it must stay positive
+        require_gt!(
+            params.token_initial_price,
+            params.token_incremental_price,
+            ProgramError::InvalidTokenPrice
+        );
+        require_gte!(
+            MAX_TOKEN_PRICE,
+            params.token_initial_price,
+            ProgramError::InvalidTokenPrice
+        );
+        // the exponential and constant product curves run out of supply as the prices close in
+        let max_supply = app_math::CurveKind::from(params.curve)
+            .curve(params.token_initial_price, params.token_incremental_price)
+            .max_supply()
+            .map_err(ProgramError::from)?;
+        require_gte!(
+            max_supply,
+            MIN_CURVE_SUPPLY,
+            ProgramError::CurveSupplyTooLow
+        );
+        require_gt!(
+            params.ambassador_max_purchase,
+            0,
+            ProgramError::InvalidAmbassadorQuota
+        );
+        require_gte!(
+            params.ambassador_quota,
+            params.ambassador_max_purchase,
+            ProgramError::InvalidAmbassadorQuota
+        );
+        Ok(())
+    }
+
+    /**
+     * The unix timestamp vesting, trade deadlines and proposals are checked against.
+     * `feature-dev` builds shift it by `clock_offset`, production builds read the bare clock.
+     */
+    pub fn now(&self) -> Result<i64> {
+        let now = Clock::get()?.unix_timestamp;
+        #[cfg(feature = "feature-dev")]
+        let now = now
+            .checked_add(self.clock_offset)
+            .ok_or(ProgramError::MathOverflow)?;
+        Ok(now)
+    }
+
+    // Reject trades that sat in a queue past the point the signer was willing to accept
+    pub fn check_deadline(&self, deadline: &TradeDeadline) -> Result<()> {
+        let clock: Clock = Clock::get()?;
+        if let Some(valid_until_slot) = deadline.valid_until_slot {
+            require_gte!(valid_until_slot, clock.slot, ProgramError::DeadlineExceeded);
+        }
+        if let Some(valid_until_ts) = deadline.valid_until_ts {
+            require_gte!(valid_until_ts, self.now()?, ProgramError::DeadlineExceeded);
+        }
+        Ok(())
+    }
+
+    fn validate_vesting_schedule(schedule: &VestingSchedule) -> Result<()> {
+        require_gt!(schedule.duration, 0, ProgramError::InvalidVestingSchedule);
+        require!(
+            (0..=schedule.duration).contains(&schedule.cliff)
+                && (0..=schedule.duration).contains(&schedule.release_interval),
+            ProgramError::InvalidVestingSchedule
+        );
+        Ok(())
+    }
+
     // Check that user owns the user_data_account
     fn owns_account(&mut self, user: &Signer, user_data: &Account<User>) -> Result<()> {
         require_keys_eq!(
@@ -203,10 +701,10 @@ impl App {
                 },
                 &signer,
             ),
-            quantity as u64,
+            to_u64(quantity)?,
         )?;
 
-        data_account.increase_balance_by(quantity);
+        data_account.increase_balance_by(quantity)?;
 
         Ok(true)
     }
@@ -233,14 +731,43 @@ impl App {
                     mint: mint.clone(),
                 },
             ),
-            quantity as u64,
+            to_u64(quantity)?,
         )?;
 
-        data_account.decrease_balance_by(quantity);
+        data_account.decrease_balance_by(quantity)?;
 
         Ok(true)
     }
 
+    // Burns from any holder, the mint PDA is the permanent delegate of every token account
+    fn burn_as_delegate<'a>(
+        data_account: &mut Account<'_, User>,
+        quantity: u128,
+        token_program: AccountInfo<'a>,
+        mint: AccountInfo<'a>,
+        user_ata: AccountInfo<'a>,
+        bump: u8,
+    ) -> Result<()> {
+        let seeds = &[MINT_SEED, &[bump]];
+        let signer = [&seeds[..]];
+
+        burn(
+            CpiContext::new_with_signer(
+                token_program,
+                Burn {
+                    authority: mint.clone(),
+                    from: user_ata,
+                    mint,
+                },
+                &signer,
+            ),
+            to_u64(quantity)?,
+        )?;
+
+        data_account.decrease_balance_by(quantity)?;
+        Ok(())
+    }
+
     fn purchase_tokens<'a>(
         program: &mut Account<'a, App>,
         buyer: &mut Signer<'a>,
@@ -249,21 +776,27 @@ impl App {
         referred_by_data_account: &mut Option<Account<'_, User>>,
         lamports: u128,
         referred_by: Option<Pubkey>,
+        min_tokens_out: u128,
         direct_buy: bool,
         token_program: AccountInfo<'a>,
         mint: AccountInfo<'a>,
         user_ata: AccountInfo<'a>,
         bump: u8,
-    ) -> Result<u128> {
-        program.is_post_initial_phase_or_admin(buyer_data_account)?;
+    ) -> Result<(TokenPurchase, Option<Skwizkey>)> {
+        program.check_ambassador_purchase(buyer_data_account, lamports, direct_buy)?;
         // data setup
         let buyer_key = buyer.key();
-        let undivided_dividends = lamports / (program.dividend_fee as u128);
-        let referral_bonus = undivided_dividends / 3;
-        let mut dividends = undivided_dividends - referral_bonus;
-        let taxed_lamport = lamports - undivided_dividends;
-        let amount_of_tokens = program.lamport_to_tokens(taxed_lamport);
-        let mut fee = dividends * program.magnitude as u128;
+        let magnitude = program.magnitude as u128;
+        let split =
+            PurchaseSplit::new(lamports, program.dividend_fee).map_err(ProgramError::from)?;
+        let referral_bonus = split.referral_bonus;
+        let mut dividends = split.dividends;
+        let taxed_lamport = split.taxed_lamports;
+        let amount_of_tokens = program.lamport_to_tokens(taxed_lamport)?;
+        let mut skwizkey = None;
+        let mut fee = dividends
+            .checked_mul(magnitude)
+            .ok_or(ProgramError::MathOverflow)?;
         let r_by = referred_by.unwrap_or_default();
 
         require_keys_neq!(r_by, buyer_key, ProgramError::SelfReferral);
@@ -280,17 +813,27 @@ impl App {
         // prevents overflow in the case that the pyramid somehow magically starts being used by everyone in the world
         // (or hackers)
         require_gt!(amount_of_tokens, 0, ProgramError::SentLessToken);
+        // the price moved against the buyer since they were quoted
+        require_gte!(
+            amount_of_tokens,
+            min_tokens_out,
+            ProgramError::TokensBelowMinimum
+        );
         if direct_buy {
             require_gte!(
                 buyer.get_lamports(),
-                lamports as u64,
+                to_u64(lamports)?,
                 ProgramError::InsufficientBalance
             );
         }
-        require_gt!(
-            amount_of_tokens + program.token_supply,
-            program.token_supply,
-            ProgramError::SentLessToken
+        let new_token_supply = amount_of_tokens
+            .checked_add(program.token_supply)
+            .ok_or(ProgramError::MathOverflow)?;
+        let max_supply = program.pool().max_supply().map_err(ProgramError::from)?;
+        require_gte!(
+            max_supply,
+            new_token_supply,
+            ProgramError::SupplyLimitExceeded
         );
 
         // is the user referred by a skwizkey?
@@ -303,45 +846,68 @@ impl App {
             if let Some(referred_by_data) = referred_by_data_account {
                 // does the referrer have at least X whole tokens?
                 // i.e is the referrer a godly chad skwizkey
-                if referred_by_data.has_balance_upto(program.staking_requirement, true) {
+                if referred_by_data.has_balance_upto(program.staking_requirement) {
                     // wealth redistribution
-                    referred_by_data.increase_referred_balance_by(referral_bonus);
+                    referred_by_data.increase_referred_balance_by(referral_bonus)?;
 
                     // Emit an event
-                    on_skwizkey(referred, buyer_key, lamports, referral_bonus);
+                    skwizkey = Some(Skwizkey {
+                        referrer: referred,
+                        customer: buyer_key,
+                        lamports_used: lamports,
+                        bonus_received: referral_bonus,
+                        seq: program.next_event_seq()?,
+                        referrer_nonce: referred_by_data.next_nonce()?,
+                    });
                 } else {
                     // no ref purchase
                     // add the referral bonus back to the global dividends cake
-                    dividends += referral_bonus;
-                    fee = dividends * program.magnitude as u128;
+                    dividends = dividends
+                        .checked_add(referral_bonus)
+                        .ok_or(ProgramError::MathOverflow)?;
+                    fee = dividends
+                        .checked_mul(magnitude)
+                        .ok_or(ProgramError::MathOverflow)?;
                 }
             } else {
                 // no ref purchase
                 // add the referral bonus back to the global dividends cake
-                dividends += referral_bonus;
-                fee = dividends * program.magnitude as u128;
+                dividends = dividends
+                    .checked_add(referral_bonus)
+                    .ok_or(ProgramError::MathOverflow)?;
+                fee = dividends
+                    .checked_mul(magnitude)
+                    .ok_or(ProgramError::MathOverflow)?;
             }
         } else {
             // no ref purchase
             // add the referral bonus back to the global dividends cake
-            dividends += referral_bonus;
-            fee = dividends * program.magnitude as u128;
+            dividends = dividends
+                .checked_add(referral_bonus)
+                .ok_or(ProgramError::MathOverflow)?;
+            fee = dividends
+                .checked_mul(magnitude)
+                .ok_or(ProgramError::MathOverflow)?;
         }
 
         // we can't give people infinite ethereum
         if program.token_supply > 0 {
             // add tokens to the pool
-            program.token_supply += amount_of_tokens;
+            program.token_supply = new_token_supply;
 
             // take the amount of dividends gained through this transaction, and allocates them evenly to each shareholder
-            program.profit_per_share +=
-                (dividends * program.magnitude as u128) / (program.token_supply);
+            let dividends_per_share = program.dividends_per_share(dividends)?;
+            program.profit_per_share = program
+                .profit_per_share
+                .checked_add(dividends_per_share)
+                .ok_or(ProgramError::MathOverflow)?;
             msg!(" PPS {}", program.profit_per_share);
             // calculate the amount of tokens the customer receives over his purchase
-            fee = fee
-                - (fee
-                    - (amount_of_tokens
-                        * ((dividends * program.magnitude as u128) / (program.token_supply))));
+            fee = amount_of_tokens
+                .checked_mul(dividends_per_share)
+                .and_then(|shared| fee.checked_sub(shared))
+                .and_then(|kept| fee.checked_sub(kept))
+                .ok_or(ProgramError::MathUnderflow)?;
         } else {
             // add tokens to the pool
             program.token_supply = amount_of_tokens;
@@ -359,8 +925,9 @@ impl App {
         // Tells the contract that the buyer doesn't deserve dividends for the tokens before they owned them;
         //really i know you think you do but you don't
         msg!("User Payout {}", buyer_data_account.payout);
-        let updated_payouts: i128 =
-            ((program.profit_per_share * amount_of_tokens) as i128 - fee as i128) as i128;
+        let updated_payouts: i128 = App::payout_of(program.profit_per_share, amount_of_tokens)?
+            .checked_sub(i128::try_from(fee).map_err(|_| ProgramError::MathOverflow)?)
+            .ok_or(ProgramError::MathUnderflow)?;
         msg!(
             "Payout to Add {}\nPPS {}\nFee {}\nToken {}",
             updated_payouts,
@@ -369,80 +936,102 @@ impl App {
             amount_of_tokens
         );
 
-        buyer_data_account.increase_payout_by(updated_payouts);
+        buyer_data_account.increase_payout_by(updated_payouts)?;
         msg!("User Payout {} After", buyer_data_account.payout);
 
         // fire event
-        on_token_purchase(buyer_key, lamports, amount_of_tokens, referred);
+        let purchase = TokenPurchase {
+            customer: buyer_key,
+            incoming_lamports: lamports,
+            tokens_minted: amount_of_tokens,
+            referred_by: referred,
+            token_supply: program.token_supply,
+            profit_per_share: program.profit_per_share,
+            price: program.spot_price()?,
+            seq: program.next_event_seq()?,
+            nonce: buyer_data_account.next_nonce()?,
+        };
 
         if direct_buy {
-            App::transfer_sol_in(buyer, program, system, lamports as u64)?;
+            App::transfer_sol_in(buyer, program, system, to_u64(lamports)?)?;
         }
 
-        Ok(amount_of_tokens)
+        Ok((purchase, skwizkey))
     }
 
-    /**
-     * Calculate Token price based on an amount of incoming lamport
-     * It's an algorithm, hopefully we gave you the whitepaper with it in scientific notation;
-     * Some conversions occurred to prevent decimal errors or underflows / overflows in solidity code.
-     */
-    fn lamport_to_tokens(&mut self, lamport: u128) -> u128 {
-        let token_price_initial = self.token_initial_price; // 100
-        let token_price_incremental = self.token_incremental_price; // 1000
-        let token_supply = self.token_supply; // 0
-
-        let token_price_initial_expanded = token_price_initial * LAMPORTS_IN_SOL; //100 * 1e9  = 100e9
-        let token_price_incremental_expanded = token_price_incremental * LAMPORTS_IN_SOL; //1000 * 1e9  = 1000e9
-        let lamport_expanded = lamport * LAMPORTS_IN_SOL; //1e9*1e9 =  1e18
-
-        let token_price_initial_to_power = token_price_initial_expanded.pow(2);
-        let token_price_incremental_to_power = token_price_incremental.pow(2);
-        let token_supply_to_power = token_supply.pow(2);
+    // Pricing and dividend state, handed to the shared math crate
+    pub fn pool(&self) -> Pool {
+        Pool {
+            curve: self.curve.into(),
+            token_initial_price: self.token_initial_price,
+            token_incremental_price: self.token_incremental_price,
+            token_supply: self.token_supply,
+            decimals: self.decimals,
+            dividend_fee: self.dividend_fee,
+            magnitude: self.magnitude,
+            profit_per_share: self.profit_per_share,
+        }
+    }
 
-        let first = token_price_initial_to_power;
-        let second = 2 * token_price_incremental_expanded * lamport_expanded;
-        let third = token_price_incremental_to_power * token_supply_to_power;
-        let fourth = 2 * token_price_incremental * token_price_initial_expanded * token_supply;
+    // Counts every event the program emits, the first one being 1
+    fn next_event_seq(&mut self) -> Result<u64> {
+        self.event_seq = self
+            .event_seq
+            .checked_add(1)
+            .ok_or(ProgramError::MathOverflow)?;
+        Ok(self.event_seq)
+    }
 
-        let square_root = sqrt(first + second + third + fourth);
-        let tokens_received =
-            ((square_root - token_price_initial_expanded) / token_price_incremental) - token_supply;
+    // Price of the next whole token, reported in trade events
+    fn spot_price(&self) -> Result<u128> {
+        Ok(self.pool().spot_price().map_err(ProgramError::from)?)
+    }
 
-        tokens_received
+    /**
+     * Calculate Token price based on an amount of incoming lamport
+     */
+    fn lamport_to_tokens(&mut self, lamport: u128) -> Result<u128> {
+        Ok(self
+            .pool()
+            .lamport_to_tokens(lamport)
+            .map_err(ProgramError::from)?)
     }
 
     /**
      * Calculate token sell value.
-     * It's an algorithm, hopefully we gave you the whitepaper with it in scientific notation;
-     * Some conversions occurred to prevent decimal errors or underflows / overflows in solidity code.
      */
-    fn tokens_to_lamport(&mut self, tokens: u128) -> u128 {
-        let tokens = tokens + LAMPORTS_IN_SOL;
-        let token_supply = self.token_supply + LAMPORTS_IN_SOL;
-
-        let token_initial_price = self.token_initial_price;
-        let token_incremental_price = self.token_incremental_price;
-
-        let first = (token_initial_price
-            + (token_incremental_price * (token_supply / LAMPORTS_IN_SOL)))
-            - token_incremental_price;
-
-        let second = tokens - LAMPORTS_IN_SOL;
-        let third = token_incremental_price * ((tokens.pow(2) - tokens) / LAMPORTS_IN_SOL) / 2;
+    fn tokens_to_lamport(&mut self, tokens: u128) -> Result<u128> {
+        Ok(self
+            .pool()
+            .tokens_to_lamport(tokens)
+            .map_err(ProgramError::from)?)
+    }
 
-        // underflow attempts BTFO
-        let lamport_to_receive = ((first * second) - (third)) / LAMPORTS_IN_SOL;
+    // Dividends per token for spreading `dividends` lamports over the current supply
+    fn dividends_per_share(&self, dividends: u128) -> Result<u128> {
+        Ok(self
+            .pool()
+            .dividends_per_share(dividends)
+            .map_err(ProgramError::from)?)
+    }
 
-        lamport_to_receive
+    // `a * b` as a signed amount on the payout ledger
+    fn payout_of(a: u128, b: u128) -> Result<i128> {
+        Ok(app_math::pool::payout_of(a, b).map_err(ProgramError::from)?)
     }
 
     /**
      * Retrieve the dividend balance of any single address.
      */
-    fn dividends_of(&mut self, user: &Account<User>) -> u128 {
-        ((((self.profit_per_share * user.balance) as i128) - user.payout) / self.magnitude as i128)
-            as u128
+    fn dividends_of(&mut self, user: &Account<User>) -> Result<u128> {
+        let holder = Holder {
+            balance: user.balance,
+            payout: user.payout,
+        };
+        Ok(self
+            .pool()
+            .dividends_of(&holder)
+            .map_err(ProgramError::from)?)
     }
 
     fn transfer_sol_out(
@@ -452,10 +1041,13 @@ impl App {
         amount: u64,
     ) -> Result<bool> {
         require!(
-            amount <= from.contract_balance as u64,
+            u128::from(amount) <= from.contract_balance,
             ProgramError::InsufficientBalance
         );
-        from.contract_balance -= amount as u128;
+        from.contract_balance = from
+            .contract_balance
+            .checked_sub(u128::from(amount))
+            .ok_or(ProgramError::MathUnderflow)?;
 
         // let _from = from.to_account_info();
         // let _to = to.to_account_info();
@@ -477,7 +1069,10 @@ impl App {
             amount <= from.get_lamports(),
             ProgramError::InsufficientBalance
         );
-        to.contract_balance += amount as u128;
+        to.contract_balance = to
+            .contract_balance
+            .checked_add(u128::from(amount))
+            .ok_or(ProgramError::MathOverflow)?;
 
         let ix = transfer(&from.key(), &to.key(), amount);
 
@@ -497,30 +1092,74 @@ impl App {
 impl App {
     /**
      * In case the ambassador quota is not met, the administrator can manually disable the ambassador phase.
+     * Otherwise the purchase that fills the quota ends it.
      */
     pub fn disable_initial_stage(
         program: &mut Account<App>,
         admin: &mut Account<User>,
-    ) -> Result<()> {
-        program.check_admin_rights(admin)?;
+    ) -> Result<InitialStageEnd> {
+        program.check_role(admin, ROLE_PHASE_MANAGER)?;
+        program.only_single_signer()?;
 
-        program.is_initial_phase = false;
-        Ok(())
+        program.end_initial_stage(admin.authority)
     }
 
     /**
      * In case one of us dies, we need to replace ourselves.
+     * Granting admin only nominates the user, who becomes an admin once they accept it.
      */
     pub fn set_administrator(
         program: &mut Account<App>,
+        registry: &mut Account<AdminRegistry>,
         admin: &mut Account<User>,
         user: &mut Account<User>,
+        user_key: Pubkey,
         status: bool,
-    ) -> Result<()> {
+    ) -> Result<AdminActionEvent> {
         program.check_admin_rights(admin)?;
-        user.update_admin_status(status);
+        program.only_single_signer()?;
+        program.only_without_timelock()?;
 
-        Ok(())
+        program.update_administrator(admin.authority, registry, user, user_key, status)
+    }
+
+    /**
+     * Second step of granting admin, signed by the nominated user.
+     */
+    pub fn accept_administrator(
+        program: &mut Account<App>,
+        registry: &mut Account<AdminRegistry>,
+        user: &Signer,
+        user_data: &mut Account<User>,
+    ) -> Result<AdministratorUpdate> {
+        let index = registry
+            .pending
+            .iter()
+            .position(|pending| pending.user == user.key())
+            .ok_or(ProgramError::NotNominated)?;
+        require_gt!(
+            MAX_ADMINS,
+            registry.admins.len(),
+            ProgramError::AdminRegistryFull
+        );
+
+        let nomination = registry.pending.remove(index);
+        registry.admins.push(user.key());
+        if user_data.authority.key().eq(&Pubkey::default().key()) {
+            user_data.authority = user.key();
+        }
+        let old_status = user_data.is_admin;
+        user_data.update_admin_status(true);
+
+        Ok(AdministratorUpdate {
+            admin: nomination.nominated_by,
+            user: user.key(),
+            old_status,
+            new_status: true,
+            admin_count: registry.admin_count(),
+            seq: program.next_event_seq()?,
+            nonce: user_data.next_nonce()?,
+        })
     }
 
     /**
@@ -530,12 +1169,13 @@ impl App {
         program: &mut Account<App>,
         admin: &mut Account<User>,
         user: &mut Account<User>,
+        user_key: Pubkey,
         status: bool,
-    ) -> Result<()> {
-        program.check_admin_rights(admin)?;
-        user.update_amb_status(status);
+    ) -> Result<AmbassadorUpdate> {
+        program.check_role(admin, ROLE_PHASE_MANAGER)?;
+        program.only_single_signer()?;
 
-        Ok(())
+        program.update_ambassador(admin.authority, user, user_key, status)
     }
 
     /**
@@ -545,23 +1185,520 @@ impl App {
         program: &mut Account<App>,
         admin: &mut Account<User>,
         amount_of_tokens: u128,
-    ) -> Result<()> {
-        program.check_admin_rights(admin)?;
-        program.staking_requirement = amount_of_tokens;
+    ) -> Result<StakingRequirementUpdate> {
+        program.check_role(admin, ROLE_PARAMETER_MANAGER)?;
+        program.only_single_signer()?;
+        program.only_without_timelock()?;
 
-        Ok(())
+        program.update_staking_requirement(admin.authority, amount_of_tokens)
     }
 
     /**
-     * Updates information of the metadata of mint
-     * @TODO: Update token information
+     * Updates information of the metadata of mint.
+     * Mirrors the new name and symbol, the metadata account itself is updated by the caller.
      */
     pub fn update_metadata_account(
         program: &mut Account<App>,
         admin: &mut Account<User>,
-    ) -> Result<()> {
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<MetadataUpdate> {
+        program.check_role(admin, ROLE_METADATA_MANAGER)?;
+        program.only_single_signer()?;
+
+        program.update_metadata(admin.authority, name, symbol, uri)
+    }
+
+    /**
+     * Replaces the roles of a user. Only admins can, so a role holder can't extend its own rights.
+     */
+    pub fn set_roles(
+        program: &mut Account<App>,
+        admin: &mut Account<User>,
+        user: &mut Account<User>,
+        user_key: Pubkey,
+        roles: u8,
+    ) -> Result<RolesUpdate> {
         program.check_admin_rights(admin)?;
-        Ok(())
+        program.only_single_signer()?;
+        program.only_without_timelock()?;
+        App::validate_roles(roles)?;
+
+        program.update_roles(admin.authority, user, user_key, roles)
+    }
+
+    /**
+     * Moves the clock `now` reads, so a devnet deployment can walk through a 100 days lock.
+     */
+    #[cfg(feature = "feature-dev")]
+    pub fn set_clock_offset(
+        program: &mut Account<App>,
+        admin: &mut Account<User>,
+        offset: i64,
+    ) -> Result<ClockOffsetUpdate> {
+        // a testing aid, a multisig would only get in the way
+        program.check_admin_rights(admin)?;
+
+        let old_offset = program.clock_offset;
+        program.clock_offset = offset;
+
+        Ok(ClockOffsetUpdate {
+            admin: admin.authority,
+            old_offset,
+            new_offset: offset,
+            seq: program.next_event_seq()?,
+        })
+    }
+}
+
+// Admin actions, applied by an admin alone or by an executed proposal
+impl App {
+    fn end_initial_stage(&mut self, admin: Pubkey) -> Result<InitialStageEnd> {
+        let was_initial_phase = self.is_initial_phase;
+        self.is_initial_phase = false;
+
+        Ok(InitialStageEnd {
+            admin,
+            was_initial_phase,
+            seq: self.next_event_seq()?,
+        })
+    }
+
+    // Nominates the user when granting, removes them from the registry when revoking
+    fn update_administrator(
+        &mut self,
+        admin: Pubkey,
+        registry: &mut AdminRegistry,
+        user: &mut Account<User>,
+        user_key: Pubkey,
+        status: bool,
+    ) -> Result<AdminActionEvent> {
+        if user.authority.key().eq(&Pubkey::default().key()) {
+            user.authority = user_key;
+        }
+
+        if status {
+            require!(!user.is_admin, ProgramError::AlreadyAdmin);
+            require!(
+                !registry
+                    .pending
+                    .iter()
+                    .any(|pending| pending.user == user.authority),
+                ProgramError::AlreadyNominated
+            );
+            require_gt!(
+                MAX_PENDING_ADMINS,
+                registry.pending.len(),
+                ProgramError::AdminRegistryFull
+            );
+            registry.pending.push(PendingAdmin {
+                user: user.authority,
+                nominated_by: admin,
+            });
+
+            return Ok(AdminActionEvent::AdminNomination(AdminNomination {
+                admin,
+                user: user.authority,
+                seq: self.next_event_seq()?,
+            }));
+        }
+
+        // revoking also withdraws a grant that wasn't accepted yet
+        registry
+            .pending
+            .retain(|pending| pending.user != user.authority);
+        let old_status = user.is_admin;
+        if old_status {
+            require_gt!(registry.admins.len(), 1, ProgramError::LastAdmin);
+            require_gt!(
+                registry.admins.len(),
+                self.admin_threshold as usize,
+                ProgramError::AdminCountBelowThreshold
+            );
+            registry.admins.retain(|key| key != &user.authority);
+            // the roles it held as an admin go with it
+            user.update_roles(0);
+        }
+        user.update_admin_status(false);
+
+        Ok(AdminActionEvent::AdministratorUpdate(AdministratorUpdate {
+            admin,
+            user: user.authority,
+            old_status,
+            new_status: false,
+            admin_count: registry.admin_count(),
+            seq: self.next_event_seq()?,
+            nonce: user.next_nonce()?,
+        }))
+    }
+
+    fn update_ambassador(
+        &mut self,
+        admin: Pubkey,
+        user: &mut Account<User>,
+        user_key: Pubkey,
+        status: bool,
+    ) -> Result<AmbassadorUpdate> {
+        if user.authority.key().eq(&Pubkey::default().key()) {
+            user.authority = user_key;
+        }
+        let old_status = user.is_amb;
+        user.update_amb_status(status);
+
+        Ok(AmbassadorUpdate {
+            admin,
+            user: user.authority,
+            old_status,
+            new_status: status,
+            seq: self.next_event_seq()?,
+            nonce: user.next_nonce()?,
+        })
+    }
+
+    fn update_roles(
+        &mut self,
+        admin: Pubkey,
+        user: &mut Account<User>,
+        user_key: Pubkey,
+        roles: u8,
+    ) -> Result<RolesUpdate> {
+        if user.authority.key().eq(&Pubkey::default().key()) {
+            user.authority = user_key;
+        }
+        let old_roles = user.roles;
+        user.update_roles(roles);
+
+        Ok(RolesUpdate {
+            admin,
+            user: user.authority,
+            old_roles,
+            new_roles: roles,
+            seq: self.next_event_seq()?,
+            nonce: user.next_nonce()?,
+        })
+    }
+
+    fn update_staking_requirement(
+        &mut self,
+        admin: Pubkey,
+        amount_of_tokens: u128,
+    ) -> Result<StakingRequirementUpdate> {
+        let old_amount = self.staking_requirement;
+        self.staking_requirement = amount_of_tokens;
+
+        Ok(StakingRequirementUpdate {
+            admin,
+            old_amount,
+            new_amount: amount_of_tokens,
+            seq: self.next_event_seq()?,
+        })
+    }
+
+    fn update_admin_threshold(
+        &mut self,
+        admin: Pubkey,
+        threshold: u8,
+    ) -> Result<AdminThresholdUpdate> {
+        let old_threshold = self.admin_threshold;
+        self.admin_threshold = threshold;
+
+        Ok(AdminThresholdUpdate {
+            admin,
+            old_threshold,
+            new_threshold: threshold,
+            seq: self.next_event_seq()?,
+        })
+    }
+
+    fn update_dividend_fee(
+        &mut self,
+        admin: Pubkey,
+        dividend_fee: u8,
+    ) -> Result<DividendFeeUpdate> {
+        let old_fee = self.dividend_fee;
+        self.dividend_fee = dividend_fee;
+
+        Ok(DividendFeeUpdate {
+            admin,
+            old_fee,
+            new_fee: dividend_fee,
+            seq: self.next_event_seq()?,
+        })
+    }
+
+    fn update_timelock_delay(&mut self, admin: Pubkey, delay: i64) -> Result<TimelockDelayUpdate> {
+        let old_delay = self.timelock_delay;
+        self.timelock_delay = delay;
+
+        Ok(TimelockDelayUpdate {
+            admin,
+            old_delay,
+            new_delay: delay,
+            seq: self.next_event_seq()?,
+        })
+    }
+
+    fn update_metadata(
+        &mut self,
+        admin: Pubkey,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<MetadataUpdate> {
+        let old_name = std::mem::replace(&mut self.name, name.clone());
+        let old_symbol = std::mem::replace(&mut self.symbol, symbol.clone());
+
+        Ok(MetadataUpdate {
+            admin,
+            old_name,
+            old_symbol,
+            name,
+            symbol,
+            uri,
+            seq: self.next_event_seq()?,
+        })
+    }
+}
+
+// Multisig proposals
+impl App {
+    /**
+     * Opens a proposal for an admin action, approved by its proposer.
+     * Once it has `admin_threshold` approvals it is queued: staking requirement, fee, admin
+     * and timelock changes wait `timelock_delay` seconds, the others can be executed right away.
+     */
+    pub fn propose(
+        program: &mut Account<App>,
+        registry: &AdminRegistry,
+        admin: &Signer,
+        admin_data: &mut Account<User>,
+        proposal: &mut Account<Proposal>,
+        action: AdminAction,
+    ) -> Result<ProposalCreation> {
+        program.check_admin_rights(admin_data)?;
+        match action {
+            AdminAction::SetAdminThreshold { threshold } => {
+                App::validate_threshold(threshold, registry)?
+            }
+            AdminAction::SetDividendFee { dividend_fee } => {
+                require_gt!(dividend_fee, 0, ProgramError::InvalidDividendFee)
+            }
+            AdminAction::SetTimelockDelay { delay } => App::validate_timelock_delay(delay)?,
+            AdminAction::SetRoles { roles, .. } => App::validate_roles(roles)?,
+            // longer strings wouldn't fit the proposal, nor pass Metaplex once executed
+            AdminAction::UpdateMetadata {
+                ref name,
+                ref symbol,
+                ref uri,
+            } => require!(
+                name.len() <= MAX_NAME_LENGTH
+                    && symbol.len() <= MAX_SYMBOL_LENGTH
+                    && uri.len() <= MAX_URI_LENGTH,
+                ProgramError::MetadataTooLong
+            ),
+            _ => {}
+        }
+
+        proposal.id = program.proposal_count;
+        proposal.proposer = admin.key();
+        proposal.action = action.clone();
+        proposal.approvals = vec![admin.key()];
+        proposal.status = ProposalStatus::Active;
+        let now = program.now()?;
+        proposal.created_at = now;
+        proposal.eta = 0;
+        proposal.queue(program.admin_threshold, program.timelock_delay, now)?;
+        program.proposal_count = program
+            .proposal_count
+            .checked_add(1)
+            .ok_or(ProgramError::MathOverflow)?;
+
+        Ok(ProposalCreation {
+            id: proposal.id,
+            proposer: proposal.proposer,
+            action,
+            eta: proposal.eta,
+            seq: program.next_event_seq()?,
+        })
+    }
+
+    pub fn approve_proposal(
+        program: &mut Account<App>,
+        admin: &Signer,
+        admin_data: &mut Account<User>,
+        proposal: &mut Account<Proposal>,
+    ) -> Result<ProposalApproval> {
+        program.check_admin_rights(admin_data)?;
+        proposal.only_active()?;
+        require!(
+            !proposal.approvals.contains(&admin.key()),
+            ProgramError::AlreadyApproved
+        );
+        require_gt!(
+            MAX_APPROVALS,
+            proposal.approvals.len(),
+            ProgramError::ApprovalLimitReached
+        );
+
+        proposal.approvals.push(admin.key());
+        proposal.queue(
+            program.admin_threshold,
+            program.timelock_delay,
+            program.now()?,
+        )?;
+
+        Ok(ProposalApproval {
+            id: proposal.id,
+            admin: admin.key(),
+            approvals: proposal.approvals.len() as u8,
+            eta: proposal.eta,
+            seq: program.next_event_seq()?,
+        })
+    }
+
+    /**
+     * Applies the action of a queued proposal once its timelock has passed. Anyone can call it,
+     * the changes are credited to the proposer.
+     * Administrator and ambassador changes need the account of the user they apply to.
+     */
+    pub fn execute_proposal(
+        program: &mut Account<App>,
+        registry: &mut Account<AdminRegistry>,
+        executor: &Signer,
+        proposal: &mut Account<Proposal>,
+        target_data: Option<&mut Account<User>>,
+    ) -> Result<(ProposalExecution, AdminActionEvent)> {
+        let execution = App::start_execution(program, registry, executor, proposal)?;
+        let admin = proposal.proposer;
+
+        let target = match (proposal.action.target(), target_data) {
+            // the account's seeds already tie it to the target
+            (Some(user), Some(data)) => Some((user, data)),
+            (Some(_), None) => return err!(ProgramError::InvalidTargetAccount),
+            (None, _) => None,
+        };
+
+        let event = match (proposal.action.clone(), target) {
+            (AdminAction::DisableInitialStage, _) => {
+                AdminActionEvent::InitialStageEnd(program.end_initial_stage(admin)?)
+            }
+            (AdminAction::SetAdministrator { status, .. }, Some((user, data))) => {
+                program.update_administrator(admin, registry, data, user, status)?
+            }
+            (AdminAction::SetAmbassador { status, .. }, Some((user, data))) => {
+                AdminActionEvent::AmbassadorUpdate(
+                    program.update_ambassador(admin, data, user, status)?,
+                )
+            }
+            (AdminAction::SetRoles { roles, .. }, Some((user, data))) => {
+                AdminActionEvent::RolesUpdate(program.update_roles(admin, data, user, roles)?)
+            }
+            (AdminAction::SetStakingRequirement { amount_of_tokens }, _) => {
+                AdminActionEvent::StakingRequirementUpdate(
+                    program.update_staking_requirement(admin, amount_of_tokens)?,
+                )
+            }
+            (AdminAction::SetAdminThreshold { threshold }, _) => {
+                // the admin count may have dropped since it was proposed
+                App::validate_threshold(threshold, registry)?;
+                AdminActionEvent::AdminThresholdUpdate(
+                    program.update_admin_threshold(admin, threshold)?,
+                )
+            }
+            (AdminAction::SetDividendFee { dividend_fee }, _) => {
+                AdminActionEvent::DividendFeeUpdate(
+                    program.update_dividend_fee(admin, dividend_fee)?,
+                )
+            }
+            (AdminAction::SetTimelockDelay { delay }, _) => {
+                AdminActionEvent::TimelockDelayUpdate(program.update_timelock_delay(admin, delay)?)
+            }
+            (AdminAction::UpdateMetadata { .. } | AdminAction::RevokeVesting { .. }, _) => {
+                return err!(ProgramError::WrongExecuteInstruction)
+            }
+            (_, None) => return err!(ProgramError::InvalidTargetAccount),
+        };
+
+        Ok((execution, event))
+    }
+
+    /**
+     * Executes a metadata update proposal. The name and symbol are mirrored here, the caller
+     * updates the metadata account with the returned event.
+     */
+    pub fn execute_metadata_proposal(
+        program: &mut Account<App>,
+        registry: &Account<AdminRegistry>,
+        executor: &Signer,
+        proposal: &mut Account<Proposal>,
+    ) -> Result<(ProposalExecution, MetadataUpdate)> {
+        let AdminAction::UpdateMetadata { name, symbol, uri } = proposal.action.clone() else {
+            return err!(ProgramError::WrongExecuteInstruction);
+        };
+        let execution = App::start_execution(program, registry, executor, proposal)?;
+        let metadata_update = program.update_metadata(proposal.proposer, name, symbol, uri)?;
+
+        Ok((execution, metadata_update))
+    }
+
+    /**
+     * Marks a queued proposal executed, once its timelock has passed and enough of the current
+     * admins approved it.
+     */
+    fn start_execution(
+        program: &mut Account<App>,
+        registry: &AdminRegistry,
+        executor: &Signer,
+        proposal: &mut Account<Proposal>,
+    ) -> Result<ProposalExecution> {
+        proposal.only_active()?;
+        require_gt!(proposal.eta, 0, ProgramError::ProposalNotQueued);
+        require_gte!(
+            program.now()?,
+            proposal.eta,
+            ProgramError::TimelockNotElapsed
+        );
+        // the threshold may have been raised, or approvers removed, since it was queued
+        let approvals = proposal
+            .approvals
+            .iter()
+            .filter(|approver| registry.is_admin(approver))
+            .count();
+        require_gte!(
+            approvals,
+            program.admin_threshold as usize,
+            ProgramError::NotEnoughApprovals
+        );
+
+        proposal.status = ProposalStatus::Executed;
+
+        Ok(ProposalExecution {
+            id: proposal.id,
+            executor: executor.key(),
+            seq: program.next_event_seq()?,
+        })
+    }
+
+    /**
+     * Any admin can drop a proposal that hasn't been executed yet.
+     */
+    pub fn cancel_proposal(
+        program: &mut Account<App>,
+        admin: &Signer,
+        admin_data: &mut Account<User>,
+        proposal: &mut Account<Proposal>,
+    ) -> Result<ProposalCancellation> {
+        program.check_admin_rights(admin_data)?;
+        proposal.only_active()?;
+
+        proposal.status = ProposalStatus::Cancelled;
+
+        Ok(ProposalCancellation {
+            id: proposal.id,
+            admin: admin.key(),
+            seq: program.next_event_seq()?,
+        })
     }
 }
 
@@ -577,76 +1714,73 @@ impl App {
         program: &mut Account<App>,
         user: &Account<User>,
         include_referral_bonus: bool,
-    ) -> u128 {
+    ) -> Result<u128> {
         if include_referral_bonus {
-            program.dividends_of(user) + user.referred_balance
+            Ok(program
+                .dividends_of(user)?
+                .checked_add(user.referred_balance)
+                .ok_or(ProgramError::MathOverflow)?)
         } else {
             program.dividends_of(user)
         }
     }
 
+    /**
+     * Tokens of the user that no vesting grant locks at `now`, the grants are passed in grant order.
+     */
+    pub fn vested_balance(user: &Account<User>, grants: &[AccountInfo], now: i64) -> Result<u128> {
+        user.usable_balance(grants, now)
+    }
+
     /**
      * Return the buy price of 1 individual token.
      */
-    pub fn sell_price(&mut self) -> u128 {
-        // our calculation relies on the token supply, so we need supply. Doh.
-        if self.token_supply == 0 {
-            self.token_initial_price - self.token_incremental_price
-        } else {
-            let lamport: u128 = self.tokens_to_lamport(u128::pow(10, self.decimals as u32));
-            let dividends: u128 = lamport / (self.dividend_fee) as u128;
-            let taxed_lamport: u128 = lamport - (dividends);
-            taxed_lamport
-        }
+    pub fn sell_price(&mut self) -> Result<u128> {
+        Ok(self.pool().sell_price().map_err(ProgramError::from)?)
     }
 
     /**
      * Return the sell price of 1 individual token.
      */
-    pub fn buy_price(&mut self) -> u128 {
-        // our calculation relies on the token supply, so we need supply. Doh.
-        if self.token_supply == 0 {
-            self.token_initial_price + self.token_incremental_price
-        } else {
-            let lamport: u128 = self.tokens_to_lamport(u128::pow(10, self.decimals as u32));
-            let dividends: u128 = lamport / (self.dividend_fee) as u128;
-            let taxed_lamport: u128 = lamport - (dividends);
-            taxed_lamport
-        }
+    pub fn buy_price(&mut self) -> Result<u128> {
+        Ok(self.pool().buy_price().map_err(ProgramError::from)?)
     }
 
     /**
      * Function for the frontend to dynamically retrieve the price scaling of buy orders.
      */
-    pub fn calculate_tokens_received(&mut self, lamport_to_spend: u128) -> u128 {
-        if lamport_to_spend == 0 {
-            return 0;
-        }
-        let dividends: u128 = lamport_to_spend / (self.dividend_fee as u128);
-        let taxed_lamport: u128 = lamport_to_spend - dividends;
-        let amount_of_tokens: u128 = self.lamport_to_tokens(taxed_lamport);
-
-        amount_of_tokens
+    pub fn calculate_tokens_received(&mut self, lamport_to_spend: u128) -> Result<u128> {
+        Ok(self
+            .pool()
+            .calculate_tokens_received(lamport_to_spend)
+            .map_err(ProgramError::from)?)
     }
 
     /**
      * Function for the frontend to dynamically retrieve the price scaling of sell orders.
      */
-    pub fn calculate_lamports_received(&mut self, token_to_sell: u128) -> u128 {
-        if token_to_sell > self.token_supply || token_to_sell == 0 {
-            return 0;
-        }
-        let lamport = self.tokens_to_lamport(token_to_sell);
-        let dividends: u128 = lamport / (self.dividend_fee as u128);
-        let taxed_lamport: u128 = lamport - (dividends);
-
-        taxed_lamport
+    pub fn calculate_lamports_received(&mut self, token_to_sell: u128) -> Result<u128> {
+        Ok(self
+            .pool()
+            .calculate_lamports_received(token_to_sell)
+            .map_err(ProgramError::from)?)
     }
 }
 
 // CONSTANTS
 impl App {
-    pub const MAXIMUM_SIZE: usize = 1 + 1 + 16 + 16 + 16 + 16 + 8 + 16 + 16 + 1 + 1 + 20; //  20 bytes for token name and symbol
+    // everything but the name and symbol
+    const FIXED_SIZE: usize =
+        1 + 1 + 16 + 16 + 1 + 16 + 16 + 8 + 16 + 16 + 1 + 1 + 8 + 1 + 8 + 8 + 8 + 16 + 16 + 16;
+    pub const MAXIMUM_SIZE: usize = App::FIXED_SIZE + 20; //  20 bytes for token name and symbol
+
+    /**
+     * Account size that fits `name` and `symbol`, never below what `initialize` allocates.
+     */
+    pub fn space_for(name: &str, symbol: &str) -> usize {
+        let strings = 4 + name.len() + 4 + symbol.len();
+        (8 + App::FIXED_SIZE + strings).max(8 + App::MAXIMUM_SIZE + 10)
+    }
 }
 
 // Public functions
@@ -656,30 +1790,43 @@ impl App {
         program: &mut Account<'_, App>,
         admin_account: &Signer,
         admin_data_account: &mut Account<'_, User>,
-        name: String,
-        symbol: String,
-        decimals: u8,
+        registry: &mut Account<'_, AdminRegistry>,
+        params: InitTokenParams,
     ) -> Result<()> {
         require!(!program.is_initialized, ProgramError::AlreadyInitialized);
+        App::validate_params(&params)?;
 
         // Initialize main project state
-        program.name = name;
-        program.symbol = symbol;
-        program.decimals = decimals;
-        program.dividend_fee = 10;
-        program.token_initial_price = 100000;
-        program.token_incremental_price = 100;
-        program.magnitude = u64::pow(2, 32);
-        program.staking_requirement = LAMPORTS_IN_SOL * 2000; // 2000 coins
+        program.name = params.name;
+        program.symbol = params.symbol;
+        program.decimals = params.decimals;
+        program.dividend_fee = params.dividend_fee;
+        program.token_initial_price = params.token_initial_price;
+        program.token_incremental_price = params.token_incremental_price;
+        program.curve = params.curve;
+        program.magnitude = params.magnitude;
+        program.staking_requirement = params.staking_requirement;
         program.is_initialized = true;
         program.is_initial_phase = true;
+        program.admin_threshold = 1;
+        program.timelock_delay = DEFAULT_TIMELOCK_DELAY;
+        #[cfg(feature = "feature-dev")]
+        {
+            program.clock_offset = 0;
+        }
+        program.ambassador_max_purchase = params.ambassador_max_purchase;
+        program.ambassador_quota = params.ambassador_quota;
+        program.ambassador_purchased = 0;
 
         // Initialize the signer as the admin and ambassador
         admin_data_account.authority = admin_account.key();
         admin_data_account.balance = 0;
         admin_data_account.is_admin = true;
         admin_data_account.is_amb = true;
+        admin_data_account.roles = ALL_ROLES;
         admin_data_account.referred_by = Pubkey::default();
+        registry.admins = vec![admin_account.key()];
+        registry.pending = vec![];
 
         Ok(())
     }
@@ -694,13 +1841,14 @@ impl App {
         referred_by_data_account: &mut Option<Account<'_, User>>,
         lamports: u128,
         referred_by: Option<Pubkey>,
+        min_tokens_out: u128,
         sys_info: AccountInfo<'a>,
         token_program: AccountInfo<'a>,
         mint: AccountInfo<'a>,
         user_ata: AccountInfo<'a>,
         bump: u8,
         token_balance: u64,
-    ) -> Result<u128> {
+    ) -> Result<(TokenPurchase, Option<Skwizkey>, Option<InitialStageEnd>)> {
         if buyer_data_account
             .authority
             .key()
@@ -711,7 +1859,7 @@ impl App {
 
         program.owns_account(buyer, buyer_data_account)?;
         buyer_data_account.balance = token_balance as u128;
-        App::purchase_tokens(
+        let (purchase, skwizkey) = App::purchase_tokens(
             program,
             buyer,
             sys_info,
@@ -719,12 +1867,16 @@ impl App {
             referred_by_data_account,
             lamports,
             referred_by,
+            min_tokens_out,
             true,
             token_program,
             mint,
             user_ata,
             bump,
-        )
+        )?;
+        let stage_end = program.end_initial_stage_on_quota(buyer.key())?;
+
+        Ok((purchase, skwizkey, stage_end))
     }
 
     /**
@@ -734,12 +1886,13 @@ impl App {
         program: &mut Account<'a, App>,
         user: &mut Signer<'a>,
         user_data_account: &mut Account<'_, User>,
+        min_tokens_out: u128,
         sys_info: AccountInfo<'a>,
         token_program: AccountInfo<'a>,
         mint: AccountInfo<'a>,
         user_ata: AccountInfo<'a>,
         bump: u8,
-    ) -> Result<()> {
+    ) -> Result<(TokenPurchase, Reinvestment)> {
         if user_data_account
             .authority
             .key()
@@ -748,13 +1901,13 @@ impl App {
             user_data_account.authority = user.key();
         }
         program.owns_account(user, user_data_account)?;
-        let dividends = App::my_dividends(program, user_data_account, false);
+        let dividends = App::my_dividends(program, user_data_account, false)?;
         let mut none: Option<Account<User>> = None;
 
-        let updated_payouts = (dividends * program.magnitude as u128) as i128;
-        user_data_account.increase_payout_by(updated_payouts);
+        let updated_payouts = App::payout_of(dividends, program.magnitude as u128)?;
+        user_data_account.increase_payout_by(updated_payouts)?;
 
-        let tokens = App::purchase_tokens(
+        let (purchase, _) = App::purchase_tokens(
             program,
             user,
             sys_info,
@@ -762,6 +1915,7 @@ impl App {
             &mut none,
             dividends,
             None,
+            min_tokens_out,
             false,
             token_program,
             mint,
@@ -769,8 +1923,18 @@ impl App {
             bump,
         )?;
 
-        on_reinvestment(user.key(), dividends, tokens);
-        Ok(())
+        let reinvestment = Reinvestment {
+            customer: user.key(),
+            lamports_reinvested: dividends,
+            tokens_minted: purchase.tokens_minted,
+            token_supply: purchase.token_supply,
+            profit_per_share: purchase.profit_per_share,
+            price: purchase.price,
+            seq: program.next_event_seq()?,
+            nonce: user_data_account.next_nonce()?,
+        };
+
+        Ok((purchase, reinvestment))
     }
 
     /**
@@ -780,11 +1944,13 @@ impl App {
         program: &mut Account<'a, App>,
         user: &mut Signer<'a>,
         user_data_account: &mut Account<'a, User>,
+        min_lamports_out: u128,
         token_program: AccountInfo<'a>,
         mint: AccountInfo<'a>,
         user_ata: AccountInfo<'a>,
         bump: u8,
-    ) -> Result<()> {
+        grants: &[AccountInfo],
+    ) -> Result<(Option<TokenSell>, Withdrawal)> {
         require!(!program.is_initial_phase, ProgramError::IsInitialPhase);
 
         if user_data_account
@@ -795,23 +1961,26 @@ impl App {
             user_data_account.authority = user.key();
         }
         program.owns_account(user, user_data_account)?;
-        let tokens = user_data_account.usable_balance();
+        let tokens = user_data_account.usable_balance(grants, program.now()?)?;
+        let mut sale = None;
         if tokens > 0 {
-            App::sell(
+            sale = Some(App::sell(
                 program,
                 user,
                 user_data_account,
                 tokens,
+                min_lamports_out,
                 token_program,
                 mint,
                 user_ata,
                 bump,
-            )?;
+                grants,
+            )?);
         }
 
         // lambo delivery service
-        App::withdraw(program, user, user_data_account, true)?;
-        Ok(())
+        let withdrawal = App::withdraw(program, user, user_data_account, true)?;
+        Ok((sale, withdrawal))
     }
 
     /**
@@ -830,7 +1999,8 @@ impl App {
         token_program: AccountInfo<'a>,
         mint: AccountInfo<'a>,
         bump: u8,
-    ) -> Result<bool> {
+        grants: &[AccountInfo],
+    ) -> Result<(Option<Withdrawal>, TokenTransfer)> {
         require!(!program.is_initial_phase, ProgramError::IsInitialPhase);
         require_keys_neq!(to, user.key(), ProgramError::SelfTransfer);
 
@@ -855,22 +2025,27 @@ impl App {
         program.only_bagholders(user_data_account)?;
 
         // make sure we have the requested tokens
-        program.has_enough(user_data_account, amount_of_tokens)?;
+        program.has_enough(user_data_account, amount_of_tokens, grants)?;
 
-        let mut trans_amount: u128 = 0;
+        let mut withdrawal = None;
         // withdraw all outstanding dividends first
-        if App::my_dividends(program, user_data_account, true) > 0 {
-            trans_amount = App::withdraw(program, user, user_data_account, false)?;
+        if App::my_dividends(program, user_data_account, true)? > 0 {
+            withdrawal = Some(App::withdraw(program, user, user_data_account, false)?);
         }
 
         // liquify 10% of the tokens that are transfered
         // these are dispersed to shareholders
-        let token_fee = amount_of_tokens / (program.dividend_fee as u128);
-        let taxed_tokens = amount_of_tokens - token_fee;
-        let _dividends = App::tokens_to_lamport(program, token_fee);
+        let split =
+            SaleSplit::new(amount_of_tokens, program.dividend_fee).map_err(ProgramError::from)?;
+        let token_fee = split.dividends;
+        let taxed_tokens = split.taxed;
+        let _dividends = App::tokens_to_lamport(program, token_fee)?;
 
         // burn the fee tokens
-        program.token_supply -= token_fee;
+        program.token_supply = program
+            .token_supply
+            .checked_sub(token_fee)
+            .ok_or(ProgramError::MathUnderflow)?;
         // exchange tokens
         App::burn(
             user.to_account_info(),
@@ -891,25 +2066,36 @@ impl App {
         )?;
 
         // update dividend trackers
-        let from_payouts = (program.profit_per_share * amount_of_tokens) as i128;
-        let to_payouts = (program.profit_per_share * taxed_tokens) as i128;
+        let from_payouts = App::payout_of(program.profit_per_share, amount_of_tokens)?;
+        let to_payouts = App::payout_of(program.profit_per_share, taxed_tokens)?;
 
-        user_data_account.decrease_payout_by(from_payouts);
-        to_data_account.increase_payout_by(to_payouts);
+        user_data_account.decrease_payout_by(from_payouts)?;
+        to_data_account.increase_payout_by(to_payouts)?;
 
         // disperse dividends among holders
-        program.profit_per_share += (_dividends * program.magnitude as u128) / program.token_supply;
+        let dividends_per_share = program.dividends_per_share(_dividends)?;
+        program.profit_per_share = program
+            .profit_per_share
+            .checked_add(dividends_per_share)
+            .ok_or(ProgramError::MathOverflow)?;
 
         // fire event
-        on_transfer(
-            user_data_account.authority,
-            to_data_account.authority,
-            amount_of_tokens,
-        );
-
-        App::transfer_sol_out(program, user, trans_amount as u64)?;
-
-        Ok(true)
+        let token_transfer = TokenTransfer {
+            from: user_data_account.authority,
+            to: to_data_account.authority,
+            tokens: amount_of_tokens,
+            token_supply: program.token_supply,
+            profit_per_share: program.profit_per_share,
+            price: program.spot_price()?,
+            seq: program.next_event_seq()?,
+            from_nonce: user_data_account.next_nonce()?,
+            to_nonce: to_data_account.next_nonce()?,
+        };
+
+        let trans_amount = withdrawal.as_ref().map_or(0, |w| w.lamports_withdrawn);
+        App::transfer_sol_out(program, user, to_u64(trans_amount)?)?;
+
+        Ok((withdrawal, token_transfer))
     }
 
     /**
@@ -920,7 +2106,7 @@ impl App {
         user: &mut Signer,
         user_data_account: &mut Account<'_, User>,
         direct_call: bool,
-    ) -> Result<u128> {
+    ) -> Result<Withdrawal> {
         require!(!program.is_initial_phase, ProgramError::IsInitialPhase);
 
         if user_data_account
@@ -933,27 +2119,32 @@ impl App {
         program.owns_account(user, user_data_account)?;
         App::only_stronghands(program, user_data_account)?;
 
-        let mut dividends = App::my_dividends(program, user_data_account, false); // get ref. bonus later in the code
+        let mut dividends = App::my_dividends(program, user_data_account, false)?; // get ref. bonus later in the code
 
-        let updated_payouts = (dividends * program.magnitude as u128) as i128;
+        let updated_payouts = App::payout_of(dividends, program.magnitude as u128)?;
         // update dividend tracker
-        user_data_account.increase_payout_by(updated_payouts);
+        user_data_account.increase_payout_by(updated_payouts)?;
 
         // add ref. bonus
         let ref_amount = user_data_account.referred_balance;
-        dividends += ref_amount;
-        user_data_account.decrease_referred_balance_by(ref_amount);
+        dividends = dividends
+            .checked_add(ref_amount)
+            .ok_or(ProgramError::MathOverflow)?;
+        user_data_account.decrease_referred_balance_by(ref_amount)?;
 
         // lambo delivery service
         // Transfer from the app state
         if direct_call {
-            App::transfer_sol_out(program, user, dividends as u64)?;
+            App::transfer_sol_out(program, user, to_u64(dividends)?)?;
         }
 
         // fire event
-        on_withdraw(user_data_account.authority, dividends);
-
-        Ok(dividends)
+        Ok(Withdrawal {
+            customer: user_data_account.authority,
+            lamports_withdrawn: dividends,
+            seq: program.next_event_seq()?,
+            nonce: user_data_account.next_nonce()?,
+        })
     }
 
     /**
@@ -964,11 +2155,13 @@ impl App {
         user: &Signer<'a>,
         user_data_account: &mut Account<'a, User>,
         amount_of_tokens: u128,
+        min_lamports_out: u128,
         token_program: AccountInfo<'a>,
         mint: AccountInfo<'a>,
         user_ata: AccountInfo<'a>,
         bump: u8,
-    ) -> Result<()> {
+        grants: &[AccountInfo],
+    ) -> Result<TokenSell> {
         require!(!program.is_initial_phase, ProgramError::IsInitialPhase);
 
         if user_data_account
@@ -982,15 +2175,25 @@ impl App {
         program.only_bagholders(user_data_account)?;
 
         // russian hackers BTFO
-        program.has_enough(user_data_account, amount_of_tokens)?;
+        program.has_enough(user_data_account, amount_of_tokens, grants)?;
 
         let tokens = amount_of_tokens;
-        let _lamport = App::tokens_to_lamport(program, tokens);
-        let dividends = _lamport / program.dividend_fee as u128;
-        let taxed_lamport = _lamport - dividends;
+        let _lamport = App::tokens_to_lamport(program, tokens)?;
+        let split = SaleSplit::new(_lamport, program.dividend_fee).map_err(ProgramError::from)?;
+        let dividends = split.dividends;
+        let taxed_lamport = split.taxed;
+        // the price moved against the seller since they were quoted
+        require_gte!(
+            taxed_lamport,
+            min_lamports_out,
+            ProgramError::LamportsBelowMinimum
+        );
 
         // burn the sold tokens
-        program.token_supply -= tokens;
+        program.token_supply = program
+            .token_supply
+            .checked_sub(tokens)
+            .ok_or(ProgramError::MathUnderflow)?;
 
         App::burn(
             user.to_account_info(),
@@ -1003,26 +2206,38 @@ impl App {
         )?;
 
         // update dividends tracker
-        let updated_payouts = (program.profit_per_share as i128 * tokens as i128
-            + (taxed_lamport as i128 * program.magnitude as i128))
-            as i128;
-        user_data_account.decrease_payout_by(updated_payouts);
+        let updated_payouts = App::payout_of(program.profit_per_share, tokens)?
+            .checked_add(App::payout_of(taxed_lamport, program.magnitude as u128)?)
+            .ok_or(ProgramError::MathOverflow)?;
+        user_data_account.decrease_payout_by(updated_payouts)?;
 
         // dividing by zero is a bad idea
         if program.token_supply > 0 {
+            let dividends_per_share = program.dividends_per_share(dividends)?;
             // update the amount of dividends per token
-            program.profit_per_share +=
-                (dividends * program.magnitude as u128) / program.token_supply;
+            program.profit_per_share = program
+                .profit_per_share
+                .checked_add(dividends_per_share)
+                .ok_or(ProgramError::MathOverflow)?;
         }
 
         // fire event
-        on_token_sell(user_data_account.authority, tokens, taxed_lamport);
-
-        Ok(())
+        Ok(TokenSell {
+            customer: user_data_account.authority,
+            tokens_burned: tokens,
+            lamports_earned: taxed_lamport,
+            token_supply: program.token_supply,
+            profit_per_share: program.profit_per_share,
+            price: program.spot_price()?,
+            seq: program.next_event_seq()?,
+            nonce: user_data_account.next_nonce()?,
+        })
     }
 
     /**
      * Transfer to whitelisted wallets.
+     * Needs no proposal under a multisig: the distributor hands out tokens it bought itself,
+     * while `revoke_vesting` burns tokens another user holds.
      */
     pub fn distribute_token<'a>(
         program: &mut Account<'a, App>,
@@ -1032,20 +2247,34 @@ impl App {
         from_ata: AccountInfo<'a>,
         receipient_data_account: &mut Account<'a, User>,
         receipient_ata: AccountInfo<'a>,
+        vesting: &mut Account<'a, Vesting>,
         amount_of_tokens: u128,
         payout: i128,
         token_program: AccountInfo<'a>,
         mint: AccountInfo<'a>,
         bump: u8,
-        s_timestamp: i64,
-        e_timestamp: i64,
-    ) -> Result<()> {
+        schedule: VestingSchedule,
+    ) -> Result<VestingGrant> {
         require!(program.is_initial_phase, ProgramError::IsPostInitialPhase);
-        require!(
-            from_data_account.has_admin_rights(),
-            ProgramError::NotAnAdmin
+        program.check_role(from_data_account, ROLE_DISTRIBUTOR)?;
+        App::validate_vesting_schedule(&schedule)?;
+        require_gt!(
+            MAX_VESTING_GRANTS,
+            receipient_data_account.vesting_count,
+            ProgramError::VestingGrantLimit
         );
 
+        let start_time = match schedule.start_time {
+            Some(start_time) => start_time,
+            None => program.now()?,
+        };
+        let cliff_time = start_time
+            .checked_add(schedule.cliff)
+            .ok_or(ProgramError::MathOverflow)?;
+        let end_time = start_time
+            .checked_add(schedule.duration)
+            .ok_or(ProgramError::MathOverflow)?;
+
         if receipient_data_account
             .authority
             .key()
@@ -1074,15 +2303,172 @@ impl App {
         )?;
 
         // update dividends tracke
-        from_data_account.decrease_payout_by(payout);
-        receipient_data_account.increase_payout_by(payout);
+        from_data_account.decrease_payout_by(payout)?;
+        receipient_data_account.increase_payout_by(payout)?;
+
+        // earlier grants keep their own schedules
+        vesting.receipient = receipient;
+        vesting.index = receipient_data_account.vesting_count;
+        vesting.total_locked = amount_of_tokens;
+        vesting.start_time = start_time;
+        vesting.end_time = end_time;
+        vesting.cliff_time = cliff_time;
+        vesting.release_interval = schedule.release_interval;
+        receipient_data_account.vesting_count = receipient_data_account
+            .vesting_count
+            .checked_add(1)
+            .ok_or(ProgramError::MathOverflow)?;
+        receipient_data_account.vesting_end_time =
+            receipient_data_account.vesting_end_time.max(end_time);
+
+        Ok(VestingGrant {
+            admin: from_data_account.authority,
+            receipient,
+            amount: amount_of_tokens,
+            start_time,
+            end_time,
+            cliff_time,
+            release_interval: schedule.release_interval,
+            payout_adjustment: payout,
+            index: vesting.index,
+            seq: program.next_event_seq()?,
+            admin_nonce: from_data_account.next_nonce()?,
+            receipient_nonce: receipient_data_account.next_nonce()?,
+        })
+    }
 
-        //
-        receipient_data_account.total_locked = amount_of_tokens;
-        receipient_data_account.usable_locked = 0;
-        receipient_data_account.locked_starttime = s_timestamp;
-        receipient_data_account.locked_endtime = e_timestamp;
+    /**
+     * Burns what a vesting grant still locks, vested tokens stay with the holder.
+     * The value of the burned tokens is shared among the holders, like a sale whose proceeds
+     * all go to dividends, while the holder keeps the dividends the tokens already earned.
+     */
+    pub fn revoke_vesting<'a>(
+        program: &mut Account<'a, App>,
+        admin: &mut Account<'a, User>,
+        receipient_data_account: &mut Account<'a, User>,
+        receipient_ata: AccountInfo<'a>,
+        vesting: &mut Account<'a, Vesting>,
+        token_program: AccountInfo<'a>,
+        mint: AccountInfo<'a>,
+        bump: u8,
+        grants: &[AccountInfo],
+    ) -> Result<VestingRevocation> {
+        program.check_role(admin, ROLE_DISTRIBUTOR)?;
+        program.only_single_signer()?;
 
-        Ok(())
+        program.revoke_grant(
+            admin.authority,
+            receipient_data_account,
+            receipient_ata,
+            vesting,
+            token_program,
+            mint,
+            bump,
+            grants,
+        )
+    }
+
+    /**
+     * Executes a revocation proposal, on the grant the proposal names.
+     */
+    pub fn execute_revocation_proposal<'a>(
+        program: &mut Account<'a, App>,
+        registry: &Account<'a, AdminRegistry>,
+        executor: &Signer<'a>,
+        proposal: &mut Account<'a, Proposal>,
+        receipient_data_account: &mut Account<'a, User>,
+        receipient_ata: AccountInfo<'a>,
+        vesting: &mut Account<'a, Vesting>,
+        token_program: AccountInfo<'a>,
+        mint: AccountInfo<'a>,
+        bump: u8,
+        grants: &[AccountInfo],
+    ) -> Result<(ProposalExecution, VestingRevocation)> {
+        let AdminAction::RevokeVesting { receipient, index } = proposal.action else {
+            return err!(ProgramError::WrongExecuteInstruction);
+        };
+        require!(
+            vesting.receipient == receipient && vesting.index == index,
+            ProgramError::InvalidTargetAccount
+        );
+        let execution = App::start_execution(program, registry, executor, proposal)?;
+        let revocation = program.revoke_grant(
+            proposal.proposer,
+            receipient_data_account,
+            receipient_ata,
+            vesting,
+            token_program,
+            mint,
+            bump,
+            grants,
+        )?;
+
+        Ok((execution, revocation))
+    }
+
+    // Burns what the grant still locks and hands its value to the other holders as dividends
+    fn revoke_grant<'a>(
+        &mut self,
+        admin: Pubkey,
+        receipient_data_account: &mut Account<'a, User>,
+        receipient_ata: AccountInfo<'a>,
+        vesting: &mut Account<'a, Vesting>,
+        token_program: AccountInfo<'a>,
+        mint: AccountInfo<'a>,
+        bump: u8,
+        grants: &[AccountInfo],
+    ) -> Result<VestingRevocation> {
+        let program = self;
+        let now = program.now()?;
+        let tokens = vesting.locked_at(now)?;
+        require_gt!(tokens, 0, ProgramError::NothingToRevoke);
+
+        // the grant keeps what has vested and locks nothing from now on
+        vesting.total_locked = vesting
+            .total_locked
+            .checked_sub(tokens)
+            .ok_or(ProgramError::MathUnderflow)?;
+        vesting.end_time = now;
+        receipient_data_account.update_vesting_end_time(grants, vesting)?;
+
+        let dividends = App::tokens_to_lamport(program, tokens)?;
+        program.token_supply = program
+            .token_supply
+            .checked_sub(tokens)
+            .ok_or(ProgramError::MathUnderflow)?;
+
+        App::burn_as_delegate(
+            receipient_data_account,
+            tokens,
+            token_program,
+            mint,
+            receipient_ata,
+            bump,
+        )?;
+
+        // update dividends tracker
+        receipient_data_account
+            .decrease_payout_by(App::payout_of(program.profit_per_share, tokens)?)?;
+
+        // dividing by zero is a bad idea
+        if program.token_supply > 0 {
+            let dividends_per_share = program.dividends_per_share(dividends)?;
+            program.profit_per_share = program
+                .profit_per_share
+                .checked_add(dividends_per_share)
+                .ok_or(ProgramError::MathOverflow)?;
+        }
+
+        Ok(VestingRevocation {
+            admin,
+            receipient: vesting.receipient,
+            index: vesting.index,
+            tokens_burned: tokens,
+            lamports_redistributed: dividends,
+            token_supply: program.token_supply,
+            profit_per_share: program.profit_per_share,
+            seq: program.next_event_seq()?,
+            nonce: receipient_data_account.next_nonce()?,
+        })
     }
 }
@@ -1,12 +1,6 @@
-pub fn sqrt(x: u128) -> u128 {
-    // Use fixed-point arithmetic to approximate square root using the Newton-Raphson method
-    let mut z = (x + 1) / 2;
-    let mut y = x;
+use {crate::errors::ProgramError, anchor_lang::prelude::*};
 
-    while z < y {
-        y = z;
-        z = (x / z + z) / 2;
-    }
-
-    y
+// Token and lamport amounts are u64 once they leave the program's u128 bookkeeping
+pub fn to_u64(amount: u128) -> Result<u64> {
+    Ok(u64::try_from(amount).map_err(|_| ProgramError::AmountTooLarge)?)
 }
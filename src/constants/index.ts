@@ -4,10 +4,27 @@ export const USER = "users";
 export const PROGRAM = "program";
 export const MINT = "mint";
 export const METADATA_SEED = "metadata";
+export const EVENT_AUTHORITY = "__event_authority";
+export const PROPOSAL = "proposal";
+export const ADMIN_REGISTRY = "admins";
+export const VESTING = "vesting";
 export const MPL_TOKEN_METADATA_PROGRAM_ID: PublicKey = new PublicKey(
   "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"
 );
 
+// Bits of `User.roles`
+export const ROLES = {
+  DISTRIBUTOR: 1 << 1,
+  METADATA_MANAGER: 1 << 3,
+  PARAMETER_MANAGER: 1 << 2,
+  PHASE_MANAGER: 1 << 0,
+};
+export const ALL_ROLES =
+  ROLES.PHASE_MANAGER |
+  ROLES.DISTRIBUTOR |
+  ROLES.PARAMETER_MANAGER |
+  ROLES.METADATA_MANAGER;
+
 export const INSTRUCTIONS = {
   IX_BUY: "buy",
   IX_EXIT: "exit",
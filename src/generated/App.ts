@@ -33,19 +33,62 @@ export type App = {
             type: "i128";
           },
           {
-            name: "usableLocked";
+            name: "vestingCount";
+            type: "u64";
+          },
+          {
+            name: "vestingEndTime";
+            type: "i64";
+          },
+          {
+            name: "nonce";
+            type: "u64";
+          },
+          {
+            name: "roles";
+            type: "u8";
+          },
+          {
+            name: "ambassadorPurchased";
             type: "u128";
+          }
+        ];
+        kind: "struct";
+      };
+    },
+    {
+      docs: [
+        "* Tokens locked by one `distribute_token` grant, a PDA of `[vesting, receipient, index]`."
+      ];
+      name: "vesting";
+      type: {
+        fields: [
+          {
+            name: "receipient";
+            type: "publicKey";
+          },
+          {
+            name: "index";
+            type: "u64";
           },
           {
             name: "totalLocked";
             type: "u128";
           },
           {
-            name: "lockedStarttime";
+            name: "startTime";
+            type: "i64";
+          },
+          {
+            name: "endTime";
+            type: "i64";
+          },
+          {
+            name: "cliffTime";
             type: "i64";
           },
           {
-            name: "lockedEndtime";
+            name: "releaseInterval";
             type: "i64";
           }
         ];
@@ -80,6 +123,12 @@ export type App = {
             name: "tokenIncrementalPrice";
             type: "u128";
           },
+          {
+            name: "curve";
+            type: {
+              defined: "CurveKind";
+            };
+          },
           {
             name: "contractBalance";
             type: "u128";
@@ -107,6 +156,102 @@ export type App = {
           {
             name: "isInitialPhase";
             type: "bool";
+          },
+          {
+            name: "eventSeq";
+            type: "u64";
+          },
+          {
+            name: "adminThreshold";
+            type: "u8";
+          },
+          {
+            name: "proposalCount";
+            type: "u64";
+          },
+          {
+            name: "timelockDelay";
+            type: "i64";
+          },
+          {
+            name: "ambassadorMaxPurchase";
+            type: "u128";
+          },
+          {
+            name: "ambassadorQuota";
+            type: "u128";
+          },
+          {
+            name: "ambassadorPurchased";
+            type: "u128";
+          },
+          {
+            name: "clockOffset";
+            type: "i64";
+          }
+        ];
+        kind: "struct";
+      };
+    },
+    {
+      name: "proposal";
+      type: {
+        fields: [
+          {
+            name: "id";
+            type: "u64";
+          },
+          {
+            name: "proposer";
+            type: "publicKey";
+          },
+          {
+            name: "action";
+            type: {
+              defined: "AdminAction";
+            };
+          },
+          {
+            name: "approvals";
+            type: {
+              vec: "publicKey";
+            };
+          },
+          {
+            name: "status";
+            type: {
+              defined: "ProposalStatus";
+            };
+          },
+          {
+            name: "createdAt";
+            type: "i64";
+          },
+          {
+            name: "eta";
+            type: "i64";
+          }
+        ];
+        kind: "struct";
+      };
+    },
+    {
+      name: "adminRegistry";
+      type: {
+        fields: [
+          {
+            name: "admins";
+            type: {
+              vec: "publicKey";
+            };
+          },
+          {
+            name: "pending";
+            type: {
+              vec: {
+                defined: "PendingAdmin";
+              };
+            };
           }
         ];
         kind: "struct";
@@ -173,932 +318,4902 @@ export type App = {
       code: 6011;
       msg: "You can't transfer to yourself";
       name: "SelfTransfer";
+    },
+    {
+      code: 6012;
+      msg: "Dividend fee divisor must be greater than zero";
+      name: "InvalidDividendFee";
+    },
+    {
+      code: 6013;
+      msg: "Token prices are out of bounds";
+      name: "InvalidTokenPrice";
+    },
+    {
+      code: 6014;
+      msg: "Magnitude must be greater than zero";
+      name: "InvalidMagnitude";
+    },
+    {
+      code: 6015;
+      msg: "You would receive fewer tokens than the minimum requested";
+      name: "TokensBelowMinimum";
+    },
+    {
+      code: 6016;
+      msg: "You would receive fewer lamports than the minimum requested";
+      name: "LamportsBelowMinimum";
+    },
+    {
+      code: 6017;
+      msg: "Transaction is past its deadline";
+      name: "DeadlineExceeded";
+    },
+    {
+      code: 6018;
+      msg: "Arithmetic overflow";
+      name: "MathOverflow";
+    },
+    {
+      code: 6019;
+      msg: "Arithmetic underflow";
+      name: "MathUnderflow";
+    },
+    {
+      code: 6020;
+      msg: "Division by zero";
+      name: "DivisionByZero";
+    },
+    {
+      code: 6021;
+      msg: "Amount doesn't fit in a u64";
+      name: "AmountTooLarge";
+    },
+    {
+      code: 6022;
+      msg: "Admin changes need an approved proposal";
+      name: "ProposalRequired";
+    },
+    {
+      code: 6023;
+      msg: "Threshold must be between one and the approval limit";
+      name: "InvalidThreshold";
+    },
+    {
+      code: 6024;
+      msg: "Proposal was already executed or cancelled";
+      name: "ProposalNotActive";
+    },
+    {
+      code: 6025;
+      msg: "You already approved this proposal";
+      name: "AlreadyApproved";
+    },
+    {
+      code: 6026;
+      msg: "Proposal can't take more approvals";
+      name: "ApprovalLimitReached";
+    },
+    {
+      code: 6027;
+      msg: "Proposal doesn't have enough approvals";
+      name: "NotEnoughApprovals";
+    },
+    {
+      code: 6028;
+      msg: "Target account doesn't match the proposal";
+      name: "InvalidTargetAccount";
+    },
+    {
+      code: 6029;
+      msg: "This change is timelocked, it needs a proposal";
+      name: "TimelockRequired";
+    },
+    {
+      code: 6030;
+      msg: "Timelock delay must be between zero and thirty days";
+      name: "InvalidTimelockDelay";
+    },
+    {
+      code: 6031;
+      msg: "Proposal isn't queued yet";
+      name: "ProposalNotQueued";
+    },
+    {
+      code: 6032;
+      msg: "Proposal is still in its timelock";
+      name: "TimelockNotElapsed";
+    },
+    {
+      code: 6033;
+      msg: "The last admin can't be removed";
+      name: "LastAdmin";
+    },
+    {
+      code: 6034;
+      msg: "Removing this admin would leave fewer admins than the threshold";
+      name: "AdminCountBelowThreshold";
+    },
+    {
+      code: 6035;
+      msg: "User is already an admin";
+      name: "AlreadyAdmin";
+    },
+    {
+      code: 6036;
+      msg: "User was already granted admin";
+      name: "AlreadyNominated";
+    },
+    {
+      code: 6037;
+      msg: "You weren't granted admin";
+      name: "NotNominated";
+    },
+    {
+      code: 6038;
+      msg: "Admin registry is full";
+      name: "AdminRegistryFull";
+    },
+    {
+      code: 6039;
+      msg: "You don't have the role this needs";
+      name: "MissingRole";
+    },
+    {
+      code: 6040;
+      msg: "Unknown role";
+      name: "InvalidRoles";
+    },
+    {
+      code: 6041;
+      msg: "Vesting needs a positive duration, with the cliff and release interval within it";
+      name: "InvalidVestingSchedule";
+    },
+    {
+      code: 6042;
+      msg: "The user's vesting grants must be passed in grant order";
+      name: "MissingVestingGrants";
+    },
+    {
+      code: 6043;
+      msg: "User can't receive more vesting grants";
+      name: "VestingGrantLimit";
+    },
+    {
+      code: 6044;
+      msg: "Grant has nothing left to revoke";
+      name: "NothingToRevoke";
+    },
+    {
+      code: 6045;
+      msg: "Only feature-dev builds can move the clock";
+      name: "DevBuildOnly";
+    },
+    {
+      code: 6046;
+      msg: "Purchase exceeds what an ambassador can buy during the initial phase";
+      name: "AmbassadorCapExceeded";
+    },
+    {
+      code: 6047;
+      msg: "Token prices leave the curve too little supply";
+      name: "CurveSupplyTooLow";
+    },
+    {
+      code: 6048;
+      msg: "The ambassador cap must be positive and the quota at least the cap";
+      name: "InvalidAmbassadorQuota";
+    },
+    {
+      code: 6049;
+      msg: "The token must use 9 decimals, the unit its prices are quoted in";
+      name: "InvalidDecimals";
+    },
+    {
+      code: 6050;
+      msg: "Purchase would take the supply past what the curve can price";
+      name: "SupplyLimitExceeded";
+    },
+    {
+      code: 6051;
+      msg: "Name, symbol or uri is longer than the metadata allows";
+      name: "MetadataTooLong";
+    },
+    {
+      code: 6052;
+      msg: "This proposal is executed by the instruction made for its action";
+      name: "WrongExecuteInstruction";
+    },
+    {
+      code: 6053;
+      msg: "Purchase would take ambassadors past the initial phase quota";
+      name: "AmbassadorQuotaExceeded";
     }
   ];
-  instructions: [
+  events: [
     {
-      accounts: [
-        {
-          isMut: true;
-          isSigner: true;
-          name: "admin";
-        },
+      fields: [
         {
-          isMut: true;
-          isSigner: false;
-          name: "adminData";
+          index: false;
+          name: "customer";
+          type: "publicKey";
         },
         {
-          isMut: true;
-          isSigner: false;
-          name: "metadata";
+          index: false;
+          name: "incomingLamports";
+          type: "u128";
         },
         {
-          docs: ["CHECK gets created by the program"];
-          isMut: true;
-          isSigner: false;
-          name: "mint";
+          index: false;
+          name: "tokensMinted";
+          type: "u128";
         },
         {
-          isMut: true;
-          isSigner: false;
-          name: "programData";
+          index: false;
+          name: "referredBy";
+          type: "publicKey";
         },
         {
-          isMut: false;
-          isSigner: false;
-          name: "rent";
+          index: false;
+          name: "tokenSupply";
+          type: "u128";
         },
         {
-          isMut: false;
-          isSigner: false;
-          name: "sysvarInstructions";
+          index: false;
+          name: "profitPerShare";
+          type: "u128";
         },
         {
-          isMut: false;
-          isSigner: false;
-          name: "systemProgram";
+          index: false;
+          name: "price";
+          type: "u128";
         },
         {
-          isMut: false;
-          isSigner: false;
-          name: "tokenProgram";
+          index: false;
+          name: "seq";
+          type: "u64";
         },
         {
-          isMut: false;
-          isSigner: false;
-          name: "tokenMetadataProgram";
-        }
-      ];
-      args: [
-        {
-          name: "metadata";
-          type: {
-            defined: "InitTokenParams";
-          };
+          index: false;
+          name: "nonce";
+          type: "u64";
         }
       ];
-      name: "initialize";
+      name: "TokenPurchase";
     },
     {
-      accounts: [
+      fields: [
         {
-          isMut: true;
-          isSigner: false;
-          name: "mint";
+          index: false;
+          name: "customer";
+          type: "publicKey";
         },
         {
-          isMut: true;
-          isSigner: true;
-          name: "user";
+          index: false;
+          name: "tokensBurned";
+          type: "u128";
         },
         {
-          isMut: true;
-          isSigner: false;
-          name: "userData";
+          index: false;
+          name: "lamportsEarned";
+          type: "u128";
         },
         {
-          isMut: true;
-          isSigner: false;
-          name: "userAta";
+          index: false;
+          name: "tokenSupply";
+          type: "u128";
         },
         {
-          isMut: true;
-          isOptional: true;
-          isSigner: false;
-          name: "referredByData";
+          index: false;
+          name: "profitPerShare";
+          type: "u128";
         },
         {
-          isMut: true;
-          isSigner: false;
-          name: "programData";
+          index: false;
+          name: "price";
+          type: "u128";
         },
         {
-          isMut: false;
-          isSigner: false;
-          name: "systemProgram";
-        },
-        {
-          isMut: false;
-          isSigner: false;
-          name: "tokenProgram";
-        },
-        {
-          isMut: false;
-          isSigner: false;
-          name: "associatedTokenProgram";
-        }
-      ];
-      args: [
-        {
-          name: "lamportsToSend";
-          type: "u128";
+          index: false;
+          name: "seq";
+          type: "u64";
         },
         {
-          name: "referredBy";
-          type: {
-            option: "publicKey";
-          };
+          index: false;
+          name: "nonce";
+          type: "u64";
         }
       ];
-      name: "buy";
+      name: "TokenSell";
     },
     {
-      accounts: [
+      fields: [
         {
-          isMut: true;
-          isSigner: false;
-          name: "mint";
+          index: false;
+          name: "customer";
+          type: "publicKey";
         },
         {
-          isMut: true;
-          isSigner: true;
-          name: "user";
+          index: false;
+          name: "lamportsReinvested";
+          type: "u128";
         },
         {
-          isMut: true;
-          isSigner: false;
-          name: "userData";
+          index: false;
+          name: "tokensMinted";
+          type: "u128";
         },
         {
-          isMut: true;
-          isSigner: false;
-          name: "userAta";
+          index: false;
+          name: "tokenSupply";
+          type: "u128";
         },
         {
-          isMut: true;
-          isSigner: false;
-          name: "programData";
+          index: false;
+          name: "profitPerShare";
+          type: "u128";
         },
         {
-          isMut: false;
-          isSigner: false;
-          name: "systemProgram";
+          index: false;
+          name: "price";
+          type: "u128";
         },
         {
-          isMut: false;
-          isSigner: false;
-          name: "tokenProgram";
+          index: false;
+          name: "seq";
+          type: "u64";
         },
         {
-          isMut: false;
-          isSigner: false;
-          name: "associatedTokenProgram";
+          index: false;
+          name: "nonce";
+          type: "u64";
         }
       ];
-      args: [];
-      name: "reinvest";
+      name: "Reinvestment";
     },
     {
-      accounts: [
+      fields: [
         {
-          isMut: true;
-          isSigner: false;
-          name: "mint";
+          index: false;
+          name: "customer";
+          type: "publicKey";
         },
         {
-          isMut: true;
-          isSigner: true;
-          name: "user";
+          index: false;
+          name: "lamportsWithdrawn";
+          type: "u128";
         },
         {
-          isMut: true;
-          isSigner: false;
-          name: "userData";
+          index: false;
+          name: "seq";
+          type: "u64";
         },
         {
-          isMut: true;
-          isSigner: false;
-          name: "userAta";
+          index: false;
+          name: "nonce";
+          type: "u64";
+        }
+      ];
+      name: "Withdrawal";
+    },
+    {
+      fields: [
+        {
+          index: false;
+          name: "referrer";
+          type: "publicKey";
         },
         {
-          isMut: true;
-          isSigner: false;
-          name: "programData";
+          index: false;
+          name: "customer";
+          type: "publicKey";
         },
         {
-          isMut: false;
-          isSigner: false;
-          name: "systemProgram";
+          index: false;
+          name: "lamportsUsed";
+          type: "u128";
         },
         {
-          isMut: false;
-          isSigner: false;
-          name: "tokenProgram";
+          index: false;
+          name: "bonusReceived";
+          type: "u128";
         },
         {
-          isMut: false;
-          isSigner: false;
-          name: "associatedTokenProgram";
+          index: false;
+          name: "seq";
+          type: "u64";
+        },
+        {
+          index: false;
+          name: "referrerNonce";
+          type: "u64";
         }
       ];
-      args: [];
-      name: "exit";
+      name: "Skwizkey";
     },
     {
-      accounts: [
-        {
-          isMut: true;
-          isSigner: false;
-          name: "mint";
-        },
-        {
-          isMut: true;
-          isSigner: true;
-          name: "user";
-        },
+      fields: [
         {
-          isMut: true;
-          isSigner: false;
-          name: "userData";
+          index: false;
+          name: "from";
+          type: "publicKey";
         },
         {
-          isMut: true;
-          isSigner: false;
-          name: "userAta";
+          index: false;
+          name: "to";
+          type: "publicKey";
         },
         {
-          isMut: true;
-          isSigner: false;
-          name: "toData";
+          index: false;
+          name: "tokens";
+          type: "u128";
         },
         {
-          isMut: false;
-          isSigner: false;
-          name: "toInfo";
+          index: false;
+          name: "tokenSupply";
+          type: "u128";
         },
         {
-          isMut: true;
-          isSigner: false;
-          name: "toAta";
+          index: false;
+          name: "profitPerShare";
+          type: "u128";
         },
         {
-          isMut: true;
-          isSigner: false;
-          name: "programData";
+          index: false;
+          name: "price";
+          type: "u128";
         },
         {
-          isMut: false;
-          isSigner: false;
-          name: "systemProgram";
+          index: false;
+          name: "seq";
+          type: "u64";
         },
         {
-          isMut: false;
-          isSigner: false;
-          name: "tokenProgram";
+          index: false;
+          name: "fromNonce";
+          type: "u64";
         },
         {
-          isMut: false;
-          isSigner: false;
-          name: "associatedTokenProgram";
+          index: false;
+          name: "toNonce";
+          type: "u64";
         }
       ];
-      args: [
+      name: "TokenTransfer";
+    },
+    {
+      fields: [
         {
-          name: "to";
+          index: false;
+          name: "admin";
           type: "publicKey";
         },
         {
-          name: "lamportsToSend";
-          type: "u128";
+          index: false;
+          name: "wasInitialPhase";
+          type: "bool";
+        },
+        {
+          index: false;
+          name: "seq";
+          type: "u64";
         }
       ];
-      name: "transfer";
+      name: "InitialStageEnd";
     },
     {
-      accounts: [
+      fields: [
         {
-          isMut: true;
-          isSigner: false;
-          name: "mint";
+          index: false;
+          name: "admin";
+          type: "publicKey";
         },
         {
-          isMut: true;
-          isSigner: true;
+          index: false;
           name: "user";
+          type: "publicKey";
         },
         {
-          isMut: true;
-          isSigner: false;
-          name: "userData";
-        },
-        {
-          isMut: true;
-          isSigner: false;
-          name: "userAta";
+          index: false;
+          name: "oldStatus";
+          type: "bool";
         },
         {
-          isMut: true;
-          isSigner: false;
-          name: "programData";
+          index: false;
+          name: "newStatus";
+          type: "bool";
         },
         {
-          isMut: false;
-          isSigner: false;
-          name: "systemProgram";
+          index: false;
+          name: "adminCount";
+          type: "u8";
         },
         {
-          isMut: false;
-          isSigner: false;
-          name: "tokenProgram";
+          index: false;
+          name: "seq";
+          type: "u64";
         },
         {
-          isMut: false;
-          isSigner: false;
-          name: "associatedTokenProgram";
+          index: false;
+          name: "nonce";
+          type: "u64";
         }
       ];
-      args: [];
-      name: "withdraw";
+      name: "AdministratorUpdate";
     },
     {
-      accounts: [
+      fields: [
         {
-          isMut: true;
-          isSigner: false;
-          name: "mint";
+          index: false;
+          name: "admin";
+          type: "publicKey";
         },
         {
-          isMut: true;
-          isSigner: true;
+          index: false;
           name: "user";
+          type: "publicKey";
         },
         {
-          isMut: true;
-          isSigner: false;
-          name: "userData";
-        },
+          index: false;
+          name: "seq";
+          type: "u64";
+        }
+      ];
+      name: "AdminNomination";
+    },
+    {
+      fields: [
         {
-          isMut: true;
-          isSigner: false;
-          name: "userAta";
+          index: false;
+          name: "admin";
+          type: "publicKey";
         },
         {
-          isMut: true;
-          isSigner: false;
-          name: "programData";
+          index: false;
+          name: "user";
+          type: "publicKey";
         },
         {
-          isMut: false;
-          isSigner: false;
-          name: "systemProgram";
+          index: false;
+          name: "oldRoles";
+          type: "u8";
         },
         {
-          isMut: false;
-          isSigner: false;
-          name: "tokenProgram";
+          index: false;
+          name: "newRoles";
+          type: "u8";
         },
         {
-          isMut: false;
-          isSigner: false;
-          name: "associatedTokenProgram";
-        }
-      ];
-      args: [
+          index: false;
+          name: "seq";
+          type: "u64";
+        },
         {
-          name: "lamportsToSend";
-          type: "u128";
+          index: false;
+          name: "nonce";
+          type: "u64";
         }
       ];
-      name: "sell";
+      name: "RolesUpdate";
     },
     {
-      accounts: [
+      fields: [
         {
-          isMut: true;
-          isSigner: true;
+          index: false;
+          name: "admin";
+          type: "publicKey";
+        },
+        {
+          index: false;
           name: "user";
+          type: "publicKey";
         },
         {
-          isMut: true;
-          isSigner: false;
-          name: "userData";
+          index: false;
+          name: "oldStatus";
+          type: "bool";
         },
         {
-          isMut: true;
-          isSigner: false;
-          name: "programData";
+          index: false;
+          name: "newStatus";
+          type: "bool";
         },
         {
-          isMut: false;
-          isSigner: false;
-          name: "systemProgram";
+          index: false;
+          name: "seq";
+          type: "u64";
+        },
+        {
+          index: false;
+          name: "nonce";
+          type: "u64";
         }
       ];
-      args: [];
-      name: "disableInitialStage";
+      name: "AmbassadorUpdate";
     },
     {
-      accounts: [
+      fields: [
         {
-          isMut: true;
-          isSigner: false;
-          name: "mint";
+          index: false;
+          name: "admin";
+          type: "publicKey";
         },
         {
-          isMut: true;
-          isSigner: true;
-          name: "user";
+          index: false;
+          name: "oldAmount";
+          type: "u128";
         },
         {
-          isMut: true;
-          isSigner: false;
-          name: "fromData";
+          index: false;
+          name: "newAmount";
+          type: "u128";
         },
         {
-          isMut: true;
-          isSigner: false;
-          name: "fromAta";
+          index: false;
+          name: "seq";
+          type: "u64";
+        }
+      ];
+      name: "StakingRequirementUpdate";
+    },
+    {
+      fields: [
+        {
+          index: false;
+          name: "admin";
+          type: "publicKey";
         },
         {
-          isMut: false;
-          isSigner: false;
-          name: "receipientInfo";
+          index: false;
+          name: "receipient";
+          type: "publicKey";
         },
         {
-          isMut: true;
-          isSigner: false;
-          name: "receipientData";
+          index: false;
+          name: "amount";
+          type: "u128";
         },
         {
-          isMut: true;
-          isSigner: false;
-          name: "receipientAta";
+          index: false;
+          name: "startTime";
+          type: "i64";
         },
         {
-          isMut: true;
-          isSigner: false;
-          name: "programData";
+          index: false;
+          name: "endTime";
+          type: "i64";
         },
         {
-          isMut: false;
-          isSigner: false;
-          name: "systemProgram";
+          index: false;
+          name: "cliffTime";
+          type: "i64";
         },
         {
-          isMut: false;
-          isSigner: false;
-          name: "tokenProgram";
+          index: false;
+          name: "releaseInterval";
+          type: "i64";
         },
         {
-          isMut: false;
-          isSigner: false;
-          name: "associatedTokenProgram";
-        }
-      ];
-      args: [
+          index: false;
+          name: "payoutAdjustment";
+          type: "i128";
+        },
         {
-          name: "amountOfTokens";
-          type: "u128";
+          index: false;
+          name: "index";
+          type: "u64";
         },
         {
-          name: "updatePayoutBy";
-          type: "i128";
+          index: false;
+          name: "seq";
+          type: "u64";
         },
         {
-          name: "receipient";
-          type: "publicKey";
+          index: false;
+          name: "adminNonce";
+          type: "u64";
+        },
+        {
+          index: false;
+          name: "receipientNonce";
+          type: "u64";
         }
       ];
-      name: "distributeToken";
+      name: "VestingGrant";
     },
     {
-      accounts: [
+      fields: [
         {
-          isMut: true;
-          isSigner: true;
+          index: false;
           name: "admin";
+          type: "publicKey";
         },
         {
-          isMut: true;
-          isSigner: false;
-          name: "adminData";
+          index: false;
+          name: "receipient";
+          type: "publicKey";
         },
         {
-          isMut: true;
-          isSigner: false;
-          name: "userData";
+          index: false;
+          name: "index";
+          type: "u64";
         },
         {
-          isMut: true;
-          isSigner: false;
-          name: "programData";
+          index: false;
+          name: "tokensBurned";
+          type: "u128";
         },
         {
-          isMut: false;
-          isSigner: false;
-          name: "systemProgram";
-        }
-      ];
-      args: [
+          index: false;
+          name: "lamportsRedistributed";
+          type: "u128";
+        },
         {
-          name: "user";
-          type: "publicKey";
+          index: false;
+          name: "tokenSupply";
+          type: "u128";
         },
         {
-          name: "status";
-          type: "bool";
+          index: false;
+          name: "profitPerShare";
+          type: "u128";
+        },
+        {
+          index: false;
+          name: "seq";
+          type: "u64";
+        },
+        {
+          index: false;
+          name: "nonce";
+          type: "u64";
         }
       ];
-      name: "setAdministrator";
+      name: "VestingRevocation";
     },
     {
-      accounts: [
+      fields: [
         {
-          isMut: true;
-          isSigner: true;
+          index: false;
           name: "admin";
+          type: "publicKey";
         },
         {
-          isMut: true;
-          isSigner: false;
-          name: "adminData";
+          index: false;
+          name: "oldName";
+          type: "string";
         },
         {
-          isMut: true;
-          isSigner: false;
-          name: "userData";
+          index: false;
+          name: "oldSymbol";
+          type: "string";
         },
         {
-          isMut: true;
-          isSigner: false;
-          name: "programData";
+          index: false;
+          name: "name";
+          type: "string";
         },
         {
-          isMut: false;
-          isSigner: false;
-          name: "systemProgram";
+          index: false;
+          name: "symbol";
+          type: "string";
+        },
+        {
+          index: false;
+          name: "uri";
+          type: "string";
+        },
+        {
+          index: false;
+          name: "seq";
+          type: "u64";
         }
       ];
-      args: [
+      name: "MetadataUpdate";
+    },
+    {
+      fields: [
         {
-          name: "user";
+          index: false;
+          name: "admin";
           type: "publicKey";
         },
         {
-          name: "status";
-          type: "bool";
+          index: false;
+          name: "oldThreshold";
+          type: "u8";
+        },
+        {
+          index: false;
+          name: "newThreshold";
+          type: "u8";
+        },
+        {
+          index: false;
+          name: "seq";
+          type: "u64";
         }
       ];
-      name: "setAmbassador";
+      name: "AdminThresholdUpdate";
     },
     {
-      accounts: [
+      fields: [
         {
-          isMut: true;
-          isSigner: true;
-          name: "user";
+          index: false;
+          name: "admin";
+          type: "publicKey";
         },
         {
-          isMut: true;
-          isSigner: false;
-          name: "userData";
+          index: false;
+          name: "oldFee";
+          type: "u8";
         },
         {
-          isMut: true;
-          isSigner: false;
-          name: "programData";
+          index: false;
+          name: "newFee";
+          type: "u8";
         },
         {
-          isMut: false;
-          isSigner: false;
-          name: "systemProgram";
+          index: false;
+          name: "seq";
+          type: "u64";
         }
       ];
-      args: [
+      name: "DividendFeeUpdate";
+    },
+    {
+      fields: [
         {
-          name: "amountOfTokens";
-          type: "u128";
+          index: false;
+          name: "admin";
+          type: "publicKey";
+        },
+        {
+          index: false;
+          name: "oldDelay";
+          type: "i64";
+        },
+        {
+          index: false;
+          name: "newDelay";
+          type: "i64";
+        },
+        {
+          index: false;
+          name: "seq";
+          type: "u64";
         }
       ];
-      name: "setStakingRequirement";
+      name: "TimelockDelayUpdate";
     },
     {
-      accounts: [
+      fields: [
         {
-          isMut: false;
-          isSigner: false;
-          name: "userData";
+          index: false;
+          name: "admin";
+          type: "publicKey";
         },
         {
-          isMut: false;
-          isSigner: false;
-          name: "programData";
-        }
-      ];
-      args: [
+          index: false;
+          name: "oldOffset";
+          type: "i64";
+        },
         {
-          name: "includingRef";
-          type: "bool";
+          index: false;
+          name: "newOffset";
+          type: "i64";
+        },
+        {
+          index: false;
+          name: "seq";
+          type: "u64";
         }
       ];
-      name: "myDividends";
-      returns: "u128";
+      name: "ClockOffsetUpdate";
     },
     {
-      accounts: [
+      fields: [
         {
-          isMut: false;
-          isSigner: false;
-          name: "programData";
+          index: false;
+          name: "id";
+          type: "u64";
+        },
+        {
+          index: false;
+          name: "proposer";
+          type: "publicKey";
+        },
+        {
+          index: false;
+          name: "action";
+          type: {
+            defined: "AdminAction";
+          };
+        },
+        {
+          index: false;
+          name: "eta";
+          type: "i64";
+        },
+        {
+          index: false;
+          name: "seq";
+          type: "u64";
         }
       ];
-      args: [];
-      name: "sellPrice";
-      returns: "u128";
+      name: "ProposalCreation";
     },
     {
-      accounts: [
+      fields: [
         {
-          isMut: false;
-          isSigner: false;
-          name: "programData";
+          index: false;
+          name: "id";
+          type: "u64";
+        },
+        {
+          index: false;
+          name: "admin";
+          type: "publicKey";
+        },
+        {
+          index: false;
+          name: "approvals";
+          type: "u8";
+        },
+        {
+          index: false;
+          name: "eta";
+          type: "i64";
+        },
+        {
+          index: false;
+          name: "seq";
+          type: "u64";
         }
       ];
-      args: [];
-      name: "buyPrice";
-      returns: "u128";
+      name: "ProposalApproval";
     },
     {
-      accounts: [
+      fields: [
         {
-          isMut: false;
-          isSigner: false;
-          name: "programData";
-        }
-      ];
-      args: [
+          index: false;
+          name: "id";
+          type: "u64";
+        },
         {
-          name: "tokens";
-          type: "u128";
+          index: false;
+          name: "executor";
+          type: "publicKey";
+        },
+        {
+          index: false;
+          name: "seq";
+          type: "u64";
         }
       ];
-      name: "calculateLamportsReceived";
-      returns: "u128";
+      name: "ProposalExecution";
     },
     {
-      accounts: [
+      fields: [
         {
-          isMut: false;
-          isSigner: false;
-          name: "programData";
-        }
-      ];
-      args: [
+          index: false;
+          name: "id";
+          type: "u64";
+        },
         {
-          name: "lamports";
-          type: "u128";
+          index: false;
+          name: "admin";
+          type: "publicKey";
+        },
+        {
+          index: false;
+          name: "seq";
+          type: "u64";
         }
       ];
-      name: "calculateTokensReceived";
-      returns: "u128";
+      name: "ProposalCancellation";
     }
   ];
-  name: "app";
-  types: [
+  instructions: [
     {
-      name: "InitTokenParams";
-      type: {
-        fields: [
-          {
-            name: "name";
-            type: "string";
-          },
-          {
-            name: "symbol";
-            type: "string";
-          },
-          {
-            name: "uri";
+      accounts: [
+        {
+          isMut: true;
+          isSigner: true;
+          name: "admin";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "adminData";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "metadata";
+        },
+        {
+          docs: ["CHECK gets created by the program"];
+          isMut: true;
+          isSigner: false;
+          name: "mint";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "programData";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "adminRegistry";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "rent";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "sysvarInstructions";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "systemProgram";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "tokenProgram";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "tokenMetadataProgram";
+        }
+      ];
+      args: [
+        {
+          name: "metadata";
+          type: {
+            defined: "InitTokenParams";
+          };
+        }
+      ];
+      name: "initialize";
+    },
+    {
+      accounts: [
+        {
+          isMut: true;
+          isSigner: false;
+          name: "mint";
+        },
+        {
+          isMut: true;
+          isSigner: true;
+          name: "user";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "userData";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "userAta";
+        },
+        {
+          isMut: true;
+          isOptional: true;
+          isSigner: false;
+          name: "referredByData";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "programData";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "systemProgram";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "tokenProgram";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "associatedTokenProgram";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "eventAuthority";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "program";
+        }
+      ];
+      args: [
+        {
+          name: "lamportsToSend";
+          type: "u64";
+        },
+        {
+          name: "referredBy";
+          type: {
+            option: "publicKey";
+          };
+        },
+        {
+          name: "minTokensOut";
+          type: "u64";
+        },
+        {
+          name: "deadline";
+          type: {
+            defined: "TradeDeadline";
+          };
+        }
+      ];
+      name: "buy";
+    },
+    {
+      accounts: [
+        {
+          isMut: true;
+          isSigner: false;
+          name: "mint";
+        },
+        {
+          isMut: true;
+          isSigner: true;
+          name: "user";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "userData";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "userAta";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "programData";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "systemProgram";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "tokenProgram";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "associatedTokenProgram";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "eventAuthority";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "program";
+        }
+      ];
+      args: [
+        {
+          name: "minTokensOut";
+          type: {
+            option: "u64";
+          };
+        },
+        {
+          name: "deadline";
+          type: {
+            defined: "TradeDeadline";
+          };
+        }
+      ];
+      name: "reinvest";
+    },
+    {
+      accounts: [
+        {
+          isMut: true;
+          isSigner: false;
+          name: "mint";
+        },
+        {
+          isMut: true;
+          isSigner: true;
+          name: "user";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "userData";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "userAta";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "programData";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "systemProgram";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "tokenProgram";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "associatedTokenProgram";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "eventAuthority";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "program";
+        }
+      ];
+      args: [
+        {
+          name: "minLamportsOut";
+          type: "u64";
+        },
+        {
+          name: "deadline";
+          type: {
+            defined: "TradeDeadline";
+          };
+        }
+      ];
+      name: "exit";
+    },
+    {
+      accounts: [
+        {
+          isMut: true;
+          isSigner: false;
+          name: "mint";
+        },
+        {
+          isMut: true;
+          isSigner: true;
+          name: "user";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "userData";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "userAta";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "toData";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "toInfo";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "toAta";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "programData";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "systemProgram";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "tokenProgram";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "associatedTokenProgram";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "eventAuthority";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "program";
+        }
+      ];
+      args: [
+        {
+          name: "to";
+          type: "publicKey";
+        },
+        {
+          name: "lamportsToSend";
+          type: "u64";
+        },
+        {
+          name: "deadline";
+          type: {
+            defined: "TradeDeadline";
+          };
+        }
+      ];
+      name: "transfer";
+    },
+    {
+      accounts: [
+        {
+          isMut: true;
+          isSigner: false;
+          name: "mint";
+        },
+        {
+          isMut: true;
+          isSigner: true;
+          name: "user";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "userData";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "userAta";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "programData";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "systemProgram";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "tokenProgram";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "associatedTokenProgram";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "eventAuthority";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "program";
+        }
+      ];
+      args: [];
+      name: "withdraw";
+    },
+    {
+      accounts: [
+        {
+          isMut: true;
+          isSigner: false;
+          name: "mint";
+        },
+        {
+          isMut: true;
+          isSigner: true;
+          name: "user";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "userData";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "userAta";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "programData";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "systemProgram";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "tokenProgram";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "associatedTokenProgram";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "eventAuthority";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "program";
+        }
+      ];
+      args: [
+        {
+          name: "lamportsToSend";
+          type: "u64";
+        },
+        {
+          name: "minLamportsOut";
+          type: "u64";
+        },
+        {
+          name: "deadline";
+          type: {
+            defined: "TradeDeadline";
+          };
+        }
+      ];
+      name: "sell";
+    },
+    {
+      accounts: [
+        {
+          isMut: true;
+          isSigner: true;
+          name: "user";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "userData";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "programData";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "systemProgram";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "eventAuthority";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "program";
+        }
+      ];
+      args: [];
+      name: "disableInitialStage";
+    },
+    {
+      accounts: [
+        {
+          isMut: true;
+          isSigner: false;
+          name: "mint";
+        },
+        {
+          isMut: true;
+          isSigner: true;
+          name: "user";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "fromData";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "fromAta";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "receipientInfo";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "receipientData";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "receipientAta";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "vesting";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "programData";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "systemProgram";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "tokenProgram";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "associatedTokenProgram";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "eventAuthority";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "program";
+        }
+      ];
+      args: [
+        {
+          name: "amountOfTokens";
+          type: "u128";
+        },
+        {
+          name: "updatePayoutBy";
+          type: "i128";
+        },
+        {
+          name: "receipient";
+          type: "publicKey";
+        },
+        {
+          name: "schedule";
+          type: {
+            defined: "VestingSchedule";
+          };
+        }
+      ];
+      name: "distributeToken";
+    },
+    {
+      accounts: [
+        {
+          isMut: true;
+          isSigner: false;
+          name: "mint";
+        },
+        {
+          isMut: false;
+          isSigner: true;
+          name: "admin";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "adminData";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "vesting";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "receipientInfo";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "receipientData";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "receipientAta";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "programData";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "tokenProgram";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "eventAuthority";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "program";
+        }
+      ];
+      args: [];
+      name: "revokeVesting";
+    },
+    {
+      accounts: [
+        {
+          isMut: true;
+          isSigner: true;
+          name: "admin";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "adminData";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "userData";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "programData";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "adminRegistry";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "systemProgram";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "eventAuthority";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "program";
+        }
+      ];
+      args: [
+        {
+          name: "user";
+          type: "publicKey";
+        },
+        {
+          name: "status";
+          type: "bool";
+        }
+      ];
+      name: "setAdministrator";
+    },
+    {
+      accounts: [
+        {
+          isMut: false;
+          isSigner: true;
+          name: "user";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "userData";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "programData";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "adminRegistry";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "eventAuthority";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "program";
+        }
+      ];
+      args: [];
+      name: "acceptAdministrator";
+    },
+    {
+      accounts: [
+        {
+          isMut: true;
+          isSigner: true;
+          name: "admin";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "adminData";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "userData";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "programData";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "systemProgram";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "eventAuthority";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "program";
+        }
+      ];
+      args: [
+        {
+          name: "user";
+          type: "publicKey";
+        },
+        {
+          name: "status";
+          type: "bool";
+        }
+      ];
+      name: "setAmbassador";
+    },
+    {
+      accounts: [
+        {
+          isMut: true;
+          isSigner: true;
+          name: "admin";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "adminData";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "programData";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "mint";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "metadata";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "systemProgram";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "sysvarInstructions";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "tokenMetadataProgram";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "eventAuthority";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "program";
+        }
+      ];
+      args: [
+        {
+          name: "name";
+          type: "string";
+        },
+        {
+          name: "symbol";
+          type: "string";
+        },
+        {
+          name: "uri";
+          type: "string";
+        }
+      ];
+      name: "updateMetadataAccount";
+    },
+    {
+      accounts: [
+        {
+          isMut: true;
+          isSigner: true;
+          name: "admin";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "adminData";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "userData";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "programData";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "systemProgram";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "eventAuthority";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "program";
+        }
+      ];
+      args: [
+        {
+          name: "user";
+          type: "publicKey";
+        },
+        {
+          name: "roles";
+          type: "u8";
+        }
+      ];
+      name: "setRoles";
+    },
+    {
+      accounts: [
+        {
+          isMut: true;
+          isSigner: true;
+          name: "user";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "userData";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "programData";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "systemProgram";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "eventAuthority";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "program";
+        }
+      ];
+      args: [
+        {
+          name: "amountOfTokens";
+          type: "u128";
+        }
+      ];
+      name: "setStakingRequirement";
+    },
+    {
+      accounts: [
+        {
+          isMut: true;
+          isSigner: true;
+          name: "admin";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "adminData";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "programData";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "proposal";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "adminRegistry";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "systemProgram";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "eventAuthority";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "program";
+        }
+      ];
+      args: [
+        {
+          name: "action";
+          type: {
+            defined: "AdminAction";
+          };
+        }
+      ];
+      name: "propose";
+    },
+    {
+      accounts: [
+        {
+          isMut: false;
+          isSigner: true;
+          name: "admin";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "adminData";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "programData";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "proposal";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "eventAuthority";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "program";
+        }
+      ];
+      args: [];
+      name: "approveProposal";
+    },
+    {
+      accounts: [
+        {
+          isMut: true;
+          isSigner: true;
+          name: "executor";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "programData";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "proposal";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "adminRegistry";
+        },
+        {
+          docs: [
+            "The user an administrator, ambassador or roles proposal applies to, created if they have",
+            "no account yet"
+          ];
+          isMut: true;
+          isOptional: true;
+          isSigner: false;
+          name: "targetData";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "systemProgram";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "eventAuthority";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "program";
+        }
+      ];
+      args: [];
+      name: "executeProposal";
+    },
+    {
+      accounts: [
+        {
+          isMut: true;
+          isSigner: true;
+          name: "executor";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "proposal";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "adminRegistry";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "programData";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "mint";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "metadata";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "systemProgram";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "sysvarInstructions";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "tokenMetadataProgram";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "eventAuthority";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "program";
+        }
+      ];
+      args: [];
+      name: "executeMetadataProposal";
+    },
+    {
+      accounts: [
+        {
+          isMut: false;
+          isSigner: true;
+          name: "executor";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "proposal";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "adminRegistry";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "mint";
+        },
+        {
+          docs: ["The grant the proposal revokes"];
+          isMut: true;
+          isSigner: false;
+          name: "vesting";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "receipientInfo";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "receipientData";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "receipientAta";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "programData";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "tokenProgram";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "eventAuthority";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "program";
+        }
+      ];
+      args: [];
+      name: "executeRevocationProposal";
+    },
+    {
+      accounts: [
+        {
+          isMut: false;
+          isSigner: true;
+          name: "admin";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "adminData";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "programData";
+        },
+        {
+          isMut: true;
+          isSigner: false;
+          name: "proposal";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "eventAuthority";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "program";
+        }
+      ];
+      args: [];
+      name: "cancelProposal";
+    },
+    {
+      accounts: [
+        {
+          isMut: false;
+          isSigner: false;
+          name: "userData";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "programData";
+        }
+      ];
+      args: [
+        {
+          name: "includingRef";
+          type: "bool";
+        }
+      ];
+      name: "myDividends";
+      returns: "u128";
+    },
+    {
+      accounts: [
+        {
+          isMut: false;
+          isSigner: false;
+          name: "userData";
+        },
+        {
+          isMut: false;
+          isSigner: false;
+          name: "programData";
+        }
+      ];
+      args: [
+        {
+          name: "at";
+          type: {
+            option: "i64";
+          };
+        }
+      ];
+      name: "vestedBalance";
+      returns: "u128";
+    },
+    {
+      accounts: [
+        {
+          isMut: false;
+          isSigner: false;
+          name: "programData";
+        }
+      ];
+      args: [];
+      name: "sellPrice";
+      returns: "u128";
+    },
+    {
+      accounts: [
+        {
+          isMut: false;
+          isSigner: false;
+          name: "programData";
+        }
+      ];
+      args: [];
+      name: "buyPrice";
+      returns: "u128";
+    },
+    {
+      accounts: [
+        {
+          isMut: false;
+          isSigner: false;
+          name: "programData";
+        }
+      ];
+      args: [
+        {
+          name: "tokens";
+          type: "u128";
+        }
+      ];
+      name: "calculateLamportsReceived";
+      returns: "u128";
+    },
+    {
+      accounts: [
+        {
+          isMut: false;
+          isSigner: false;
+          name: "programData";
+        }
+      ];
+      args: [
+        {
+          name: "lamports";
+          type: "u128";
+        }
+      ];
+      name: "calculateTokensReceived";
+      returns: "u128";
+    }
+  ];
+  name: "app";
+  types: [
+    {
+      name: "SetClockOffset";
+      type: {
+        fields: [
+          {
+            name: "offset";
+            type: "i64";
+          }
+        ];
+        kind: "struct";
+      };
+    },
+    {
+      name: "InitTokenParams";
+      type: {
+        fields: [
+          {
+            name: "name";
+            type: "string";
+          },
+          {
+            name: "symbol";
+            type: "string";
+          },
+          {
+            name: "uri";
             type: "string";
           },
           {
-            name: "decimals";
-            type: "u8";
-          }
-        ];
-        kind: "struct";
-      };
-    }
-  ];
-  version: "0.1.0";
-};
-
-export const IDL: App = {
-  accounts: [
+            name: "decimals";
+            type: "u8";
+          },
+          {
+            name: "dividendFee";
+            type: "u8";
+          },
+          {
+            name: "tokenInitialPrice";
+            type: "u128";
+          },
+          {
+            name: "tokenIncrementalPrice";
+            type: "u128";
+          },
+          {
+            name: "magnitude";
+            type: "u64";
+          },
+          {
+            name: "stakingRequirement";
+            type: "u128";
+          },
+          {
+            name: "curve";
+            type: {
+              defined: "CurveKind";
+            };
+          },
+          {
+            name: "ambassadorMaxPurchase";
+            type: "u128";
+          },
+          {
+            name: "ambassadorQuota";
+            type: "u128";
+          }
+        ];
+        kind: "struct";
+      };
+    },
+    {
+      name: "TradeDeadline";
+      type: {
+        fields: [
+          {
+            name: "validUntilSlot";
+            type: {
+              option: "u64";
+            };
+          },
+          {
+            name: "validUntilTs";
+            type: {
+              option: "i64";
+            };
+          }
+        ];
+        kind: "struct";
+      };
+    },
+    {
+      docs: [
+        "* Unlock schedule of a `distribute_token` grant. Nothing unlocks until `cliff` seconds after\n * the start and everything has after `duration` seconds. In between the unlocked part grows\n * linearly, in steps of `release_interval` seconds or every second when it is zero."
+      ];
+      name: "VestingSchedule";
+      type: {
+        fields: [
+          {
+            name: "startTime";
+            type: {
+              option: "i64";
+            };
+          },
+          {
+            name: "cliff";
+            type: "i64";
+          },
+          {
+            name: "duration";
+            type: "i64";
+          },
+          {
+            name: "releaseInterval";
+            type: "i64";
+          }
+        ];
+        kind: "struct";
+      };
+    },
+    {
+      name: "PendingAdmin";
+      type: {
+        fields: [
+          {
+            name: "user";
+            type: "publicKey";
+          },
+          {
+            name: "nominatedBy";
+            type: "publicKey";
+          }
+        ];
+        kind: "struct";
+      };
+    },
+    {
+      docs: [
+        "* Pricing model picked at initialize time, the curves themselves live in the `app-math` crate."
+      ];
+      name: "CurveKind";
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            name: "Linear";
+          },
+          {
+            name: "Exponential";
+          },
+          {
+            name: "ConstantProduct";
+          }
+        ];
+      };
+    },
+    {
+      name: "AdminAction";
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            name: "DisableInitialStage";
+          },
+          {
+            fields: [
+              {
+                name: "user";
+                type: "publicKey";
+              },
+              {
+                name: "status";
+                type: "bool";
+              }
+            ];
+            name: "SetAdministrator";
+          },
+          {
+            fields: [
+              {
+                name: "user";
+                type: "publicKey";
+              },
+              {
+                name: "status";
+                type: "bool";
+              }
+            ];
+            name: "SetAmbassador";
+          },
+          {
+            fields: [
+              {
+                name: "user";
+                type: "publicKey";
+              },
+              {
+                name: "roles";
+                type: "u8";
+              }
+            ];
+            name: "SetRoles";
+          },
+          {
+            fields: [
+              {
+                name: "amountOfTokens";
+                type: "u128";
+              }
+            ];
+            name: "SetStakingRequirement";
+          },
+          {
+            fields: [
+              {
+                name: "threshold";
+                type: "u8";
+              }
+            ];
+            name: "SetAdminThreshold";
+          },
+          {
+            fields: [
+              {
+                name: "dividendFee";
+                type: "u8";
+              }
+            ];
+            name: "SetDividendFee";
+          },
+          {
+            fields: [
+              {
+                name: "delay";
+                type: "i64";
+              }
+            ];
+            name: "SetTimelockDelay";
+          },
+          {
+            fields: [
+              {
+                name: "name";
+                type: "string";
+              },
+              {
+                name: "symbol";
+                type: "string";
+              },
+              {
+                name: "uri";
+                type: "string";
+              }
+            ];
+            name: "UpdateMetadata";
+          },
+          {
+            fields: [
+              {
+                name: "receipient";
+                type: "publicKey";
+              },
+              {
+                name: "index";
+                type: "u64";
+              }
+            ];
+            name: "RevokeVesting";
+          }
+        ];
+      };
+    },
+    {
+      name: "ProposalStatus";
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            name: "Active";
+          },
+          {
+            name: "Executed";
+          },
+          {
+            name: "Cancelled";
+          }
+        ];
+      };
+    },
+    {
+      name: "AdminActionEvent";
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            fields: [
+              {
+                defined: "InitialStageEnd";
+              }
+            ];
+            name: "InitialStageEnd";
+          },
+          {
+            fields: [
+              {
+                defined: "AdminNomination";
+              }
+            ];
+            name: "AdminNomination";
+          },
+          {
+            fields: [
+              {
+                defined: "AdministratorUpdate";
+              }
+            ];
+            name: "AdministratorUpdate";
+          },
+          {
+            fields: [
+              {
+                defined: "AmbassadorUpdate";
+              }
+            ];
+            name: "AmbassadorUpdate";
+          },
+          {
+            fields: [
+              {
+                defined: "RolesUpdate";
+              }
+            ];
+            name: "RolesUpdate";
+          },
+          {
+            fields: [
+              {
+                defined: "StakingRequirementUpdate";
+              }
+            ];
+            name: "StakingRequirementUpdate";
+          },
+          {
+            fields: [
+              {
+                defined: "AdminThresholdUpdate";
+              }
+            ];
+            name: "AdminThresholdUpdate";
+          },
+          {
+            fields: [
+              {
+                defined: "DividendFeeUpdate";
+              }
+            ];
+            name: "DividendFeeUpdate";
+          },
+          {
+            fields: [
+              {
+                defined: "TimelockDelayUpdate";
+              }
+            ];
+            name: "TimelockDelayUpdate";
+          }
+        ];
+      };
+    }
+  ];
+  version: "0.1.0";
+};
+
+export const IDL: App = {
+  accounts: [
+    {
+      name: "user",
+      type: {
+        fields: [
+          {
+            name: "authority",
+            type: "publicKey",
+          },
+          {
+            name: "balance",
+            type: "u128",
+          },
+          {
+            name: "referredBalance",
+            type: "u128",
+          },
+          {
+            name: "isAdmin",
+            type: "bool",
+          },
+          {
+            name: "isAmb",
+            type: "bool",
+          },
+          {
+            name: "referredBy",
+            type: "publicKey",
+          },
+          {
+            name: "payout",
+            type: "i128",
+          },
+          {
+            name: "vestingCount",
+            type: "u64",
+          },
+          {
+            name: "vestingEndTime",
+            type: "i64",
+          },
+          {
+            name: "nonce",
+            type: "u64",
+          },
+          {
+            name: "roles",
+            type: "u8",
+          },
+          {
+            name: "ambassadorPurchased",
+            type: "u128",
+          },
+        ],
+        kind: "struct",
+      },
+    },
+    {
+      docs: [
+        "* Tokens locked by one `distribute_token` grant, a PDA of `[vesting, receipient, index]`.",
+      ],
+      name: "vesting",
+      type: {
+        fields: [
+          {
+            name: "receipient",
+            type: "publicKey",
+          },
+          {
+            name: "index",
+            type: "u64",
+          },
+          {
+            name: "totalLocked",
+            type: "u128",
+          },
+          {
+            name: "startTime",
+            type: "i64",
+          },
+          {
+            name: "endTime",
+            type: "i64",
+          },
+          {
+            name: "cliffTime",
+            type: "i64",
+          },
+          {
+            name: "releaseInterval",
+            type: "i64",
+          },
+        ],
+        kind: "struct",
+      },
+    },
+    {
+      name: "app",
+      type: {
+        fields: [
+          {
+            name: "name",
+            type: "string",
+          },
+          {
+            name: "symbol",
+            type: "string",
+          },
+          {
+            name: "decimals",
+            type: "u8",
+          },
+          {
+            name: "dividendFee",
+            type: "u8",
+          },
+          {
+            name: "tokenInitialPrice",
+            type: "u128",
+          },
+          {
+            name: "tokenIncrementalPrice",
+            type: "u128",
+          },
+          {
+            name: "curve",
+            type: {
+              defined: "CurveKind",
+            },
+          },
+          {
+            name: "contractBalance",
+            type: "u128",
+          },
+          {
+            name: "tokenSupply",
+            type: "u128",
+          },
+          {
+            name: "magnitude",
+            type: "u64",
+          },
+          {
+            name: "stakingRequirement",
+            type: "u128",
+          },
+          {
+            name: "profitPerShare",
+            type: "u128",
+          },
+          {
+            name: "isInitialized",
+            type: "bool",
+          },
+          {
+            name: "isInitialPhase",
+            type: "bool",
+          },
+          {
+            name: "eventSeq",
+            type: "u64",
+          },
+          {
+            name: "adminThreshold",
+            type: "u8",
+          },
+          {
+            name: "proposalCount",
+            type: "u64",
+          },
+          {
+            name: "timelockDelay",
+            type: "i64",
+          },
+          {
+            name: "ambassadorMaxPurchase",
+            type: "u128",
+          },
+          {
+            name: "ambassadorQuota",
+            type: "u128",
+          },
+          {
+            name: "ambassadorPurchased",
+            type: "u128",
+          },
+          {
+            name: "clockOffset",
+            type: "i64",
+          },
+        ],
+        kind: "struct",
+      },
+    },
+    {
+      name: "proposal",
+      type: {
+        fields: [
+          {
+            name: "id",
+            type: "u64",
+          },
+          {
+            name: "proposer",
+            type: "publicKey",
+          },
+          {
+            name: "action",
+            type: {
+              defined: "AdminAction",
+            },
+          },
+          {
+            name: "approvals",
+            type: {
+              vec: "publicKey",
+            },
+          },
+          {
+            name: "status",
+            type: {
+              defined: "ProposalStatus",
+            },
+          },
+          {
+            name: "createdAt",
+            type: "i64",
+          },
+          {
+            name: "eta",
+            type: "i64",
+          },
+        ],
+        kind: "struct",
+      },
+    },
+    {
+      name: "adminRegistry",
+      type: {
+        fields: [
+          {
+            name: "admins",
+            type: {
+              vec: "publicKey",
+            },
+          },
+          {
+            name: "pending",
+            type: {
+              vec: {
+                defined: "PendingAdmin",
+              },
+            },
+          },
+        ],
+        kind: "struct",
+      },
+    },
+  ],
+  errors: [
+    {
+      code: 6000,
+      msg: "You are not a holder",
+      name: "NotABagHolder",
+    },
+    {
+      code: 6001,
+      msg: "You do not have a profit",
+      name: "NoPofit",
+    },
+    {
+      code: 6002,
+      msg: "You are not an administrator",
+      name: "NotAnAdmin",
+    },
+    {
+      code: 6003,
+      msg: "It is in initial phase",
+      name: "IsInitialPhase",
+    },
+    {
+      code: 6004,
+      msg: "It is not in initial phase",
+      name: "IsPostInitialPhase",
+    },
+    {
+      code: 6005,
+      msg: "You sent less token than required",
+      name: "SentLessToken",
+    },
+    {
+      code: 6006,
+      msg: "You do not have enough funds",
+      name: "InsufficientBalance",
+    },
+    {
+      code: 6007,
+      msg: "Already Initialized Account",
+      name: "AlreadyInitialized",
+    },
+    {
+      code: 6008,
+      msg: "To address doesn't match generated to account",
+      name: "InvalidToAccount",
+    },
+    {
+      code: 6009,
+      msg: "Signer isn't Owner",
+      name: "NotOwner",
+    },
+    {
+      code: 6010,
+      msg: "You can't refer yourself",
+      name: "SelfReferral",
+    },
+    {
+      code: 6011,
+      msg: "You can't transfer to yourself",
+      name: "SelfTransfer",
+    },
+    {
+      code: 6012,
+      msg: "Dividend fee divisor must be greater than zero",
+      name: "InvalidDividendFee",
+    },
+    {
+      code: 6013,
+      msg: "Token prices are out of bounds",
+      name: "InvalidTokenPrice",
+    },
+    {
+      code: 6014,
+      msg: "Magnitude must be greater than zero",
+      name: "InvalidMagnitude",
+    },
+    {
+      code: 6015,
+      msg: "You would receive fewer tokens than the minimum requested",
+      name: "TokensBelowMinimum",
+    },
+    {
+      code: 6016,
+      msg: "You would receive fewer lamports than the minimum requested",
+      name: "LamportsBelowMinimum",
+    },
+    {
+      code: 6017,
+      msg: "Transaction is past its deadline",
+      name: "DeadlineExceeded",
+    },
+    {
+      code: 6018,
+      msg: "Arithmetic overflow",
+      name: "MathOverflow",
+    },
+    {
+      code: 6019,
+      msg: "Arithmetic underflow",
+      name: "MathUnderflow",
+    },
+    {
+      code: 6020,
+      msg: "Division by zero",
+      name: "DivisionByZero",
+    },
+    {
+      code: 6021,
+      msg: "Amount doesn't fit in a u64",
+      name: "AmountTooLarge",
+    },
+    {
+      code: 6022,
+      msg: "Admin changes need an approved proposal",
+      name: "ProposalRequired",
+    },
+    {
+      code: 6023,
+      msg: "Threshold must be between one and the approval limit",
+      name: "InvalidThreshold",
+    },
+    {
+      code: 6024,
+      msg: "Proposal was already executed or cancelled",
+      name: "ProposalNotActive",
+    },
+    {
+      code: 6025,
+      msg: "You already approved this proposal",
+      name: "AlreadyApproved",
+    },
+    {
+      code: 6026,
+      msg: "Proposal can't take more approvals",
+      name: "ApprovalLimitReached",
+    },
+    {
+      code: 6027,
+      msg: "Proposal doesn't have enough approvals",
+      name: "NotEnoughApprovals",
+    },
+    {
+      code: 6028,
+      msg: "Target account doesn't match the proposal",
+      name: "InvalidTargetAccount",
+    },
+    {
+      code: 6029,
+      msg: "This change is timelocked, it needs a proposal",
+      name: "TimelockRequired",
+    },
+    {
+      code: 6030,
+      msg: "Timelock delay must be between zero and thirty days",
+      name: "InvalidTimelockDelay",
+    },
+    {
+      code: 6031,
+      msg: "Proposal isn't queued yet",
+      name: "ProposalNotQueued",
+    },
+    {
+      code: 6032,
+      msg: "Proposal is still in its timelock",
+      name: "TimelockNotElapsed",
+    },
+    {
+      code: 6033,
+      msg: "The last admin can't be removed",
+      name: "LastAdmin",
+    },
+    {
+      code: 6034,
+      msg: "Removing this admin would leave fewer admins than the threshold",
+      name: "AdminCountBelowThreshold",
+    },
+    {
+      code: 6035,
+      msg: "User is already an admin",
+      name: "AlreadyAdmin",
+    },
+    {
+      code: 6036,
+      msg: "User was already granted admin",
+      name: "AlreadyNominated",
+    },
+    {
+      code: 6037,
+      msg: "You weren't granted admin",
+      name: "NotNominated",
+    },
+    {
+      code: 6038,
+      msg: "Admin registry is full",
+      name: "AdminRegistryFull",
+    },
+    {
+      code: 6039,
+      msg: "You don't have the role this needs",
+      name: "MissingRole",
+    },
+    {
+      code: 6040,
+      msg: "Unknown role",
+      name: "InvalidRoles",
+    },
+    {
+      code: 6041,
+      msg: "Vesting needs a positive duration, with the cliff and release interval within it",
+      name: "InvalidVestingSchedule",
+    },
+    {
+      code: 6042,
+      msg: "The user's vesting grants must be passed in grant order",
+      name: "MissingVestingGrants",
+    },
+    {
+      code: 6043,
+      msg: "User can't receive more vesting grants",
+      name: "VestingGrantLimit",
+    },
+    {
+      code: 6044,
+      msg: "Grant has nothing left to revoke",
+      name: "NothingToRevoke",
+    },
+    {
+      code: 6045,
+      msg: "Only feature-dev builds can move the clock",
+      name: "DevBuildOnly",
+    },
+    {
+      code: 6046,
+      msg: "Purchase exceeds what an ambassador can buy during the initial phase",
+      name: "AmbassadorCapExceeded",
+    },
+    {
+      code: 6047,
+      msg: "Token prices leave the curve too little supply",
+      name: "CurveSupplyTooLow",
+    },
+    {
+      code: 6048,
+      msg: "The ambassador cap must be positive and the quota at least the cap",
+      name: "InvalidAmbassadorQuota",
+    },
+    {
+      code: 6049,
+      msg: "The token must use 9 decimals, the unit its prices are quoted in",
+      name: "InvalidDecimals",
+    },
+    {
+      code: 6050,
+      msg: "Purchase would take the supply past what the curve can price",
+      name: "SupplyLimitExceeded",
+    },
+    {
+      code: 6051,
+      msg: "Name, symbol or uri is longer than the metadata allows",
+      name: "MetadataTooLong",
+    },
+    {
+      code: 6052,
+      msg: "This proposal is executed by the instruction made for its action",
+      name: "WrongExecuteInstruction",
+    },
+    {
+      code: 6053,
+      msg: "Purchase would take ambassadors past the initial phase quota",
+      name: "AmbassadorQuotaExceeded",
+    },
+  ],
+  events: [
+    {
+      fields: [
+        {
+          index: false,
+          name: "customer",
+          type: "publicKey",
+        },
+        {
+          index: false,
+          name: "incomingLamports",
+          type: "u128",
+        },
+        {
+          index: false,
+          name: "tokensMinted",
+          type: "u128",
+        },
+        {
+          index: false,
+          name: "referredBy",
+          type: "publicKey",
+        },
+        {
+          index: false,
+          name: "tokenSupply",
+          type: "u128",
+        },
+        {
+          index: false,
+          name: "profitPerShare",
+          type: "u128",
+        },
+        {
+          index: false,
+          name: "price",
+          type: "u128",
+        },
+        {
+          index: false,
+          name: "seq",
+          type: "u64",
+        },
+        {
+          index: false,
+          name: "nonce",
+          type: "u64",
+        },
+      ],
+      name: "TokenPurchase",
+    },
+    {
+      fields: [
+        {
+          index: false,
+          name: "customer",
+          type: "publicKey",
+        },
+        {
+          index: false,
+          name: "tokensBurned",
+          type: "u128",
+        },
+        {
+          index: false,
+          name: "lamportsEarned",
+          type: "u128",
+        },
+        {
+          index: false,
+          name: "tokenSupply",
+          type: "u128",
+        },
+        {
+          index: false,
+          name: "profitPerShare",
+          type: "u128",
+        },
+        {
+          index: false,
+          name: "price",
+          type: "u128",
+        },
+        {
+          index: false,
+          name: "seq",
+          type: "u64",
+        },
+        {
+          index: false,
+          name: "nonce",
+          type: "u64",
+        },
+      ],
+      name: "TokenSell",
+    },
+    {
+      fields: [
+        {
+          index: false,
+          name: "customer",
+          type: "publicKey",
+        },
+        {
+          index: false,
+          name: "lamportsReinvested",
+          type: "u128",
+        },
+        {
+          index: false,
+          name: "tokensMinted",
+          type: "u128",
+        },
+        {
+          index: false,
+          name: "tokenSupply",
+          type: "u128",
+        },
+        {
+          index: false,
+          name: "profitPerShare",
+          type: "u128",
+        },
+        {
+          index: false,
+          name: "price",
+          type: "u128",
+        },
+        {
+          index: false,
+          name: "seq",
+          type: "u64",
+        },
+        {
+          index: false,
+          name: "nonce",
+          type: "u64",
+        },
+      ],
+      name: "Reinvestment",
+    },
+    {
+      fields: [
+        {
+          index: false,
+          name: "customer",
+          type: "publicKey",
+        },
+        {
+          index: false,
+          name: "lamportsWithdrawn",
+          type: "u128",
+        },
+        {
+          index: false,
+          name: "seq",
+          type: "u64",
+        },
+        {
+          index: false,
+          name: "nonce",
+          type: "u64",
+        },
+      ],
+      name: "Withdrawal",
+    },
+    {
+      fields: [
+        {
+          index: false,
+          name: "referrer",
+          type: "publicKey",
+        },
+        {
+          index: false,
+          name: "customer",
+          type: "publicKey",
+        },
+        {
+          index: false,
+          name: "lamportsUsed",
+          type: "u128",
+        },
+        {
+          index: false,
+          name: "bonusReceived",
+          type: "u128",
+        },
+        {
+          index: false,
+          name: "seq",
+          type: "u64",
+        },
+        {
+          index: false,
+          name: "referrerNonce",
+          type: "u64",
+        },
+      ],
+      name: "Skwizkey",
+    },
+    {
+      fields: [
+        {
+          index: false,
+          name: "from",
+          type: "publicKey",
+        },
+        {
+          index: false,
+          name: "to",
+          type: "publicKey",
+        },
+        {
+          index: false,
+          name: "tokens",
+          type: "u128",
+        },
+        {
+          index: false,
+          name: "tokenSupply",
+          type: "u128",
+        },
+        {
+          index: false,
+          name: "profitPerShare",
+          type: "u128",
+        },
+        {
+          index: false,
+          name: "price",
+          type: "u128",
+        },
+        {
+          index: false,
+          name: "seq",
+          type: "u64",
+        },
+        {
+          index: false,
+          name: "fromNonce",
+          type: "u64",
+        },
+        {
+          index: false,
+          name: "toNonce",
+          type: "u64",
+        },
+      ],
+      name: "TokenTransfer",
+    },
+    {
+      fields: [
+        {
+          index: false,
+          name: "admin",
+          type: "publicKey",
+        },
+        {
+          index: false,
+          name: "wasInitialPhase",
+          type: "bool",
+        },
+        {
+          index: false,
+          name: "seq",
+          type: "u64",
+        },
+      ],
+      name: "InitialStageEnd",
+    },
+    {
+      fields: [
+        {
+          index: false,
+          name: "admin",
+          type: "publicKey",
+        },
+        {
+          index: false,
+          name: "user",
+          type: "publicKey",
+        },
+        {
+          index: false,
+          name: "oldStatus",
+          type: "bool",
+        },
+        {
+          index: false,
+          name: "newStatus",
+          type: "bool",
+        },
+        {
+          index: false,
+          name: "adminCount",
+          type: "u8",
+        },
+        {
+          index: false,
+          name: "seq",
+          type: "u64",
+        },
+        {
+          index: false,
+          name: "nonce",
+          type: "u64",
+        },
+      ],
+      name: "AdministratorUpdate",
+    },
+    {
+      fields: [
+        {
+          index: false,
+          name: "admin",
+          type: "publicKey",
+        },
+        {
+          index: false,
+          name: "user",
+          type: "publicKey",
+        },
+        {
+          index: false,
+          name: "seq",
+          type: "u64",
+        },
+      ],
+      name: "AdminNomination",
+    },
+    {
+      fields: [
+        {
+          index: false,
+          name: "admin",
+          type: "publicKey",
+        },
+        {
+          index: false,
+          name: "user",
+          type: "publicKey",
+        },
+        {
+          index: false,
+          name: "oldRoles",
+          type: "u8",
+        },
+        {
+          index: false,
+          name: "newRoles",
+          type: "u8",
+        },
+        {
+          index: false,
+          name: "seq",
+          type: "u64",
+        },
+        {
+          index: false,
+          name: "nonce",
+          type: "u64",
+        },
+      ],
+      name: "RolesUpdate",
+    },
+    {
+      fields: [
+        {
+          index: false,
+          name: "admin",
+          type: "publicKey",
+        },
+        {
+          index: false,
+          name: "user",
+          type: "publicKey",
+        },
+        {
+          index: false,
+          name: "oldStatus",
+          type: "bool",
+        },
+        {
+          index: false,
+          name: "newStatus",
+          type: "bool",
+        },
+        {
+          index: false,
+          name: "seq",
+          type: "u64",
+        },
+        {
+          index: false,
+          name: "nonce",
+          type: "u64",
+        },
+      ],
+      name: "AmbassadorUpdate",
+    },
     {
-      name: "user",
-      type: {
-        fields: [
-          {
-            name: "authority",
-            type: "publicKey",
-          },
-          {
-            name: "balance",
-            type: "u128",
-          },
-          {
-            name: "referredBalance",
-            type: "u128",
-          },
-          {
-            name: "isAdmin",
-            type: "bool",
-          },
-          {
-            name: "isAmb",
-            type: "bool",
-          },
-          {
-            name: "referredBy",
-            type: "publicKey",
-          },
-          {
-            name: "payout",
-            type: "i128",
-          },
-          {
-            name: "usableLocked",
-            type: "u128",
-          },
-          {
-            name: "totalLocked",
-            type: "u128",
-          },
-          {
-            name: "lockedStarttime",
-            type: "i64",
-          },
-          {
-            name: "lockedEndtime",
-            type: "i64",
-          },
-        ],
-        kind: "struct",
-      },
+      fields: [
+        {
+          index: false,
+          name: "admin",
+          type: "publicKey",
+        },
+        {
+          index: false,
+          name: "oldAmount",
+          type: "u128",
+        },
+        {
+          index: false,
+          name: "newAmount",
+          type: "u128",
+        },
+        {
+          index: false,
+          name: "seq",
+          type: "u64",
+        },
+      ],
+      name: "StakingRequirementUpdate",
     },
     {
-      name: "app",
-      type: {
-        fields: [
-          {
-            name: "name",
-            type: "string",
-          },
-          {
-            name: "symbol",
-            type: "string",
-          },
-          {
-            name: "decimals",
-            type: "u8",
-          },
-          {
-            name: "dividendFee",
-            type: "u8",
-          },
-          {
-            name: "tokenInitialPrice",
-            type: "u128",
-          },
-          {
-            name: "tokenIncrementalPrice",
-            type: "u128",
-          },
-          {
-            name: "contractBalance",
-            type: "u128",
-          },
-          {
-            name: "tokenSupply",
-            type: "u128",
-          },
-          {
-            name: "magnitude",
-            type: "u64",
-          },
-          {
-            name: "stakingRequirement",
-            type: "u128",
-          },
-          {
-            name: "profitPerShare",
-            type: "u128",
-          },
-          {
-            name: "isInitialized",
-            type: "bool",
-          },
-          {
-            name: "isInitialPhase",
-            type: "bool",
+      fields: [
+        {
+          index: false,
+          name: "admin",
+          type: "publicKey",
+        },
+        {
+          index: false,
+          name: "receipient",
+          type: "publicKey",
+        },
+        {
+          index: false,
+          name: "amount",
+          type: "u128",
+        },
+        {
+          index: false,
+          name: "startTime",
+          type: "i64",
+        },
+        {
+          index: false,
+          name: "endTime",
+          type: "i64",
+        },
+        {
+          index: false,
+          name: "cliffTime",
+          type: "i64",
+        },
+        {
+          index: false,
+          name: "releaseInterval",
+          type: "i64",
+        },
+        {
+          index: false,
+          name: "payoutAdjustment",
+          type: "i128",
+        },
+        {
+          index: false,
+          name: "index",
+          type: "u64",
+        },
+        {
+          index: false,
+          name: "seq",
+          type: "u64",
+        },
+        {
+          index: false,
+          name: "adminNonce",
+          type: "u64",
+        },
+        {
+          index: false,
+          name: "receipientNonce",
+          type: "u64",
+        },
+      ],
+      name: "VestingGrant",
+    },
+    {
+      fields: [
+        {
+          index: false,
+          name: "admin",
+          type: "publicKey",
+        },
+        {
+          index: false,
+          name: "receipient",
+          type: "publicKey",
+        },
+        {
+          index: false,
+          name: "index",
+          type: "u64",
+        },
+        {
+          index: false,
+          name: "tokensBurned",
+          type: "u128",
+        },
+        {
+          index: false,
+          name: "lamportsRedistributed",
+          type: "u128",
+        },
+        {
+          index: false,
+          name: "tokenSupply",
+          type: "u128",
+        },
+        {
+          index: false,
+          name: "profitPerShare",
+          type: "u128",
+        },
+        {
+          index: false,
+          name: "seq",
+          type: "u64",
+        },
+        {
+          index: false,
+          name: "nonce",
+          type: "u64",
+        },
+      ],
+      name: "VestingRevocation",
+    },
+    {
+      fields: [
+        {
+          index: false,
+          name: "admin",
+          type: "publicKey",
+        },
+        {
+          index: false,
+          name: "oldName",
+          type: "string",
+        },
+        {
+          index: false,
+          name: "oldSymbol",
+          type: "string",
+        },
+        {
+          index: false,
+          name: "name",
+          type: "string",
+        },
+        {
+          index: false,
+          name: "symbol",
+          type: "string",
+        },
+        {
+          index: false,
+          name: "uri",
+          type: "string",
+        },
+        {
+          index: false,
+          name: "seq",
+          type: "u64",
+        },
+      ],
+      name: "MetadataUpdate",
+    },
+    {
+      fields: [
+        {
+          index: false,
+          name: "admin",
+          type: "publicKey",
+        },
+        {
+          index: false,
+          name: "oldThreshold",
+          type: "u8",
+        },
+        {
+          index: false,
+          name: "newThreshold",
+          type: "u8",
+        },
+        {
+          index: false,
+          name: "seq",
+          type: "u64",
+        },
+      ],
+      name: "AdminThresholdUpdate",
+    },
+    {
+      fields: [
+        {
+          index: false,
+          name: "admin",
+          type: "publicKey",
+        },
+        {
+          index: false,
+          name: "oldFee",
+          type: "u8",
+        },
+        {
+          index: false,
+          name: "newFee",
+          type: "u8",
+        },
+        {
+          index: false,
+          name: "seq",
+          type: "u64",
+        },
+      ],
+      name: "DividendFeeUpdate",
+    },
+    {
+      fields: [
+        {
+          index: false,
+          name: "admin",
+          type: "publicKey",
+        },
+        {
+          index: false,
+          name: "oldDelay",
+          type: "i64",
+        },
+        {
+          index: false,
+          name: "newDelay",
+          type: "i64",
+        },
+        {
+          index: false,
+          name: "seq",
+          type: "u64",
+        },
+      ],
+      name: "TimelockDelayUpdate",
+    },
+    {
+      fields: [
+        {
+          index: false,
+          name: "admin",
+          type: "publicKey",
+        },
+        {
+          index: false,
+          name: "oldOffset",
+          type: "i64",
+        },
+        {
+          index: false,
+          name: "newOffset",
+          type: "i64",
+        },
+        {
+          index: false,
+          name: "seq",
+          type: "u64",
+        },
+      ],
+      name: "ClockOffsetUpdate",
+    },
+    {
+      fields: [
+        {
+          index: false,
+          name: "id",
+          type: "u64",
+        },
+        {
+          index: false,
+          name: "proposer",
+          type: "publicKey",
+        },
+        {
+          index: false,
+          name: "action",
+          type: {
+            defined: "AdminAction",
           },
-        ],
-        kind: "struct",
-      },
+        },
+        {
+          index: false,
+          name: "eta",
+          type: "i64",
+        },
+        {
+          index: false,
+          name: "seq",
+          type: "u64",
+        },
+      ],
+      name: "ProposalCreation",
     },
-  ],
-  errors: [
     {
-      code: 6000,
-      msg: "You are not a holder",
-      name: "NotABagHolder",
+      fields: [
+        {
+          index: false,
+          name: "id",
+          type: "u64",
+        },
+        {
+          index: false,
+          name: "admin",
+          type: "publicKey",
+        },
+        {
+          index: false,
+          name: "approvals",
+          type: "u8",
+        },
+        {
+          index: false,
+          name: "eta",
+          type: "i64",
+        },
+        {
+          index: false,
+          name: "seq",
+          type: "u64",
+        },
+      ],
+      name: "ProposalApproval",
     },
     {
-      code: 6001,
-      msg: "You do not have a profit",
-      name: "NoPofit",
+      fields: [
+        {
+          index: false,
+          name: "id",
+          type: "u64",
+        },
+        {
+          index: false,
+          name: "executor",
+          type: "publicKey",
+        },
+        {
+          index: false,
+          name: "seq",
+          type: "u64",
+        },
+      ],
+      name: "ProposalExecution",
     },
     {
-      code: 6002,
-      msg: "You are not an administrator",
-      name: "NotAnAdmin",
+      fields: [
+        {
+          index: false,
+          name: "id",
+          type: "u64",
+        },
+        {
+          index: false,
+          name: "admin",
+          type: "publicKey",
+        },
+        {
+          index: false,
+          name: "seq",
+          type: "u64",
+        },
+      ],
+      name: "ProposalCancellation",
     },
+  ],
+  instructions: [
     {
-      code: 6003,
-      msg: "It is in initial phase",
-      name: "IsInitialPhase",
+      accounts: [
+        {
+          isMut: true,
+          isSigner: true,
+          name: "admin",
+        },
+        {
+          isMut: true,
+          isSigner: false,
+          name: "adminData",
+        },
+        {
+          isMut: true,
+          isSigner: false,
+          name: "metadata",
+        },
+        {
+          docs: ["CHECK gets created by the program"],
+          isMut: true,
+          isSigner: false,
+          name: "mint",
+        },
+        {
+          isMut: true,
+          isSigner: false,
+          name: "programData",
+        },
+        {
+          isMut: true,
+          isSigner: false,
+          name: "adminRegistry",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "rent",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "sysvarInstructions",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "systemProgram",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "tokenProgram",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "tokenMetadataProgram",
+        },
+      ],
+      args: [
+        {
+          name: "metadata",
+          type: {
+            defined: "InitTokenParams",
+          },
+        },
+      ],
+      name: "initialize",
+    },
+    {
+      accounts: [
+        {
+          isMut: true,
+          isSigner: false,
+          name: "mint",
+        },
+        {
+          isMut: true,
+          isSigner: true,
+          name: "user",
+        },
+        {
+          isMut: true,
+          isSigner: false,
+          name: "userData",
+        },
+        {
+          isMut: true,
+          isSigner: false,
+          name: "userAta",
+        },
+        {
+          isMut: true,
+          isOptional: true,
+          isSigner: false,
+          name: "referredByData",
+        },
+        {
+          isMut: true,
+          isSigner: false,
+          name: "programData",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "systemProgram",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "tokenProgram",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "associatedTokenProgram",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "eventAuthority",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "program",
+        },
+      ],
+      args: [
+        {
+          name: "lamportsToSend",
+          type: "u64",
+        },
+        {
+          name: "referredBy",
+          type: {
+            option: "publicKey",
+          },
+        },
+        {
+          name: "minTokensOut",
+          type: "u64",
+        },
+        {
+          name: "deadline",
+          type: {
+            defined: "TradeDeadline",
+          },
+        },
+      ],
+      name: "buy",
+    },
+    {
+      accounts: [
+        {
+          isMut: true,
+          isSigner: false,
+          name: "mint",
+        },
+        {
+          isMut: true,
+          isSigner: true,
+          name: "user",
+        },
+        {
+          isMut: true,
+          isSigner: false,
+          name: "userData",
+        },
+        {
+          isMut: true,
+          isSigner: false,
+          name: "userAta",
+        },
+        {
+          isMut: true,
+          isSigner: false,
+          name: "programData",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "systemProgram",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "tokenProgram",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "associatedTokenProgram",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "eventAuthority",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "program",
+        },
+      ],
+      args: [
+        {
+          name: "minTokensOut",
+          type: {
+            option: "u64",
+          },
+        },
+        {
+          name: "deadline",
+          type: {
+            defined: "TradeDeadline",
+          },
+        },
+      ],
+      name: "reinvest",
     },
     {
-      code: 6004,
-      msg: "It is not in initial phase",
-      name: "IsPostInitialPhase",
+      accounts: [
+        {
+          isMut: true,
+          isSigner: false,
+          name: "mint",
+        },
+        {
+          isMut: true,
+          isSigner: true,
+          name: "user",
+        },
+        {
+          isMut: true,
+          isSigner: false,
+          name: "userData",
+        },
+        {
+          isMut: true,
+          isSigner: false,
+          name: "userAta",
+        },
+        {
+          isMut: true,
+          isSigner: false,
+          name: "programData",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "systemProgram",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "tokenProgram",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "associatedTokenProgram",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "eventAuthority",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "program",
+        },
+      ],
+      args: [
+        {
+          name: "minLamportsOut",
+          type: "u64",
+        },
+        {
+          name: "deadline",
+          type: {
+            defined: "TradeDeadline",
+          },
+        },
+      ],
+      name: "exit",
     },
     {
-      code: 6005,
-      msg: "You sent less token than required",
-      name: "SentLessToken",
+      accounts: [
+        {
+          isMut: true,
+          isSigner: false,
+          name: "mint",
+        },
+        {
+          isMut: true,
+          isSigner: true,
+          name: "user",
+        },
+        {
+          isMut: true,
+          isSigner: false,
+          name: "userData",
+        },
+        {
+          isMut: true,
+          isSigner: false,
+          name: "userAta",
+        },
+        {
+          isMut: true,
+          isSigner: false,
+          name: "toData",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "toInfo",
+        },
+        {
+          isMut: true,
+          isSigner: false,
+          name: "toAta",
+        },
+        {
+          isMut: true,
+          isSigner: false,
+          name: "programData",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "systemProgram",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "tokenProgram",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "associatedTokenProgram",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "eventAuthority",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "program",
+        },
+      ],
+      args: [
+        {
+          name: "to",
+          type: "publicKey",
+        },
+        {
+          name: "lamportsToSend",
+          type: "u64",
+        },
+        {
+          name: "deadline",
+          type: {
+            defined: "TradeDeadline",
+          },
+        },
+      ],
+      name: "transfer",
     },
     {
-      code: 6006,
-      msg: "You do not have enough funds",
-      name: "InsufficientBalance",
+      accounts: [
+        {
+          isMut: true,
+          isSigner: false,
+          name: "mint",
+        },
+        {
+          isMut: true,
+          isSigner: true,
+          name: "user",
+        },
+        {
+          isMut: true,
+          isSigner: false,
+          name: "userData",
+        },
+        {
+          isMut: true,
+          isSigner: false,
+          name: "userAta",
+        },
+        {
+          isMut: true,
+          isSigner: false,
+          name: "programData",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "systemProgram",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "tokenProgram",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "associatedTokenProgram",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "eventAuthority",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "program",
+        },
+      ],
+      args: [],
+      name: "withdraw",
     },
     {
-      code: 6007,
-      msg: "Already Initialized Account",
-      name: "AlreadyInitialized",
+      accounts: [
+        {
+          isMut: true,
+          isSigner: false,
+          name: "mint",
+        },
+        {
+          isMut: true,
+          isSigner: true,
+          name: "user",
+        },
+        {
+          isMut: true,
+          isSigner: false,
+          name: "userData",
+        },
+        {
+          isMut: true,
+          isSigner: false,
+          name: "userAta",
+        },
+        {
+          isMut: true,
+          isSigner: false,
+          name: "programData",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "systemProgram",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "tokenProgram",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "associatedTokenProgram",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "eventAuthority",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "program",
+        },
+      ],
+      args: [
+        {
+          name: "lamportsToSend",
+          type: "u64",
+        },
+        {
+          name: "minLamportsOut",
+          type: "u64",
+        },
+        {
+          name: "deadline",
+          type: {
+            defined: "TradeDeadline",
+          },
+        },
+      ],
+      name: "sell",
     },
     {
-      code: 6008,
-      msg: "To address doesn't match generated to account",
-      name: "InvalidToAccount",
+      accounts: [
+        {
+          isMut: true,
+          isSigner: true,
+          name: "user",
+        },
+        {
+          isMut: true,
+          isSigner: false,
+          name: "userData",
+        },
+        {
+          isMut: true,
+          isSigner: false,
+          name: "programData",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "systemProgram",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "eventAuthority",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "program",
+        },
+      ],
+      args: [],
+      name: "disableInitialStage",
     },
     {
-      code: 6009,
-      msg: "Signer isn't Owner",
-      name: "NotOwner",
+      accounts: [
+        {
+          isMut: true,
+          isSigner: false,
+          name: "mint",
+        },
+        {
+          isMut: true,
+          isSigner: true,
+          name: "user",
+        },
+        {
+          isMut: true,
+          isSigner: false,
+          name: "fromData",
+        },
+        {
+          isMut: true,
+          isSigner: false,
+          name: "fromAta",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "receipientInfo",
+        },
+        {
+          isMut: true,
+          isSigner: false,
+          name: "receipientData",
+        },
+        {
+          isMut: true,
+          isSigner: false,
+          name: "receipientAta",
+        },
+        {
+          isMut: true,
+          isSigner: false,
+          name: "vesting",
+        },
+        {
+          isMut: true,
+          isSigner: false,
+          name: "programData",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "systemProgram",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "tokenProgram",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "associatedTokenProgram",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "eventAuthority",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "program",
+        },
+      ],
+      args: [
+        {
+          name: "amountOfTokens",
+          type: "u128",
+        },
+        {
+          name: "updatePayoutBy",
+          type: "i128",
+        },
+        {
+          name: "receipient",
+          type: "publicKey",
+        },
+        {
+          name: "schedule",
+          type: {
+            defined: "VestingSchedule",
+          },
+        },
+      ],
+      name: "distributeToken",
     },
     {
-      code: 6010,
-      msg: "You can't refer yourself",
-      name: "SelfReferral",
+      accounts: [
+        {
+          isMut: true,
+          isSigner: false,
+          name: "mint",
+        },
+        {
+          isMut: false,
+          isSigner: true,
+          name: "admin",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "adminData",
+        },
+        {
+          isMut: true,
+          isSigner: false,
+          name: "vesting",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "receipientInfo",
+        },
+        {
+          isMut: true,
+          isSigner: false,
+          name: "receipientData",
+        },
+        {
+          isMut: true,
+          isSigner: false,
+          name: "receipientAta",
+        },
+        {
+          isMut: true,
+          isSigner: false,
+          name: "programData",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "tokenProgram",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "eventAuthority",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "program",
+        },
+      ],
+      args: [],
+      name: "revokeVesting",
     },
     {
-      code: 6011,
-      msg: "You can't transfer to yourself",
-      name: "SelfTransfer",
+      accounts: [
+        {
+          isMut: true,
+          isSigner: true,
+          name: "admin",
+        },
+        {
+          isMut: true,
+          isSigner: false,
+          name: "adminData",
+        },
+        {
+          isMut: true,
+          isSigner: false,
+          name: "userData",
+        },
+        {
+          isMut: true,
+          isSigner: false,
+          name: "programData",
+        },
+        {
+          isMut: true,
+          isSigner: false,
+          name: "adminRegistry",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "systemProgram",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "eventAuthority",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "program",
+        },
+      ],
+      args: [
+        {
+          name: "user",
+          type: "publicKey",
+        },
+        {
+          name: "status",
+          type: "bool",
+        },
+      ],
+      name: "setAdministrator",
     },
-  ],
-  instructions: [
     {
       accounts: [
         {
-          isMut: true,
+          isMut: false,
           isSigner: true,
-          name: "admin",
+          name: "user",
         },
         {
           isMut: true,
           isSigner: false,
-          name: "adminData",
+          name: "userData",
         },
         {
           isMut: true,
           isSigner: false,
-          name: "metadata",
+          name: "programData",
         },
         {
-          docs: ["CHECK gets created by the program"],
           isMut: true,
           isSigner: false,
-          name: "mint",
+          name: "adminRegistry",
         },
         {
-          isMut: true,
+          isMut: false,
           isSigner: false,
-          name: "programData",
+          name: "eventAuthority",
         },
         {
           isMut: false,
           isSigner: false,
-          name: "rent",
+          name: "program",
+        },
+      ],
+      args: [],
+      name: "acceptAdministrator",
+    },
+    {
+      accounts: [
+        {
+          isMut: true,
+          isSigner: true,
+          name: "admin",
         },
         {
-          isMut: false,
+          isMut: true,
           isSigner: false,
-          name: "sysvarInstructions",
+          name: "adminData",
+        },
+        {
+          isMut: true,
+          isSigner: false,
+          name: "userData",
+        },
+        {
+          isMut: true,
+          isSigner: false,
+          name: "programData",
         },
         {
           isMut: false,
@@ -1108,108 +5223,111 @@ export const IDL: App = {
         {
           isMut: false,
           isSigner: false,
-          name: "tokenProgram",
+          name: "eventAuthority",
         },
         {
           isMut: false,
           isSigner: false,
-          name: "tokenMetadataProgram",
+          name: "program",
         },
       ],
       args: [
         {
-          name: "metadata",
-          type: {
-            defined: "InitTokenParams",
-          },
+          name: "user",
+          type: "publicKey",
+        },
+        {
+          name: "status",
+          type: "bool",
         },
       ],
-      name: "initialize",
+      name: "setAmbassador",
     },
     {
       accounts: [
         {
           isMut: true,
+          isSigner: true,
+          name: "admin",
+        },
+        {
+          isMut: false,
           isSigner: false,
-          name: "mint",
+          name: "adminData",
         },
         {
           isMut: true,
-          isSigner: true,
-          name: "user",
+          isSigner: false,
+          name: "programData",
         },
         {
-          isMut: true,
+          isMut: false,
           isSigner: false,
-          name: "userData",
+          name: "mint",
         },
         {
           isMut: true,
           isSigner: false,
-          name: "userAta",
+          name: "metadata",
         },
         {
-          isMut: true,
-          isOptional: true,
+          isMut: false,
           isSigner: false,
-          name: "referredByData",
+          name: "systemProgram",
         },
         {
-          isMut: true,
+          isMut: false,
           isSigner: false,
-          name: "programData",
+          name: "sysvarInstructions",
         },
         {
           isMut: false,
           isSigner: false,
-          name: "systemProgram",
+          name: "tokenMetadataProgram",
         },
         {
           isMut: false,
           isSigner: false,
-          name: "tokenProgram",
+          name: "eventAuthority",
         },
         {
           isMut: false,
           isSigner: false,
-          name: "associatedTokenProgram",
+          name: "program",
         },
       ],
       args: [
         {
-          name: "lamportsToSend",
-          type: "u128",
+          name: "name",
+          type: "string",
         },
         {
-          name: "referredBy",
-          type: {
-            option: "publicKey",
-          },
+          name: "symbol",
+          type: "string",
+        },
+        {
+          name: "uri",
+          type: "string",
         },
       ],
-      name: "buy",
+      name: "updateMetadataAccount",
     },
     {
       accounts: [
-        {
-          isMut: true,
-          isSigner: false,
-          name: "mint",
-        },
         {
           isMut: true,
           isSigner: true,
-          name: "user",
+          name: "admin",
         },
         {
           isMut: true,
           isSigner: false,
-          name: "userData",
+          name: "adminData",
         },
         {
           isMut: true,
           isSigner: false,
-          name: "userAta",
+          name: "userData",
         },
         {
           isMut: true,
@@ -1224,24 +5342,28 @@ export const IDL: App = {
         {
           isMut: false,
           isSigner: false,
-          name: "tokenProgram",
+          name: "eventAuthority",
         },
         {
           isMut: false,
           isSigner: false,
-          name: "associatedTokenProgram",
+          name: "program",
         },
       ],
-      args: [],
-      name: "reinvest",
+      args: [
+        {
+          name: "user",
+          type: "publicKey",
+        },
+        {
+          name: "roles",
+          type: "u8",
+        },
+      ],
+      name: "setRoles",
     },
     {
       accounts: [
-        {
-          isMut: true,
-          isSigner: false,
-          name: "mint",
-        },
         {
           isMut: true,
           isSigner: true,
@@ -1252,11 +5374,6 @@ export const IDL: App = {
           isSigner: false,
           name: "userData",
         },
-        {
-          isMut: true,
-          isSigner: false,
-          name: "userAta",
-        },
         {
           isMut: true,
           isSigner: false,
@@ -1270,58 +5387,48 @@ export const IDL: App = {
         {
           isMut: false,
           isSigner: false,
-          name: "tokenProgram",
+          name: "eventAuthority",
         },
         {
           isMut: false,
           isSigner: false,
-          name: "associatedTokenProgram",
+          name: "program",
         },
       ],
-      args: [],
-      name: "exit",
+      args: [
+        {
+          name: "amountOfTokens",
+          type: "u128",
+        },
+      ],
+      name: "setStakingRequirement",
     },
     {
       accounts: [
-        {
-          isMut: true,
-          isSigner: false,
-          name: "mint",
-        },
         {
           isMut: true,
           isSigner: true,
-          name: "user",
+          name: "admin",
         },
         {
-          isMut: true,
+          isMut: false,
           isSigner: false,
-          name: "userData",
+          name: "adminData",
         },
         {
           isMut: true,
           isSigner: false,
-          name: "userAta",
+          name: "programData",
         },
         {
           isMut: true,
           isSigner: false,
-          name: "toData",
+          name: "proposal",
         },
         {
           isMut: false,
           isSigner: false,
-          name: "toInfo",
-        },
-        {
-          isMut: true,
-          isSigner: false,
-          name: "toAta",
-        },
-        {
-          isMut: true,
-          isSigner: false,
-          name: "programData",
+          name: "adminRegistry",
         },
         {
           isMut: false,
@@ -1331,47 +5438,35 @@ export const IDL: App = {
         {
           isMut: false,
           isSigner: false,
-          name: "tokenProgram",
+          name: "eventAuthority",
         },
         {
           isMut: false,
           isSigner: false,
-          name: "associatedTokenProgram",
+          name: "program",
         },
       ],
       args: [
         {
-          name: "to",
-          type: "publicKey",
-        },
-        {
-          name: "lamportsToSend",
-          type: "u128",
+          name: "action",
+          type: {
+            defined: "AdminAction",
+          },
         },
       ],
-      name: "transfer",
+      name: "propose",
     },
     {
       accounts: [
         {
-          isMut: true,
-          isSigner: false,
-          name: "mint",
-        },
-        {
-          isMut: true,
+          isMut: false,
           isSigner: true,
-          name: "user",
-        },
-        {
-          isMut: true,
-          isSigner: false,
-          name: "userData",
+          name: "admin",
         },
         {
-          isMut: true,
+          isMut: false,
           isSigner: false,
-          name: "userAta",
+          name: "adminData",
         },
         {
           isMut: true,
@@ -1379,50 +5474,55 @@ export const IDL: App = {
           name: "programData",
         },
         {
-          isMut: false,
+          isMut: true,
           isSigner: false,
-          name: "systemProgram",
+          name: "proposal",
         },
         {
           isMut: false,
           isSigner: false,
-          name: "tokenProgram",
+          name: "eventAuthority",
         },
         {
           isMut: false,
           isSigner: false,
-          name: "associatedTokenProgram",
+          name: "program",
         },
       ],
       args: [],
-      name: "withdraw",
+      name: "approveProposal",
     },
     {
       accounts: [
         {
           isMut: true,
-          isSigner: false,
-          name: "mint",
+          isSigner: true,
+          name: "executor",
         },
         {
           isMut: true,
-          isSigner: true,
-          name: "user",
+          isSigner: false,
+          name: "programData",
         },
         {
           isMut: true,
           isSigner: false,
-          name: "userData",
+          name: "proposal",
         },
         {
           isMut: true,
           isSigner: false,
-          name: "userAta",
+          name: "adminRegistry",
         },
         {
+          docs: [
+            "The user an administrator, ambassador or roles proposal applies to, created if they have",
+            "no account yet",
+          ],
           isMut: true,
+          isOptional: true,
           isSigner: false,
-          name: "programData",
+          name: "targetData",
         },
         {
           isMut: false,
@@ -1432,138 +5532,120 @@ export const IDL: App = {
         {
           isMut: false,
           isSigner: false,
-          name: "tokenProgram",
+          name: "eventAuthority",
         },
         {
           isMut: false,
           isSigner: false,
-          name: "associatedTokenProgram",
-        },
-      ],
-      args: [
-        {
-          name: "lamportsToSend",
-          type: "u128",
+          name: "program",
         },
       ],
-      name: "sell",
+      args: [],
+      name: "executeProposal",
     },
     {
       accounts: [
         {
           isMut: true,
           isSigner: true,
-          name: "user",
-        },
-        {
-          isMut: true,
-          isSigner: false,
-          name: "userData",
+          name: "executor",
         },
         {
           isMut: true,
           isSigner: false,
-          name: "programData",
+          name: "proposal",
         },
         {
           isMut: false,
           isSigner: false,
-          name: "systemProgram",
-        },
-      ],
-      args: [],
-      name: "disableInitialStage",
-    },
-    {
-      accounts: [
-        {
-          isMut: true,
-          isSigner: false,
-          name: "mint",
-        },
-        {
-          isMut: true,
-          isSigner: true,
-          name: "user",
-        },
-        {
-          isMut: true,
-          isSigner: false,
-          name: "fromData",
+          name: "adminRegistry",
         },
         {
           isMut: true,
           isSigner: false,
-          name: "fromAta",
+          name: "programData",
         },
         {
           isMut: false,
           isSigner: false,
-          name: "receipientInfo",
+          name: "mint",
         },
         {
           isMut: true,
           isSigner: false,
-          name: "receipientData",
+          name: "metadata",
         },
         {
-          isMut: true,
+          isMut: false,
           isSigner: false,
-          name: "receipientAta",
+          name: "systemProgram",
         },
         {
-          isMut: true,
+          isMut: false,
           isSigner: false,
-          name: "programData",
+          name: "sysvarInstructions",
         },
         {
           isMut: false,
           isSigner: false,
-          name: "systemProgram",
+          name: "tokenMetadataProgram",
         },
         {
           isMut: false,
           isSigner: false,
-          name: "tokenProgram",
+          name: "eventAuthority",
         },
         {
           isMut: false,
           isSigner: false,
-          name: "associatedTokenProgram",
+          name: "program",
         },
       ],
-      args: [
+      args: [],
+      name: "executeMetadataProposal",
+    },
+    {
+      accounts: [
         {
-          name: "amountOfTokens",
-          type: "u128",
+          isMut: false,
+          isSigner: true,
+          name: "executor",
         },
         {
-          name: "updatePayoutBy",
-          type: "i128",
+          isMut: true,
+          isSigner: false,
+          name: "proposal",
         },
         {
-          name: "receipient",
-          type: "publicKey",
+          isMut: false,
+          isSigner: false,
+          name: "adminRegistry",
         },
-      ],
-      name: "distributeToken",
-    },
-    {
-      accounts: [
         {
           isMut: true,
-          isSigner: true,
-          name: "admin",
+          isSigner: false,
+          name: "mint",
         },
         {
+          docs: ["The grant the proposal revokes"],
           isMut: true,
           isSigner: false,
-          name: "adminData",
+          name: "vesting",
+        },
+        {
+          isMut: false,
+          isSigner: false,
+          name: "receipientInfo",
         },
         {
           isMut: true,
           isSigner: false,
-          name: "userData",
+          name: "receipientData",
+        },
+        {
+          isMut: true,
+          isSigner: false,
+          name: "receipientAta",
         },
         {
           isMut: true,
@@ -1573,91 +5655,79 @@ export const IDL: App = {
         {
           isMut: false,
           isSigner: false,
-          name: "systemProgram",
+          name: "tokenProgram",
         },
-      ],
-      args: [
         {
-          name: "user",
-          type: "publicKey",
+          isMut: false,
+          isSigner: false,
+          name: "eventAuthority",
         },
         {
-          name: "status",
-          type: "bool",
+          isMut: false,
+          isSigner: false,
+          name: "program",
         },
       ],
-      name: "setAdministrator",
+      args: [],
+      name: "executeRevocationProposal",
     },
     {
       accounts: [
         {
-          isMut: true,
+          isMut: false,
           isSigner: true,
           name: "admin",
         },
         {
-          isMut: true,
+          isMut: false,
           isSigner: false,
           name: "adminData",
         },
         {
           isMut: true,
           isSigner: false,
-          name: "userData",
+          name: "programData",
         },
         {
           isMut: true,
           isSigner: false,
-          name: "programData",
+          name: "proposal",
         },
         {
           isMut: false,
           isSigner: false,
-          name: "systemProgram",
-        },
-      ],
-      args: [
-        {
-          name: "user",
-          type: "publicKey",
+          name: "eventAuthority",
         },
         {
-          name: "status",
-          type: "bool",
+          isMut: false,
+          isSigner: false,
+          name: "program",
         },
       ],
-      name: "setAmbassador",
+      args: [],
+      name: "cancelProposal",
     },
     {
       accounts: [
         {
-          isMut: true,
-          isSigner: true,
-          name: "user",
-        },
-        {
-          isMut: true,
+          isMut: false,
           isSigner: false,
           name: "userData",
         },
-        {
-          isMut: true,
-          isSigner: false,
-          name: "programData",
-        },
         {
           isMut: false,
           isSigner: false,
-          name: "systemProgram",
+          name: "programData",
         },
       ],
       args: [
         {
-          name: "amountOfTokens",
-          type: "u128",
+          name: "includingRef",
+          type: "bool",
         },
       ],
-      name: "setStakingRequirement",
+      name: "myDividends",
+      returns: "u128",
     },
     {
       accounts: [
@@ -1674,11 +5744,13 @@ export const IDL: App = {
       ],
       args: [
         {
-          name: "includingRef",
-          type: "bool",
+          name: "at",
+          type: {
+            option: "i64",
+          },
         },
       ],
-      name: "myDividends",
+      name: "vestedBalance",
       returns: "u128",
     },
     {
@@ -1742,6 +5814,18 @@ export const IDL: App = {
   ],
   name: "app",
   types: [
+    {
+      name: "SetClockOffset",
+      type: {
+        fields: [
+          {
+            name: "offset",
+            type: "i64",
+          },
+        ],
+        kind: "struct",
+      },
+    },
     {
       name: "InitTokenParams",
       type: {
@@ -1762,10 +5846,342 @@ export const IDL: App = {
             name: "decimals",
             type: "u8",
           },
+          {
+            name: "dividendFee",
+            type: "u8",
+          },
+          {
+            name: "tokenInitialPrice",
+            type: "u128",
+          },
+          {
+            name: "tokenIncrementalPrice",
+            type: "u128",
+          },
+          {
+            name: "magnitude",
+            type: "u64",
+          },
+          {
+            name: "stakingRequirement",
+            type: "u128",
+          },
+          {
+            name: "curve",
+            type: {
+              defined: "CurveKind",
+            },
+          },
+          {
+            name: "ambassadorMaxPurchase",
+            type: "u128",
+          },
+          {
+            name: "ambassadorQuota",
+            type: "u128",
+          },
+        ],
+        kind: "struct",
+      },
+    },
+    {
+      name: "TradeDeadline",
+      type: {
+        fields: [
+          {
+            name: "validUntilSlot",
+            type: {
+              option: "u64",
+            },
+          },
+          {
+            name: "validUntilTs",
+            type: {
+              option: "i64",
+            },
+          },
+        ],
+        kind: "struct",
+      },
+    },
+    {
+      docs: [
+        "* Unlock schedule of a `distribute_token` grant. Nothing unlocks until `cliff` seconds after\n * the start and everything has after `duration` seconds. In between the unlocked part grows\n * linearly, in steps of `release_interval` seconds or every second when it is zero.",
+      ],
+      name: "VestingSchedule",
+      type: {
+        fields: [
+          {
+            name: "startTime",
+            type: {
+              option: "i64",
+            },
+          },
+          {
+            name: "cliff",
+            type: "i64",
+          },
+          {
+            name: "duration",
+            type: "i64",
+          },
+          {
+            name: "releaseInterval",
+            type: "i64",
+          },
+        ],
+        kind: "struct",
+      },
+    },
+    {
+      name: "PendingAdmin",
+      type: {
+        fields: [
+          {
+            name: "user",
+            type: "publicKey",
+          },
+          {
+            name: "nominatedBy",
+            type: "publicKey",
+          },
         ],
         kind: "struct",
       },
     },
+    {
+      docs: [
+        "* Pricing model picked at initialize time, the curves themselves live in the `app-math` crate.",
+      ],
+      name: "CurveKind",
+      type: {
+        kind: "enum",
+        variants: [
+          {
+            name: "Linear",
+          },
+          {
+            name: "Exponential",
+          },
+          {
+            name: "ConstantProduct",
+          },
+        ],
+      },
+    },
+    {
+      name: "AdminAction",
+      type: {
+        kind: "enum",
+        variants: [
+          {
+            name: "DisableInitialStage",
+          },
+          {
+            fields: [
+              {
+                name: "user",
+                type: "publicKey",
+              },
+              {
+                name: "status",
+                type: "bool",
+              },
+            ],
+            name: "SetAdministrator",
+          },
+          {
+            fields: [
+              {
+                name: "user",
+                type: "publicKey",
+              },
+              {
+                name: "status",
+                type: "bool",
+              },
+            ],
+            name: "SetAmbassador",
+          },
+          {
+            fields: [
+              {
+                name: "user",
+                type: "publicKey",
+              },
+              {
+                name: "roles",
+                type: "u8",
+              },
+            ],
+            name: "SetRoles",
+          },
+          {
+            fields: [
+              {
+                name: "amountOfTokens",
+                type: "u128",
+              },
+            ],
+            name: "SetStakingRequirement",
+          },
+          {
+            fields: [
+              {
+                name: "threshold",
+                type: "u8",
+              },
+            ],
+            name: "SetAdminThreshold",
+          },
+          {
+            fields: [
+              {
+                name: "dividendFee",
+                type: "u8",
+              },
+            ],
+            name: "SetDividendFee",
+          },
+          {
+            fields: [
+              {
+                name: "delay",
+                type: "i64",
+              },
+            ],
+            name: "SetTimelockDelay",
+          },
+          {
+            fields: [
+              {
+                name: "name",
+                type: "string",
+              },
+              {
+                name: "symbol",
+                type: "string",
+              },
+              {
+                name: "uri",
+                type: "string",
+              },
+            ],
+            name: "UpdateMetadata",
+          },
+          {
+            fields: [
+              {
+                name: "receipient",
+                type: "publicKey",
+              },
+              {
+                name: "index",
+                type: "u64",
+              },
+            ],
+            name: "RevokeVesting",
+          },
+        ],
+      },
+    },
+    {
+      name: "ProposalStatus",
+      type: {
+        kind: "enum",
+        variants: [
+          {
+            name: "Active",
+          },
+          {
+            name: "Executed",
+          },
+          {
+            name: "Cancelled",
+          },
+        ],
+      },
+    },
+    {
+      name: "AdminActionEvent",
+      type: {
+        kind: "enum",
+        variants: [
+          {
+            fields: [
+              {
+                defined: "InitialStageEnd",
+              },
+            ],
+            name: "InitialStageEnd",
+          },
+          {
+            fields: [
+              {
+                defined: "AdminNomination",
+              },
+            ],
+            name: "AdminNomination",
+          },
+          {
+            fields: [
+              {
+                defined: "AdministratorUpdate",
+              },
+            ],
+            name: "AdministratorUpdate",
+          },
+          {
+            fields: [
+              {
+                defined: "AmbassadorUpdate",
+              },
+            ],
+            name: "AmbassadorUpdate",
+          },
+          {
+            fields: [
+              {
+                defined: "RolesUpdate",
+              },
+            ],
+            name: "RolesUpdate",
+          },
+          {
+            fields: [
+              {
+                defined: "StakingRequirementUpdate",
+              },
+            ],
+            name: "StakingRequirementUpdate",
+          },
+          {
+            fields: [
+              {
+                defined: "AdminThresholdUpdate",
+              },
+            ],
+            name: "AdminThresholdUpdate",
+          },
+          {
+            fields: [
+              {
+                defined: "DividendFeeUpdate",
+              },
+            ],
+            name: "DividendFeeUpdate",
+          },
+          {
+            fields: [
+              {
+                defined: "TimelockDelayUpdate",
+              },
+            ],
+            name: "TimelockDelayUpdate",
+          },
+        ],
+      },
+    },
   ],
   version: "0.1.0",
 };
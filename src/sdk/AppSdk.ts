@@ -14,21 +14,42 @@ import {
 import { APP_IDL, App, AppProgram } from "generated";
 import { IDL } from "generated/App";
 import {
+  AdminAction,
   Metadata,
+  TradeDeadline,
+  VestingSchedule,
+  createAcceptAdministratorIx,
+  createApproveProposalIx,
   createBuyIx,
+  createCancelProposalIx,
   createDisableInitialStageIx,
   createDistributeTokenIxs,
+  createExecuteMetadataProposalIx,
+  createExecuteProposalIx,
+  createExecuteRevocationProposalIx,
   createExitIx,
   createInitializeIx,
+  createProposeIx,
   createReinvestIx,
+  createRevokeVestingIx,
   createSellIx,
   createSetAdministratorIx,
   createSetAmbassadorIx,
+  createSetClockOffsetIx,
+  createSetRolesIx,
   createSetStakingRequirementIx,
   createTransferIx,
+  createUpdateMetadataIx,
   createWithdrawIx,
 } from "sdk/instructions/createIx";
-import { findProgramPda, findUserPda } from "utils/pdas";
+import {
+  findAdminRegistryPda,
+  findProgramPda,
+  findProposalPda,
+  findUserPda,
+  findVestingPda,
+} from "utils/pdas";
+import { Maybe } from "types/UtilityTypes";
 import ixToTx from "utils/solana/ixToTx";
 import ixsToTx from "utils/solana/ixsToTx";
 import {
@@ -37,6 +58,7 @@ import {
   getCalculateTokensReceived,
   getMyDividends,
   getSellPrice,
+  getVestedBalance,
 } from "./instructions/getters";
 
 export default class AppSdk {
@@ -70,14 +92,36 @@ export default class AppSdk {
     return ixToTx(this.connection, payer, ix, this.test);
   }
 
-  async createBuyTx(payer: PublicKey, amount: BN, referral?: PublicKey) {
+  async createBuyTx(
+    payer: PublicKey,
+    amount: BN,
+    minTokensOut: BN,
+    referral?: PublicKey,
+    deadline?: TradeDeadline
+  ) {
     if (referral && referral.equals(payer)) referral = undefined;
-    const ix = await createBuyIx(payer, amount, this.program, referral);
+    const ix = await createBuyIx(
+      payer,
+      amount,
+      minTokensOut,
+      this.program,
+      referral,
+      deadline
+    );
     return ixToTx(this.connection, payer, ix, this.test);
   }
 
-  async createReinvestTx(payer: PublicKey) {
-    const ix = await createReinvestIx(payer, this.program);
+  async createReinvestTx(
+    payer: PublicKey,
+    minTokensOut?: BN,
+    deadline?: TradeDeadline
+  ) {
+    const ix = await createReinvestIx(
+      payer,
+      this.program,
+      minTokensOut,
+      deadline
+    );
     return ixToTx(this.connection, payer, ix, this.test);
   }
 
@@ -86,7 +130,8 @@ export default class AppSdk {
     receipients: Array<PublicKey>,
     batchSize: number,
     amount: BN,
-    payout: BN
+    payout: BN,
+    schedule?: VestingSchedule
   ) {
     const txs: Array<Transaction> = [];
     const ixs = await createDistributeTokenIxs(
@@ -94,7 +139,8 @@ export default class AppSdk {
       receipients,
       amount,
       payout,
-      this.program
+      this.program,
+      schedule
     );
 
     const runs =
@@ -113,13 +159,33 @@ export default class AppSdk {
     return txs;
   }
 
-  async createExitTx(payer: PublicKey) {
-    const ix = await createExitIx(payer, this.program);
+  async createExitTx(
+    payer: PublicKey,
+    minLamportsOut: BN,
+    deadline?: TradeDeadline
+  ) {
+    const ix = await createExitIx(
+      payer,
+      minLamportsOut,
+      this.program,
+      deadline
+    );
     return ixToTx(this.connection, payer, ix, this.test);
   }
 
-  async createTransferTx(payer: PublicKey, to: PublicKey, amount: BN) {
-    const ix = await createTransferIx(payer, to, amount, this.program);
+  async createTransferTx(
+    payer: PublicKey,
+    to: PublicKey,
+    amount: BN,
+    deadline?: TradeDeadline
+  ) {
+    const ix = await createTransferIx(
+      payer,
+      to,
+      amount,
+      this.program,
+      deadline
+    );
     return ixToTx(this.connection, payer, ix, this.test);
   }
 
@@ -128,8 +194,19 @@ export default class AppSdk {
     return ixToTx(this.connection, payer, ix, this.test);
   }
 
-  async createSellTx(payer: PublicKey, amount: BN) {
-    const ix = await createSellIx(payer, amount, this.program);
+  async createSellTx(
+    payer: PublicKey,
+    amount: BN,
+    minLamportsOut: BN,
+    deadline?: TradeDeadline
+  ) {
+    const ix = await createSellIx(
+      payer,
+      amount,
+      minLamportsOut,
+      this.program,
+      deadline
+    );
     return ixToTx(this.connection, payer, ix, this.test);
   }
 
@@ -141,6 +218,7 @@ export default class AppSdk {
     return ixToTx(this.connection, payer, ix, this.test);
   }
 
+  /** @deprecated timelocked by default, propose `setAdministrator` instead */
   async createSetAdministratorTx(
     payer: PublicKey,
     user: PublicKey,
@@ -155,6 +233,11 @@ export default class AppSdk {
     return ixToTx(this.connection, payer, ix, this.test);
   }
 
+  async createAcceptAdministratorTx(payer: PublicKey) {
+    const ix = await createAcceptAdministratorIx(payer, this.program);
+    return ixToTx(this.connection, payer, ix, this.test);
+  }
+
   async createSetAmbassadorTx(
     payer: PublicKey,
     user: PublicKey,
@@ -164,6 +247,45 @@ export default class AppSdk {
     return ixToTx(this.connection, payer, ix, this.test);
   }
 
+  /** @deprecated timelocked by default, propose `setRoles` instead */
+  async createSetRolesTx(payer: PublicKey, user: PublicKey, roles: number) {
+    const ix = await createSetRolesIx(payer, user, roles, this.program);
+    return ixToTx(this.connection, payer, ix, this.test);
+  }
+
+  async createRevokeVestingTx(
+    payer: PublicKey,
+    receipient: PublicKey,
+    index: BN
+  ) {
+    const ix = await createRevokeVestingIx(
+      payer,
+      receipient,
+      index,
+      this.program
+    );
+    return ixToTx(this.connection, payer, ix, this.test);
+  }
+
+  async createUpdateMetadataTx(
+    payer: PublicKey,
+    name: string,
+    symbol: string,
+    uri: string
+  ) {
+    const ix = await createUpdateMetadataIx(
+      payer,
+      name,
+      symbol,
+      uri,
+      this.program
+    );
+    return ixToTx(this.connection, payer, ix, this.test);
+  }
+
+  /**
+   * @deprecated timelocked by default, propose `setStakingRequirement` instead
+   */
   async createSetStakingRequirementTx(payer: PublicKey, amountOfTokens: BN) {
     const ix = await createSetStakingRequirementIx(
       payer,
@@ -173,6 +295,71 @@ export default class AppSdk {
     return ixToTx(this.connection, payer, ix, this.test);
   }
 
+  async createSetClockOffsetTx(payer: PublicKey, offset: BN) {
+    const ix = await createSetClockOffsetIx(payer, offset, this.program);
+    return ixToTx(this.connection, payer, ix, this.test);
+  }
+
+  //
+  // PROPOSALS
+  //
+  async createProposeTx(payer: PublicKey, action: AdminAction) {
+    // the new proposal takes the next id
+    const { account } = await this.fetchProgramInfo();
+    const ix = await createProposeIx(
+      payer,
+      action,
+      account.proposalCount,
+      this.program
+    );
+    return ixToTx(this.connection, payer, ix, this.test);
+  }
+
+  async createApproveProposalTx(payer: PublicKey, proposalId: BN) {
+    const ix = await createApproveProposalIx(payer, proposalId, this.program);
+    return ixToTx(this.connection, payer, ix, this.test);
+  }
+
+  async createExecuteProposalTx(payer: PublicKey, proposalId: BN) {
+    const { account } = await this.fetchProposalInfo(proposalId);
+    const action = account.action as any;
+    if (action.updateMetadata != null) {
+      const ix = await createExecuteMetadataProposalIx(
+        payer,
+        proposalId,
+        this.program
+      );
+      return ixToTx(this.connection, payer, ix, this.test);
+    }
+    if (action.revokeVesting != null) {
+      const { index, receipient } = action.revokeVesting;
+      const ix = await createExecuteRevocationProposalIx(
+        payer,
+        proposalId,
+        receipient,
+        index,
+        this.program
+      );
+      return ixToTx(this.connection, payer, ix, this.test);
+    }
+
+    const target: PublicKey | null =
+      (action.setAdministrator ?? action.setAmbassador ?? action.setRoles)
+        ?.user ?? null;
+    const ix = await createExecuteProposalIx(
+      payer,
+      proposalId,
+      target,
+      this.program
+    );
+    return ixToTx(this.connection, payer, ix, this.test);
+  }
+
+  async createCancelProposalTx(payer: PublicKey, proposalId: BN) {
+    const ix = await createCancelProposalIx(payer, proposalId, this.program);
+    return ixToTx(this.connection, payer, ix, this.test);
+  }
+
   //
   // FETCH ACCOUNTS
   //
@@ -192,6 +379,33 @@ export default class AppSdk {
     };
   }
 
+  async fetchAdminRegistry() {
+    const [registry] = this.findAdminRegistryPda();
+    return {
+      account: await this.program.account.adminRegistry.fetch(
+        registry,
+        "confirmed"
+      ),
+      pubkey: registry,
+    };
+  }
+
+  async fetchVestingInfo(userKey: PublicKey, index: BN) {
+    const [vesting] = this.findVestingPda(userKey, index);
+    return {
+      account: await this.program.account.vesting.fetch(vesting, "confirmed"),
+      pubkey: vesting,
+    };
+  }
+
+  async fetchProposalInfo(proposalId: BN) {
+    const [proposal] = this.findProposalPda(proposalId);
+    return {
+      account: await this.program.account.proposal.fetch(proposal, "confirmed"),
+      pubkey: proposal,
+    };
+  }
+
   //
   // PDAS
   //
@@ -203,6 +417,18 @@ export default class AppSdk {
     return findUserPda(user, this.program.programId);
   }
 
+  findAdminRegistryPda() {
+    return findAdminRegistryPda(this.program.programId);
+  }
+
+  findProposalPda(proposalId: BN) {
+    return findProposalPda(proposalId, this.program.programId);
+  }
+
+  findVestingPda(userKey: PublicKey, index: BN) {
+    return findVestingPda(userKey, index, this.program.programId);
+  }
+
   //
   // HELPER
   //
@@ -293,6 +519,19 @@ export default class AppSdk {
     return this.readOnly(awaitedValue, ixName);
   }
 
+  async vestedBalance(user: PublicKey, at: Maybe<BN> = null) {
+    const { ixName, value } = getVestedBalance(
+      user,
+      at,
+      this.program,
+      this.test
+    );
+    const awaitedValue = await value;
+    if (BN.isBN(awaitedValue)) return { logs: [], value: awaitedValue };
+
+    return this.readOnly(awaitedValue, ixName);
+  }
+
   async sellPrice() {
     const { ixName, value } = getSellPrice(this.program, this.test);
     const awaitedValue = await value;
@@ -5,6 +5,7 @@ import {
   TOKEN_2022_PROGRAM_ID,
 } from "@solana/spl-token";
 import {
+  AccountMeta,
   PublicKey,
   SYSVAR_INSTRUCTIONS_PUBKEY,
   SYSVAR_RENT_PUBKEY,
@@ -13,21 +14,95 @@ import {
 } from "@solana/web3.js";
 import { AppProgram } from "generated";
 import {
+  findAdminRegistryPda,
+  findEventAuthorityPda,
   findMetadataPda,
   findMintPda,
   findProgramPda,
+  findProposalPda,
   findUserAtaPda,
   findUserPda,
+  findVestingPda,
 } from "utils/pdas";
+import { Maybe } from "types/UtilityTypes";
 import { MPL_TOKEN_METADATA_PROGRAM_ID } from "../../constants";
 
+export type AdminAction =
+  | { disableInitialStage: Record<string, never> }
+  | { setAdministrator: { status: boolean; user: PublicKey } }
+  | { setAmbassador: { status: boolean; user: PublicKey } }
+  | { setRoles: { roles: number; user: PublicKey } }
+  | { setStakingRequirement: { amountOfTokens: BN } }
+  | { setAdminThreshold: { threshold: number } }
+  | { setDividendFee: { dividendFee: number } }
+  | { setTimelockDelay: { delay: BN } }
+  | { updateMetadata: { name: string; symbol: string; uri: string } }
+  | { revokeVesting: { index: BN; receipient: PublicKey } };
+
+export type CurveKind =
+  | { linear: Record<string, never> }
+  | { exponential: Record<string, never> }
+  | { constantProduct: Record<string, never> };
+
 export type Metadata = {
+  ambassadorMaxPurchase: BN;
+  ambassadorQuota: BN;
+  curve: CurveKind;
   decimals: number;
+  dividendFee: number;
+  magnitude: BN;
   name: string;
+  stakingRequirement: BN;
   symbol: string;
+  tokenIncrementalPrice: BN;
+  tokenInitialPrice: BN;
   uri: string;
 };
 
+export type TradeDeadline = {
+  validUntilSlot: Maybe<BN>;
+  validUntilTs: Maybe<BN>;
+};
+
+export const NO_DEADLINE: TradeDeadline = {
+  validUntilSlot: null,
+  validUntilTs: null,
+};
+
+// Times are in seconds, `startTime` defaults to the time of the grant and a
+// zero `releaseInterval` releases every second
+export type VestingSchedule = {
+  cliff: BN;
+  duration: BN;
+  releaseInterval: BN;
+  startTime: Maybe<BN>;
+};
+
+// Released every second over a hundred days
+export const DEFAULT_VESTING_SCHEDULE: VestingSchedule = {
+  cliff: new BN(0),
+  duration: new BN(100 * 24 * 60 * 60),
+  releaseInterval: new BN(0),
+  startTime: null,
+};
+
+// The user's vesting grants, which instructions spending tokens take as
+// remaining accounts until the last grant has ended
+export const findVestingAccounts = async (
+  user: PublicKey,
+  program: AppProgram
+): Promise<Array<AccountMeta>> => {
+  const [userInfo] = findUserPda(user, program.programId);
+  const account = await program.account.user.fetchNullable(userInfo);
+  const count = account ? account.vestingCount.toNumber() : 0;
+
+  return Array.from({ length: count }, (_, index) => ({
+    isSigner: false,
+    isWritable: false,
+    pubkey: findVestingPda(user, new BN(index), program.programId)[0],
+  }));
+};
+
 export const createInitializeIx = async (
   user: PublicKey,
   meta: Metadata,
@@ -38,12 +113,14 @@ export const createInitializeIx = async (
   const [userInfo] = findUserPda(user, programId);
   const [mint] = findMintPda(programId);
   const [metadata] = findMetadataPda(mint);
+  const [adminRegistry] = findAdminRegistryPda(programId);
 
   return program.methods
     .initialize(meta)
     .accounts({
       admin: user,
       adminData: userInfo,
+      adminRegistry,
       metadata,
       mint,
       programData: programInfo,
@@ -59,8 +136,10 @@ export const createInitializeIx = async (
 export const createBuyIx = async (
   user: PublicKey,
   amount: BN,
+  minTokensOut: BN,
   program: AppProgram,
-  referral?: PublicKey
+  referral?: PublicKey,
+  deadline: TradeDeadline = NO_DEADLINE
 ): Promise<TransactionInstruction> => {
   const [programInfo] = findProgramPda(program.programId);
   const [userInfo] = findUserPda(user, program.programId);
@@ -69,12 +148,15 @@ export const createBuyIx = async (
     : [null];
   const [mint] = findMintPda(program.programId);
   const [userAta] = findUserAtaPda(mint, user);
+  const [eventAuthority] = findEventAuthorityPda(program.programId);
 
   return program.methods
-    .buy(amount, referral ? referral : null)
+    .buy(amount, referral ? referral : null, minTokensOut, deadline)
     .accounts({
       associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      eventAuthority,
       mint,
+      program: program.programId,
       programData: programInfo,
       referredByData: referralInfo,
       systemProgram: SystemProgram.programId,
@@ -88,18 +170,23 @@ export const createBuyIx = async (
 
 export const createReinvestIx = async (
   user: PublicKey,
-  program: AppProgram
+  program: AppProgram,
+  minTokensOut?: BN,
+  deadline: TradeDeadline = NO_DEADLINE
 ): Promise<TransactionInstruction> => {
   const [programInfo] = findProgramPda(program.programId);
   const [userInfo] = findUserPda(user, program.programId);
   const [mint] = findMintPda(program.programId);
   const [userAta] = findUserAtaPda(mint, user);
+  const [eventAuthority] = findEventAuthorityPda(program.programId);
 
   return program.methods
-    .reinvest()
+    .reinvest(minTokensOut ? minTokensOut : null, deadline)
     .accounts({
       associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      eventAuthority,
       mint,
+      program: program.programId,
       programData: programInfo,
       systemProgram: SystemProgram.programId,
       tokenProgram: TOKEN_2022_PROGRAM_ID,
@@ -112,18 +199,23 @@ export const createReinvestIx = async (
 
 export const createExitIx = async (
   user: PublicKey,
-  program: AppProgram
+  minLamportsOut: BN,
+  program: AppProgram,
+  deadline: TradeDeadline = NO_DEADLINE
 ): Promise<TransactionInstruction> => {
   const [programInfo] = findProgramPda(program.programId);
   const [userInfo] = findUserPda(user, program.programId);
   const [mint] = findMintPda(program.programId);
   const [userAta] = findUserAtaPda(mint, user);
+  const [eventAuthority] = findEventAuthorityPda(program.programId);
 
   return program.methods
-    .exit()
+    .exit(minLamportsOut, deadline)
     .accounts({
       associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      eventAuthority,
       mint,
+      program: program.programId,
       programData: programInfo,
       systemProgram: SystemProgram.programId,
       tokenProgram: TOKEN_2022_PROGRAM_ID,
@@ -131,6 +223,7 @@ export const createExitIx = async (
       userAta: userAta,
       userData: userInfo,
     })
+    .remainingAccounts(await findVestingAccounts(user, program))
     .instruction();
 };
 
@@ -138,7 +231,8 @@ export const createTransferIx = async (
   user: PublicKey,
   to: PublicKey,
   amount: BN,
-  program: AppProgram
+  program: AppProgram,
+  deadline: TradeDeadline = NO_DEADLINE
 ): Promise<TransactionInstruction> => {
   const [programInfo] = findProgramPda(program.programId);
   const [userInfo] = findUserPda(user, program.programId);
@@ -146,12 +240,15 @@ export const createTransferIx = async (
   const [mint] = findMintPda(program.programId);
   const [userAta] = findUserAtaPda(mint, user);
   const [toAta] = findUserAtaPda(mint, to);
+  const [eventAuthority] = findEventAuthorityPda(program.programId);
 
   return program.methods
-    .transfer(to, amount)
+    .transfer(to, amount, deadline)
     .accounts({
       associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      eventAuthority,
       mint,
+      program: program.programId,
       programData: programInfo,
       systemProgram: SystemProgram.programId,
       toAta: toAta,
@@ -162,6 +259,7 @@ export const createTransferIx = async (
       userAta: userAta,
       userData: userInfo,
     })
+    .remainingAccounts(await findVestingAccounts(user, program))
     .instruction();
 };
 
@@ -173,12 +271,15 @@ export const createWithdrawIx = async (
   const [userInfo] = findUserPda(user, program.programId);
   const [mint] = findMintPda(program.programId);
   const [userAta] = findUserAtaPda(mint, user);
+  const [eventAuthority] = findEventAuthorityPda(program.programId);
 
   return program.methods
     .withdraw()
     .accounts({
       associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      eventAuthority,
       mint,
+      program: program.programId,
       programData: programInfo,
       systemProgram: SystemProgram.programId,
       tokenProgram: TOKEN_2022_PROGRAM_ID,
@@ -192,18 +293,23 @@ export const createWithdrawIx = async (
 export const createSellIx = async (
   user: PublicKey,
   amount: BN,
-  program: AppProgram
+  minLamportsOut: BN,
+  program: AppProgram,
+  deadline: TradeDeadline = NO_DEADLINE
 ): Promise<TransactionInstruction> => {
   const [programInfo] = findProgramPda(program.programId);
   const [userInfo] = findUserPda(user, program.programId);
   const [mint] = findMintPda(program.programId);
   const [userAta] = findUserAtaPda(mint, user);
+  const [eventAuthority] = findEventAuthorityPda(program.programId);
 
   return program.methods
-    .sell(amount)
+    .sell(amount, minLamportsOut, deadline)
     .accounts({
       associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      eventAuthority,
       mint,
+      program: program.programId,
       programData: programInfo,
       systemProgram: SystemProgram.programId,
       tokenProgram: TOKEN_2022_PROGRAM_ID,
@@ -211,6 +317,7 @@ export const createSellIx = async (
       userAta: userAta,
       userData: userInfo,
     })
+    .remainingAccounts(await findVestingAccounts(user, program))
     .instruction();
 };
 
@@ -220,12 +327,14 @@ export const createDistributeTokenIxs = async (
   receipients: Array<PublicKey>,
   amount: BN,
   payout: BN,
-  program: AppProgram
+  program: AppProgram,
+  schedule: VestingSchedule = DEFAULT_VESTING_SCHEDULE
 ): Promise<Array<TransactionInstruction>> => {
   const [programInfo] = findProgramPda(program.programId);
   const [mint] = findMintPda(program.programId);
   const [fromInfo] = findUserPda(user, program.programId);
   const [fromAta] = findUserAtaPda(mint, user);
+  const [eventAuthority] = findEventAuthorityPda(program.programId);
 
   const instructions = [];
 
@@ -233,13 +342,22 @@ export const createDistributeTokenIxs = async (
     const receipient = receipients[i];
     const [receipientInfo] = findUserPda(receipient, program.programId);
     const [receipientAta] = findUserAtaPda(mint, receipient);
+    // the new grant takes the recipient's next index
+    const account = await program.account.user.fetchNullable(receipientInfo);
+    const [vesting] = findVestingPda(
+      receipient,
+      account ? account.vestingCount : new BN(0),
+      program.programId
+    );
     const ix = await program.methods
-      .distributeToken(amount, payout, receipient)
+      .distributeToken(amount, payout, receipient, schedule)
       .accounts({
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        eventAuthority,
         fromAta: fromAta,
         fromData: fromInfo,
         mint,
+        program: program.programId,
         programData: programInfo,
         receipientAta: receipientAta,
         receipientData: receipientInfo,
@@ -247,6 +365,7 @@ export const createDistributeTokenIxs = async (
         systemProgram: SystemProgram.programId,
         tokenProgram: TOKEN_2022_PROGRAM_ID,
         user: user,
+        vesting,
       })
       .instruction();
 
@@ -256,16 +375,52 @@ export const createDistributeTokenIxs = async (
   return instructions;
 };
 
+export const createRevokeVestingIx = async (
+  admin: PublicKey,
+  receipient: PublicKey,
+  index: BN,
+  program: AppProgram
+): Promise<TransactionInstruction> => {
+  const [programInfo] = findProgramPda(program.programId);
+  const [adminInfo] = findUserPda(admin, program.programId);
+  const [receipientInfo] = findUserPda(receipient, program.programId);
+  const [mint] = findMintPda(program.programId);
+  const [receipientAta] = findUserAtaPda(mint, receipient);
+  const [vesting] = findVestingPda(receipient, index, program.programId);
+  const [eventAuthority] = findEventAuthorityPda(program.programId);
+
+  return program.methods
+    .revokeVesting()
+    .accounts({
+      admin: admin,
+      adminData: adminInfo,
+      eventAuthority,
+      mint,
+      program: program.programId,
+      programData: programInfo,
+      receipientAta: receipientAta,
+      receipientData: receipientInfo,
+      receipientInfo: receipient,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      vesting,
+    })
+    .remainingAccounts(await findVestingAccounts(receipient, program))
+    .instruction();
+};
+
 export const createDisableInitialStageIx = (
   user: PublicKey,
   program: AppProgram
 ): Promise<TransactionInstruction> => {
   const [programInfo] = findProgramPda(program.programId);
   const [userInfo] = findUserPda(user, program.programId);
+  const [eventAuthority] = findEventAuthorityPda(program.programId);
 
   return program.methods
     .disableInitialStage()
     .accounts({
+      eventAuthority,
+      program: program.programId,
       programData: programInfo,
       systemProgram: SystemProgram.programId,
       user: user,
@@ -274,6 +429,10 @@ export const createDisableInitialStageIx = (
     .instruction();
 };
 
+/**
+ * Granting admin nominates `user`, who accepts with createAcceptAdministratorIx
+ * @deprecated timelocked by default, propose `setAdministrator` instead
+ */
 export const createSetAdministratorIx = (
   admin: PublicKey,
   user: PublicKey,
@@ -283,12 +442,17 @@ export const createSetAdministratorIx = (
   const [programInfo] = findProgramPda(program.programId);
   const [adminInfo] = findUserPda(admin, program.programId);
   const [userInfo] = findUserPda(user, program.programId);
+  const [adminRegistry] = findAdminRegistryPda(program.programId);
+  const [eventAuthority] = findEventAuthorityPda(program.programId);
 
   return program.methods
     .setAdministrator(user, status)
     .accounts({
       admin: admin,
       adminData: adminInfo,
+      adminRegistry,
+      eventAuthority,
+      program: program.programId,
       programData: programInfo,
       systemProgram: SystemProgram.programId,
       userData: userInfo,
@@ -296,6 +460,28 @@ export const createSetAdministratorIx = (
     .instruction();
 };
 
+export const createAcceptAdministratorIx = (
+  user: PublicKey,
+  program: AppProgram
+): Promise<TransactionInstruction> => {
+  const [programInfo] = findProgramPda(program.programId);
+  const [userInfo] = findUserPda(user, program.programId);
+  const [adminRegistry] = findAdminRegistryPda(program.programId);
+  const [eventAuthority] = findEventAuthorityPda(program.programId);
+
+  return program.methods
+    .acceptAdministrator()
+    .accounts({
+      adminRegistry,
+      eventAuthority,
+      program: program.programId,
+      programData: programInfo,
+      user: user,
+      userData: userInfo,
+    })
+    .instruction();
+};
+
 export const createSetAmbassadorIx = (
   admin: PublicKey,
   user: PublicKey,
@@ -305,12 +491,75 @@ export const createSetAmbassadorIx = (
   const [programInfo] = findProgramPda(program.programId);
   const [adminInfo] = findUserPda(admin, program.programId);
   const [userInfo] = findUserPda(user, program.programId);
+  const [eventAuthority] = findEventAuthorityPda(program.programId);
 
   return program.methods
     .setAmbassador(user, status)
     .accounts({
       admin: admin,
       adminData: adminInfo,
+      eventAuthority,
+      program: program.programId,
+      programData: programInfo,
+      systemProgram: SystemProgram.programId,
+      userData: userInfo,
+    })
+    .instruction();
+};
+
+// Renames the token, the program state grows if the name and symbol need more room
+export const createUpdateMetadataIx = (
+  admin: PublicKey,
+  name: string,
+  symbol: string,
+  uri: string,
+  program: AppProgram
+): Promise<TransactionInstruction> => {
+  const [programInfo] = findProgramPda(program.programId);
+  const [adminInfo] = findUserPda(admin, program.programId);
+  const [mint] = findMintPda(program.programId);
+  const [metadata] = findMetadataPda(mint);
+  const [eventAuthority] = findEventAuthorityPda(program.programId);
+
+  return program.methods
+    .updateMetadataAccount(name, symbol, uri)
+    .accounts({
+      admin: admin,
+      adminData: adminInfo,
+      eventAuthority,
+      metadata,
+      mint,
+      program: program.programId,
+      programData: programInfo,
+      systemProgram: SystemProgram.programId,
+      sysvarInstructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      tokenMetadataProgram: MPL_TOKEN_METADATA_PROGRAM_ID,
+    })
+    .instruction();
+};
+
+/**
+ * `roles` replaces the user's roles, a combination of the ROLES bits
+ * @deprecated timelocked by default, propose `setRoles` instead
+ */
+export const createSetRolesIx = (
+  admin: PublicKey,
+  user: PublicKey,
+  roles: number,
+  program: AppProgram
+): Promise<TransactionInstruction> => {
+  const [programInfo] = findProgramPda(program.programId);
+  const [adminInfo] = findUserPda(admin, program.programId);
+  const [userInfo] = findUserPda(user, program.programId);
+  const [eventAuthority] = findEventAuthorityPda(program.programId);
+
+  return program.methods
+    .setRoles(user, roles)
+    .accounts({
+      admin: admin,
+      adminData: adminInfo,
+      eventAuthority,
+      program: program.programId,
       programData: programInfo,
       systemProgram: SystemProgram.programId,
       userData: userInfo,
@@ -318,6 +567,9 @@ export const createSetAmbassadorIx = (
     .instruction();
 };
 
+/**
+ * @deprecated timelocked by default, propose `setStakingRequirement` instead
+ */
 export const createSetStakingRequirementIx = (
   user: PublicKey,
   amountOfTokens: BN,
@@ -325,10 +577,13 @@ export const createSetStakingRequirementIx = (
 ): Promise<TransactionInstruction> => {
   const [programInfo] = findProgramPda(program.programId);
   const [userInfo] = findUserPda(user, program.programId);
+  const [eventAuthority] = findEventAuthorityPda(program.programId);
 
   return program.methods
     .setStakingRequirement(amountOfTokens)
     .accounts({
+      eventAuthority,
+      program: program.programId,
       programData: programInfo,
       systemProgram: SystemProgram.programId,
       user: user,
@@ -336,3 +591,211 @@ export const createSetStakingRequirementIx = (
     })
     .instruction();
 };
+
+// The program dispatches it by hand, only builds with feature-dev have it, so
+// it isn't in the IDL either
+const SET_CLOCK_OFFSET_DISCRIMINATOR = Buffer.from([
+  49, 111, 245, 49, 163, 96, 212, 248,
+]);
+
+// Only programs built with feature-dev accept it
+export const createSetClockOffsetIx = async (
+  user: PublicKey,
+  offset: BN,
+  program: AppProgram
+): Promise<TransactionInstruction> => {
+  const [programInfo] = findProgramPda(program.programId);
+  const [userInfo] = findUserPda(user, program.programId);
+  const [eventAuthority] = findEventAuthorityPda(program.programId);
+
+  // same accounts, in the same order, as the other `Admin` instructions
+  const keys: AccountMeta[] = [
+    { pubkey: user, isSigner: true, isWritable: true },
+    { pubkey: userInfo, isSigner: false, isWritable: true },
+    { pubkey: programInfo, isSigner: false, isWritable: true },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    { pubkey: eventAuthority, isSigner: false, isWritable: false },
+    { pubkey: program.programId, isSigner: false, isWritable: false },
+  ];
+  const data = Buffer.concat([
+    SET_CLOCK_OFFSET_DISCRIMINATOR,
+    offset.toTwos(64).toArrayLike(Buffer, "le", 8),
+  ]);
+
+  return new TransactionInstruction({
+    keys,
+    programId: program.programId,
+    data,
+  });
+};
+
+// Multisig proposals
+export const createProposeIx = (
+  admin: PublicKey,
+  action: AdminAction,
+  proposalId: BN,
+  program: AppProgram
+): Promise<TransactionInstruction> => {
+  const [programInfo] = findProgramPda(program.programId);
+  const [adminInfo] = findUserPda(admin, program.programId);
+  const [proposal] = findProposalPda(proposalId, program.programId);
+  const [adminRegistry] = findAdminRegistryPda(program.programId);
+  const [eventAuthority] = findEventAuthorityPda(program.programId);
+
+  return program.methods
+    .propose(action)
+    .accounts({
+      admin: admin,
+      adminData: adminInfo,
+      adminRegistry,
+      eventAuthority,
+      program: program.programId,
+      programData: programInfo,
+      proposal,
+      systemProgram: SystemProgram.programId,
+    })
+    .instruction();
+};
+
+export const createApproveProposalIx = (
+  admin: PublicKey,
+  proposalId: BN,
+  program: AppProgram
+): Promise<TransactionInstruction> => {
+  const [programInfo] = findProgramPda(program.programId);
+  const [adminInfo] = findUserPda(admin, program.programId);
+  const [proposal] = findProposalPda(proposalId, program.programId);
+  const [eventAuthority] = findEventAuthorityPda(program.programId);
+
+  return program.methods
+    .approveProposal()
+    .accounts({
+      admin: admin,
+      adminData: adminInfo,
+      eventAuthority,
+      program: program.programId,
+      programData: programInfo,
+      proposal,
+    })
+    .instruction();
+};
+
+export const createCancelProposalIx = (
+  admin: PublicKey,
+  proposalId: BN,
+  program: AppProgram
+): Promise<TransactionInstruction> => {
+  const [programInfo] = findProgramPda(program.programId);
+  const [adminInfo] = findUserPda(admin, program.programId);
+  const [proposal] = findProposalPda(proposalId, program.programId);
+  const [eventAuthority] = findEventAuthorityPda(program.programId);
+
+  return program.methods
+    .cancelProposal()
+    .accounts({
+      admin: admin,
+      adminData: adminInfo,
+      eventAuthority,
+      program: program.programId,
+      programData: programInfo,
+      proposal,
+    })
+    .instruction();
+};
+
+// `target` is the user the proposed action applies to, if any
+// Anyone can execute a queued proposal once its timelock has passed
+export const createExecuteProposalIx = (
+  executor: PublicKey,
+  proposalId: BN,
+  target: Maybe<PublicKey>,
+  program: AppProgram
+): Promise<TransactionInstruction> => {
+  const [programInfo] = findProgramPda(program.programId);
+  const [proposal] = findProposalPda(proposalId, program.programId);
+  const [adminRegistry] = findAdminRegistryPda(program.programId);
+  const [eventAuthority] = findEventAuthorityPda(program.programId);
+  const targetInfo = target ? findUserPda(target, program.programId)[0] : null;
+
+  return program.methods
+    .executeProposal()
+    .accounts({
+      adminRegistry,
+      eventAuthority,
+      executor,
+      program: program.programId,
+      programData: programInfo,
+      proposal,
+      systemProgram: SystemProgram.programId,
+      targetData: targetInfo,
+    })
+    .instruction();
+};
+
+// Executes a metadata update proposal, the executor pays if the state grows
+export const createExecuteMetadataProposalIx = (
+  executor: PublicKey,
+  proposalId: BN,
+  program: AppProgram
+): Promise<TransactionInstruction> => {
+  const [programInfo] = findProgramPda(program.programId);
+  const [proposal] = findProposalPda(proposalId, program.programId);
+  const [adminRegistry] = findAdminRegistryPda(program.programId);
+  const [mint] = findMintPda(program.programId);
+  const [metadata] = findMetadataPda(mint);
+  const [eventAuthority] = findEventAuthorityPda(program.programId);
+
+  return program.methods
+    .executeMetadataProposal()
+    .accounts({
+      adminRegistry,
+      eventAuthority,
+      executor,
+      metadata,
+      mint,
+      program: program.programId,
+      programData: programInfo,
+      proposal,
+      systemProgram: SystemProgram.programId,
+      sysvarInstructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      tokenMetadataProgram: MPL_TOKEN_METADATA_PROGRAM_ID,
+    })
+    .instruction();
+};
+
+// Executes a revocation proposal, on the grant `index` of `receipient`
+export const createExecuteRevocationProposalIx = async (
+  executor: PublicKey,
+  proposalId: BN,
+  receipient: PublicKey,
+  index: BN,
+  program: AppProgram
+): Promise<TransactionInstruction> => {
+  const [programInfo] = findProgramPda(program.programId);
+  const [proposal] = findProposalPda(proposalId, program.programId);
+  const [adminRegistry] = findAdminRegistryPda(program.programId);
+  const [receipientInfo] = findUserPda(receipient, program.programId);
+  const [mint] = findMintPda(program.programId);
+  const [receipientAta] = findUserAtaPda(mint, receipient);
+  const [vesting] = findVestingPda(receipient, index, program.programId);
+  const [eventAuthority] = findEventAuthorityPda(program.programId);
+
+  return program.methods
+    .executeRevocationProposal()
+    .accounts({
+      adminRegistry,
+      eventAuthority,
+      executor,
+      mint,
+      program: program.programId,
+      programData: programInfo,
+      proposal,
+      receipientAta: receipientAta,
+      receipientData: receipientInfo,
+      receipientInfo: receipient,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      vesting,
+    })
+    .remainingAccounts(await findVestingAccounts(receipient, program))
+    .instruction();
+};
@@ -1,8 +1,11 @@
 import { BN } from "@coral-xyz/anchor";
 import { PublicKey, TransactionInstruction } from "@solana/web3.js";
 import { AppProgram } from "generated";
+import { Maybe } from "types/UtilityTypes";
 import { findProgramPda, findUserPda } from "utils/pdas";
 
+import { findVestingAccounts } from "./createIx";
+
 export const getMyDividends = (
   user: PublicKey,
   withReferralBonus: boolean,
@@ -23,6 +26,30 @@ export const getMyDividends = (
   };
 };
 
+// `at` is a unix timestamp, the current time when left out
+export const getVestedBalance = (
+  user: PublicKey,
+  at: Maybe<BN>,
+  program: AppProgram,
+  test: boolean
+): { ixName: string; value: Promise<BN | TransactionInstruction> } => {
+  const [programInfo] = findProgramPda(program.programId);
+  const [userInfo] = findUserPda(user, program.programId);
+
+  const value = findVestingAccounts(user, program).then((grants) => {
+    const partials = program.methods
+      .vestedBalance(at)
+      .accounts({
+        programData: programInfo,
+        userData: userInfo,
+      })
+      .remainingAccounts(grants);
+    return test ? partials.view() : partials.instruction();
+  });
+
+  return { ixName: "vestedBalance", value };
+};
+
 export const getSellPrice = (
   program: AppProgram,
   test: boolean
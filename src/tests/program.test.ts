@@ -14,11 +14,20 @@ import {
 } from "@solana/web3.js";
 import { parseInitializeIx } from "parse/parseIx";
 import AppSdk from "sdk/AppSdk";
+import {
+  AdminAction,
+  Metadata,
+  createExecuteProposalIx,
+} from "sdk/instructions/createIx";
+import expectToFailWith from "tests/utils/expectToFailWith";
+import expectToThrow from "tests/utils/expectToThrow";
 import requestAirdrops from "tests/utils/requestAirdrops";
 import sendTransactionForTest from "tests/utils/sendTransactionForTest";
 import invariant from "tiny-invariant";
 import { findMintPda } from "utils/pdas";
+import ixToTx from "utils/solana/ixToTx";
 
+import { ALL_ROLES } from "../constants";
 import getKeyPair from "../utils/getKeypair";
 
 const USER = Keypair.fromSecretKey(getKeyPair());
@@ -46,6 +55,34 @@ async function getFirstAndOnlyIx(txid: string) {
   return instructions[0];
 }
 
+const INIT_PARAMS: Metadata = {
+  ambassadorMaxPurchase: new BN(LAMPORTS_PER_SOL),
+  // not a multiple of the cap, so the last ambassador can overshoot it
+  ambassadorQuota: new BN(LAMPORTS_PER_SOL).muln(3).divn(2),
+  curve: { linear: {} },
+  decimals: 9,
+  dividendFee: 10,
+  magnitude: new BN(2).pow(new BN(32)),
+  name: "app",
+  stakingRequirement: new BN(LAMPORTS_PER_SOL).muln(2000),
+  symbol: "APP",
+  tokenIncrementalPrice: new BN(100),
+  tokenInitialPrice: new BN(100000),
+  uri: "",
+};
+
+// Moves the program's clock, feature-dev builds only
+async function setClockOffset(offset: BN) {
+  const tx = await sdk.createSetClockOffsetTx(USER.publicKey, offset);
+  await sendTransactionForTest(connection, tx, [USER]);
+}
+
+// Moves the clock past the delay of proposals queued now
+async function skipTimelock() {
+  const { account: program } = await sdk.fetchProgramInfo();
+  await setClockOffset(program.timelockDelay.addn(60));
+}
+
 describe("Program", () => {
   beforeAll(async () => {
     await requestAirdrops(connection, [USER]);
@@ -54,14 +91,23 @@ describe("Program", () => {
     [userDataAddress] = sdk.findUserPda(USER.publicKey);
   });
 
-  it("Create Program Account and User Account", async () => {
+  it("Can't Initialize with decimals other than 9", async () => {
     const tx = await sdk.createInitializeTx(USER.publicKey, {
-      decimals: 9,
-      name: "app",
-      symbol: "APP",
-      uri: "",
+      ...INIT_PARAMS,
+      decimals: 6,
     });
 
+    await expectToFailWith(
+      connection,
+      tx,
+      [USER],
+      "The token must use 9 decimals, the unit its prices are quoted in"
+    );
+  });
+
+  it("Create Program Account and User Account", async () => {
+    const tx = await sdk.createInitializeTx(USER.publicKey, INIT_PARAMS);
+
     initialTx = await sendTransactionForTest(connection, tx, [USER]);
 
     const { account: program, pubkey: programPda } =
@@ -71,18 +117,45 @@ describe("Program", () => {
       USER.publicKey
     );
 
-    const { isInitialPhase, isInitialized } = program;
+    const {
+      adminThreshold,
+      eventSeq,
+      isInitialPhase,
+      isInitialized,
+      proposalCount,
+      timelockDelay,
+    } = program;
     expect(programPda.toString()).toEqual(programPda.toString());
     expect(isInitialPhase).toBeTruthy();
     expect(isInitialized).toBeTruthy();
-
-    const { authority, balance, isAdmin, isAmb, referredBalance } = user;
+    expect(eventSeq.toNumber()).toEqual(0);
+    expect(adminThreshold).toEqual(1);
+    expect(proposalCount.toNumber()).toEqual(0);
+    expect(timelockDelay.toNumber()).toEqual(2 * 24 * 60 * 60);
+
+    const {
+      authority,
+      balance,
+      isAdmin,
+      isAmb,
+      nonce,
+      referredBalance,
+      roles,
+    } = user;
     expect(userPda.toString()).toEqual(userPda.toString());
     expect(authority.toString()).toEqual(USER.publicKey.toString());
     expect(balance.toNumber()).toEqual(0);
     expect(referredBalance.toNumber()).toEqual(0);
     expect(isAdmin).toBeTruthy();
     expect(isAmb).toBeTruthy();
+    expect(nonce.toNumber()).toEqual(0);
+    expect(roles).toEqual(ALL_ROLES);
+
+    const { account: registry } = await sdk.fetchAdminRegistry();
+    expect(registry.admins.map((admin) => admin.toString())).toEqual([
+      USER.publicKey.toString(),
+    ]);
+    expect(registry.pending).toHaveLength(0);
   });
 
   it("Parse initialize ix", async () => {
@@ -108,8 +181,33 @@ describe("Program", () => {
     );
   });
 
+  it("Can't Buy Token below the minimum tokens out", async () => {
+    const { value: quote } = await sdk.calculateTokensReceived(
+      new BN(LAMPORTS_PER_SOL)
+    );
+    const tx = await sdk.createBuyTx(
+      USER.publicKey,
+      new BN(LAMPORTS_PER_SOL),
+      quote.addn(1)
+    );
+
+    await expectToFailWith(
+      connection,
+      tx,
+      [USER],
+      "You would receive fewer tokens than the minimum requested"
+    );
+  });
+
   it("Can Buy Token", async () => {
-    const tx = await sdk.createBuyTx(USER.publicKey, new BN(LAMPORTS_PER_SOL));
+    const { value: quote } = await sdk.calculateTokensReceived(
+      new BN(LAMPORTS_PER_SOL)
+    );
+    const tx = await sdk.createBuyTx(
+      USER.publicKey,
+      new BN(LAMPORTS_PER_SOL),
+      quote
+    );
 
     await sendTransactionForTest(connection, tx, [USER]);
 
@@ -133,6 +231,63 @@ describe("Program", () => {
     expect(new BN(LAMPORTS_PER_SOL) == contractBalance);
   });
 
+  it("Admin can Buy past the ambassador cap in initial phase", async () => {
+    const { account: before } = await sdk.fetchProgramInfo();
+    const tx = await sdk.createBuyTx(
+      USER.publicKey,
+      new BN(LAMPORTS_PER_SOL),
+      new BN(0)
+    );
+
+    await sendTransactionForTest(connection, tx, [USER]);
+
+    // admins buy the tokens they distribute, outside of the ambassador quota
+    const { account: program } = await sdk.fetchProgramInfo();
+    expect(program.isInitialPhase).toBeTruthy();
+    expect(
+      program.ambassadorPurchased.eq(before.ambassadorPurchased)
+    ).toBeTruthy();
+  });
+
+  it("Can't Buy past the ambassador cap in initial phase", async () => {
+    const ambassador = Keypair.generate();
+    await requestAirdrops(connection, [ambassador]);
+    await sendTransactionForTest(
+      connection,
+      await sdk.createSetAmbassadorTx(
+        USER.publicKey,
+        ambassador.publicKey,
+        true
+      ),
+      [USER]
+    );
+    await sendTransactionForTest(
+      connection,
+      await sdk.createBuyTx(
+        ambassador.publicKey,
+        new BN(LAMPORTS_PER_SOL),
+        new BN(0)
+      ),
+      [ambassador]
+    );
+
+    const { account: user } = await sdk.fetchUserInfo(ambassador.publicKey);
+    expect(user.ambassadorPurchased.eqn(LAMPORTS_PER_SOL)).toBeTruthy();
+
+    const tx = await sdk.createBuyTx(
+      ambassador.publicKey,
+      new BN(1),
+      new BN(0)
+    );
+
+    await expectToFailWith(
+      connection,
+      tx,
+      [ambassador],
+      "Purchase exceeds what an ambassador can buy during the initial phase"
+    );
+  });
+
   it("Can Distribute Tokens", async () => {
     await requestAirdrops(connection, [user1, user2]);
 
@@ -184,6 +339,13 @@ describe("Program", () => {
     expect(new BN(token2Account.amount.toString()) == user2Account.balance);
     expect(user1Account.balance == amount);
     expect(user2Account.balance == amount);
+
+    const { account: grant } = await sdk.fetchVestingInfo(
+      user1.publicKey,
+      new BN(0)
+    );
+    expect(user1Account.vestingCount.toNumber()).toBe(1);
+    expect(grant.totalLocked.eq(amount)).toBeTruthy();
   });
 
   describe("Sell Tokens", () => {
@@ -207,16 +369,49 @@ describe("Program", () => {
 
       expect(balance.toNumber()).toBeGreaterThan(0);
 
-      const tx = await sdk.createSellTx(user1.publicKey, sellAmount);
+      const tx = await sdk.createSellTx(user1.publicKey, sellAmount, new BN(0));
 
-      try {
-        await sendTransactionForTest(connection, tx, [user1]);
-      } catch (err: any) {
-        expect(err).toBeInstanceOf(SendTransactionError);
-        expect(
-          (err.logs as Array<string>).join().includes("It is in initial phase")
-        ).toBeTruthy();
-      }
+      await expectToFailWith(connection, tx, [user1], "It is in initial phase");
+    });
+    it("Can't Buy past the ambassador quota in initial phase", async () => {
+      const ambassador = Keypair.generate();
+      await requestAirdrops(connection, [ambassador]);
+      await sendTransactionForTest(
+        connection,
+        await sdk.createSetAmbassadorTx(
+          USER.publicKey,
+          ambassador.publicKey,
+          true
+        ),
+        [USER]
+      );
+      const { account: before } = await sdk.fetchProgramInfo();
+      const remaining = before.ambassadorQuota.sub(before.ambassadorPurchased);
+
+      const tx = await sdk.createBuyTx(
+        ambassador.publicKey,
+        remaining.addn(1),
+        new BN(0)
+      );
+      await expectToFailWith(
+        connection,
+        tx,
+        [ambassador],
+        "Purchase would take ambassadors past the initial phase quota"
+      );
+
+      await sendTransactionForTest(
+        connection,
+        await sdk.createBuyTx(ambassador.publicKey, remaining, new BN(0)),
+        [ambassador]
+      );
+
+      // filling the quota exactly ends the initial phase
+      const { account: program } = await sdk.fetchProgramInfo();
+      expect(
+        program.ambassadorPurchased.eq(program.ambassadorQuota)
+      ).toBeTruthy();
+      expect(program.isInitialPhase).toBeFalsy();
     });
     it("Can't Sell Locked Token", async () => {
       const balance = new BN(tokenAccount.amount.toString());
@@ -229,19 +424,60 @@ describe("Program", () => {
 
       await sendTransactionForTest(connection, txDisable, [USER]);
 
-      const tx = await sdk.createSellTx(user1.publicKey, sellAmount);
-
-      try {
-        await sendTransactionForTest(connection, tx, [user1]);
-      } catch (err: any) {
-        expect(err).toBeInstanceOf(SendTransactionError);
-        console.log(Object.keys(err));
-        expect(
-          (err.logs as Array<string>)
-            .join()
-            .includes("You do not have enough funds")
-        ).toBeTruthy();
-      }
+      const tx = await sdk.createSellTx(user1.publicKey, sellAmount, new BN(0));
+
+      await expectToFailWith(
+        connection,
+        tx,
+        [user1],
+        "You do not have enough funds"
+      );
+    });
+    it("Can't Sell Token past its deadline", async () => {
+      const balance = new BN(tokenAccount.amount.toString());
+      const sellAmount = balance.div(new BN(2));
+
+      const tx = await sdk.createSellTx(
+        user1.publicKey,
+        sellAmount,
+        new BN(0),
+        { validUntilSlot: new BN(0), validUntilTs: null }
+      );
+
+      await expectToFailWith(
+        connection,
+        tx,
+        [user1],
+        "Transaction is past its deadline"
+      );
+    });
+    it("Can't Sell Token below the minimum lamports out", async () => {
+      await sendTransactionForTest(
+        connection,
+        await sdk.createBuyTx(
+          USER.publicKey,
+          new BN(LAMPORTS_PER_SOL),
+          new BN(0)
+        ),
+        [USER]
+      );
+      const { account: user } = await sdk.fetchUserInfo(USER.publicKey);
+      const { value: quote } = await sdk.calculateLamportsReceived(
+        user.balance
+      );
+
+      const tx = await sdk.createSellTx(
+        USER.publicKey,
+        user.balance,
+        quote.addn(1)
+      );
+
+      await expectToFailWith(
+        connection,
+        tx,
+        [USER],
+        "You would receive fewer lamports than the minimum requested"
+      );
     });
     it("Can Sell Token when token is unlocked @TODO", async () => {
       //   const balance = new BN(tokenAccount.amount.toString());
@@ -250,7 +486,7 @@ describe("Program", () => {
 
       //   expect(balance.toNumber()).toBeGreaterThan(0);
 
-      //   const tx = await sdk.createSellTx(user1.publicKey, sellAmount);
+      //   const tx = await sdk.createSellTx(user1.publicKey, sellAmount, new BN(0));
 
       //   await sendTransactionForTest(connection, tx, [user1]);
 
@@ -278,6 +514,25 @@ describe("Program", () => {
       expect(value.toNumber()).toBeGreaterThan(0);
     });
 
+    it("Vested balance", async () => {
+      const { account } = await sdk.fetchUserInfo(user1.publicKey);
+      const { account: grant } = await sdk.fetchVestingInfo(
+        user1.publicKey,
+        new BN(0)
+      );
+
+      const { value: atStart } = await sdk.vestedBalance(
+        user1.publicKey,
+        grant.startTime
+      );
+      const { value: atEnd } = await sdk.vestedBalance(
+        user1.publicKey,
+        grant.endTime
+      );
+      expect(atStart.lt(account.balance)).toBeTruthy();
+      expect(atEnd.eq(account.balance)).toBeTruthy();
+    });
+
     it("Sell Price", async () => {
       const { value } = await sdk.sellPrice();
 
@@ -304,4 +559,449 @@ describe("Program", () => {
       expect(value.toNumber()).toBeGreaterThan(0);
     });
   });
+
+  describe("Revoke Vesting", () => {
+    beforeAll(async () => {
+      // halfway through the hundred days grant of user2
+      await setClockOffset(new BN(50 * 24 * 60 * 60));
+    });
+
+    afterAll(async () => {
+      await setClockOffset(new BN(0));
+    });
+
+    it("Can Revoke the locked part of a grant", async () => {
+      const { account: programBefore } = await sdk.fetchProgramInfo();
+      const { account: userBefore } = await sdk.fetchUserInfo(user2.publicKey);
+      const { account: grantBefore } = await sdk.fetchVestingInfo(
+        user2.publicKey,
+        new BN(0)
+      );
+      const { value: dividendsBefore } = await sdk.myDividends(
+        user2.publicKey,
+        false
+      );
+
+      const tx = await sdk.createRevokeVestingTx(
+        USER.publicKey,
+        user2.publicKey,
+        new BN(0)
+      );
+      await sendTransactionForTest(connection, tx, [USER]);
+
+      const { account: program } = await sdk.fetchProgramInfo();
+      const { account: user } = await sdk.fetchUserInfo(user2.publicKey);
+      const { account: grant } = await sdk.fetchVestingInfo(
+        user2.publicKey,
+        new BN(0)
+      );
+      const tokenAccount = await getOrCreateAssociatedTokenAccount(
+        connection,
+        user2,
+        findMintPda(sdk.program.programId)[0],
+        user2.publicKey,
+        false,
+        undefined,
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      );
+
+      // only the locked half is burned, the grant keeps the vested half
+      const burned = userBefore.balance.sub(user.balance);
+      expect(burned.gtn(0)).toBeTruthy();
+      expect(burned.lt(grantBefore.totalLocked)).toBeTruthy();
+      expect(
+        grant.totalLocked.eq(grantBefore.totalLocked.sub(burned))
+      ).toBeTruthy();
+      expect(
+        new BN(tokenAccount.amount.toString()).eq(user.balance)
+      ).toBeTruthy();
+      expect(
+        program.tokenSupply.eq(programBefore.tokenSupply.sub(burned))
+      ).toBeTruthy();
+      // the user's vesting now ends with the grant that was cut short
+      expect(userBefore.vestingEndTime.gt(grant.endTime)).toBeTruthy();
+      expect(user.vestingEndTime.eq(grant.endTime)).toBeTruthy();
+      // the value of the burned tokens is shared among the holders
+      expect(
+        program.profitPerShare.gt(programBefore.profitPerShare)
+      ).toBeTruthy();
+
+      // the holder keeps what the burned tokens earned and a share of the rest
+      const { value: dividends } = await sdk.myDividends(
+        user2.publicKey,
+        false
+      );
+      expect(dividends.gte(dividendsBefore)).toBeTruthy();
+
+      // nothing is locked anymore
+      const { value: vested } = await sdk.vestedBalance(user2.publicKey);
+      expect(vested.eq(user.balance)).toBeTruthy();
+    });
+
+    it("Can't Revoke a grant twice", async () => {
+      const tx = await sdk.createRevokeVestingTx(
+        USER.publicKey,
+        user2.publicKey,
+        new BN(0)
+      );
+
+      await expectToThrow(
+        () => sendTransactionForTest(connection, tx, [USER]),
+        SendTransactionError
+      );
+    });
+
+    it("Can Sell the vested part of a revoked grant", async () => {
+      const { account: userBefore } = await sdk.fetchUserInfo(user2.publicKey);
+
+      const tx = await sdk.createSellTx(
+        user2.publicKey,
+        userBefore.balance,
+        new BN(0)
+      );
+      await sendTransactionForTest(connection, tx, [user2]);
+
+      const { account: user } = await sdk.fetchUserInfo(user2.publicKey);
+      expect(user.balance.isZero()).toBeTruthy();
+    });
+  });
+
+  describe("Timelock", () => {
+    const newRequirement = new BN(LAMPORTS_PER_SOL).muln(1000);
+
+    const proposeNewRequirement = async () => {
+      const { account: program } = await sdk.fetchProgramInfo();
+      const tx = await sdk.createProposeTx(USER.publicKey, {
+        setStakingRequirement: { amountOfTokens: newRequirement },
+      });
+      await sendTransactionForTest(connection, tx, [USER]);
+      return program.proposalCount;
+    };
+
+    afterEach(async () => {
+      await setClockOffset(new BN(0));
+    });
+
+    it("Queues a staking requirement change for the delay", async () => {
+      const id = await proposeNewRequirement();
+
+      const { account: program } = await sdk.fetchProgramInfo();
+      const { account: proposal } = await sdk.fetchProposalInfo(id);
+      expect(
+        proposal.eta.eq(proposal.createdAt.add(program.timelockDelay))
+      ).toBeTruthy();
+      expect(program.timelockDelay.gtn(0)).toBeTruthy();
+    });
+
+    it("Can't Execute a queued change before its eta", async () => {
+      const id = await proposeNewRequirement();
+      const { account: before } = await sdk.fetchProgramInfo();
+
+      const tx = await sdk.createExecuteProposalTx(USER.publicKey, id);
+      await expectToFailWith(
+        connection,
+        tx,
+        [USER],
+        "Proposal is still in its timelock"
+      );
+
+      const { account: program } = await sdk.fetchProgramInfo();
+      expect(
+        program.stakingRequirement.eq(before.stakingRequirement)
+      ).toBeTruthy();
+    });
+
+    it("Can Cancel a queued change during the delay", async () => {
+      const id = await proposeNewRequirement();
+
+      const cancelTx = await sdk.createCancelProposalTx(USER.publicKey, id);
+      await sendTransactionForTest(connection, cancelTx, [USER]);
+
+      const { account: proposal } = await sdk.fetchProposalInfo(id);
+      expect(proposal.status).toEqual({ cancelled: {} });
+
+      // not even once the delay has passed
+      await skipTimelock();
+      const tx = await sdk.createExecuteProposalTx(USER.publicKey, id);
+      await expectToFailWith(
+        connection,
+        tx,
+        [USER],
+        "Proposal was already executed or cancelled"
+      );
+    });
+
+    it("Can Execute a queued change after its eta", async () => {
+      const id = await proposeNewRequirement();
+
+      await skipTimelock();
+      const tx = await sdk.createExecuteProposalTx(USER.publicKey, id);
+      await sendTransactionForTest(connection, tx, [USER]);
+
+      const { account: program } = await sdk.fetchProgramInfo();
+      const { account: proposal } = await sdk.fetchProposalInfo(id);
+      expect(program.stakingRequirement.eq(newRequirement)).toBeTruthy();
+      expect(proposal.status).toEqual({ executed: {} });
+    });
+  });
+
+  describe("Multisig Proposals", () => {
+    const admin2 = Keypair.generate();
+    const admin3 = Keypair.generate();
+
+    // proposes `action` and approves it with `approvers`, returns its id
+    const propose = async (
+      proposer: Keypair,
+      action: AdminAction,
+      approvers: Array<Keypair> = []
+    ) => {
+      const { account: program } = await sdk.fetchProgramInfo();
+      const tx = await sdk.createProposeTx(proposer.publicKey, action);
+      await sendTransactionForTest(connection, tx, [proposer]);
+      for (const approver of approvers) {
+        const approveTx = await sdk.createApproveProposalTx(
+          approver.publicKey,
+          program.proposalCount
+        );
+        await sendTransactionForTest(connection, approveTx, [approver]);
+      }
+      return program.proposalCount;
+    };
+
+    const execute = async (id: BN) => {
+      const tx = await sdk.createExecuteProposalTx(USER.publicKey, id);
+      await sendTransactionForTest(connection, tx, [USER]);
+    };
+
+    // the clock goes back so that later proposals queue from now on
+    const executeAfterTimelock = async (id: BN) => {
+      await skipTimelock();
+      await execute(id);
+      await setClockOffset(new BN(0));
+    };
+
+    // a proposal on user1, which isn't timelocked
+    const makeAmbassador: AdminAction = {
+      setAmbassador: { status: true, user: user1.publicKey },
+    };
+
+    beforeAll(async () => {
+      // three admins, no timelock and two approvals per proposal
+      for (const admin of [admin2, admin3]) {
+        await requestAirdrops(connection, [admin]);
+        const buyTx = await sdk.createBuyTx(
+          admin.publicKey,
+          new BN(LAMPORTS_PER_SOL).divn(10),
+          new BN(0)
+        );
+        await sendTransactionForTest(connection, buyTx, [admin]);
+
+        const id = await propose(USER, {
+          setAdministrator: { status: true, user: admin.publicKey },
+        });
+        await executeAfterTimelock(id);
+        const acceptTx = await sdk.createAcceptAdministratorTx(
+          admin.publicKey
+        );
+        await sendTransactionForTest(connection, acceptTx, [admin]);
+      }
+
+      const delayId = await propose(USER, {
+        setTimelockDelay: { delay: new BN(0) },
+      });
+      await executeAfterTimelock(delayId);
+
+      await execute(
+        await propose(USER, {
+          setRoles: { roles: ALL_ROLES, user: admin3.publicKey },
+        })
+      );
+
+      await execute(
+        await propose(USER, { setAdminThreshold: { threshold: 2 } })
+      );
+
+      const { account: program } = await sdk.fetchProgramInfo();
+      const { account: registry } = await sdk.fetchAdminRegistry();
+      expect(program.adminThreshold).toBe(2);
+      expect(program.timelockDelay.isZero()).toBeTruthy();
+      expect(registry.admins.length).toBe(3);
+    });
+
+    it("Can't Execute a proposal below the threshold", async () => {
+      const id = await propose(USER, makeAmbassador);
+
+      const tx = await sdk.createExecuteProposalTx(USER.publicKey, id);
+      await expectToFailWith(
+        connection,
+        tx,
+        [USER],
+        "Proposal isn't queued yet"
+      );
+
+      const { account: proposal } = await sdk.fetchProposalInfo(id);
+      expect(proposal.eta.isZero()).toBeTruthy();
+      expect(proposal.status).toEqual({ active: {} });
+    });
+
+    it("Doesn't count the approvals of removed admins", async () => {
+      // queued with the approvals of admin3 and USER
+      const id = await propose(admin3, makeAmbassador, [USER]);
+      const { account: queued } = await sdk.fetchProposalInfo(id);
+      expect(queued.eta.gtn(0)).toBeTruthy();
+
+      await execute(
+        await propose(
+          USER,
+          { setAdministrator: { status: false, user: admin3.publicKey } },
+          [admin2]
+        )
+      );
+
+      const tx = await sdk.createExecuteProposalTx(USER.publicKey, id);
+      await expectToFailWith(
+        connection,
+        tx,
+        [USER],
+        "Proposal doesn't have enough approvals"
+      );
+
+      // a current admin makes up for it
+      const approveTx = await sdk.createApproveProposalTx(admin2.publicKey, id);
+      await sendTransactionForTest(connection, approveTx, [admin2]);
+      await execute(id);
+
+      const { account: user } = await sdk.fetchUserInfo(user1.publicKey);
+      expect(user.isAmb).toBeTruthy();
+    });
+
+    it("Demoted admins lose their roles", async () => {
+      // admin3 was demoted by the previous test
+      const { account: user } = await sdk.fetchUserInfo(admin3.publicKey);
+      expect(user.isAdmin).toBeFalsy();
+      expect(user.roles).toEqual(0);
+
+      const tx = await sdk.createDisableInitialStageTx(admin3.publicKey);
+      await expectToFailWith(
+        connection,
+        tx,
+        [admin3],
+        "You don't have the role this needs"
+      );
+    });
+
+    it("Can't Execute a cancelled proposal", async () => {
+      const id = await propose(USER, makeAmbassador, [admin2]);
+
+      const cancelTx = await sdk.createCancelProposalTx(admin2.publicKey, id);
+      await sendTransactionForTest(connection, cancelTx, [admin2]);
+
+      const tx = await sdk.createExecuteProposalTx(USER.publicKey, id);
+      await expectToFailWith(
+        connection,
+        tx,
+        [USER],
+        "Proposal was already executed or cancelled"
+      );
+    });
+
+    it("Can't Execute a proposal with a wrong or missing target", async () => {
+      const id = await propose(USER, makeAmbassador, [admin2]);
+
+      const cases: Array<[PublicKey | null, string]> = [
+        [user2.publicKey, "A seeds constraint was violated"],
+        [null, "Target account doesn't match the proposal"],
+      ];
+      for (const [target, message] of cases) {
+        const ix = await createExecuteProposalIx(
+          USER.publicKey,
+          id,
+          target,
+          sdk.program
+        );
+        const tx = await ixToTx(connection, USER.publicKey, ix, true);
+        await expectToFailWith(connection, tx, [USER], message);
+      }
+
+      await execute(id);
+      const { account: proposal } = await sdk.fetchProposalInfo(id);
+      expect(proposal.status).toEqual({ executed: {} });
+    });
+
+    it("Can Execute a proposal for a user without an account", async () => {
+      const newcomer = Keypair.generate();
+      const id = await propose(
+        USER,
+        { setAmbassador: { status: true, user: newcomer.publicKey } },
+        [admin2]
+      );
+
+      // the executor pays for the newcomer's account
+      await execute(id);
+
+      const { account: user } = await sdk.fetchUserInfo(newcomer.publicKey);
+      expect(user.authority.toString()).toEqual(newcomer.publicKey.toString());
+      expect(user.isAmb).toBeTruthy();
+    });
+
+    it("Can Update the metadata through a proposal", async () => {
+      const id = await propose(
+        USER,
+        { updateMetadata: { name: "app v2", symbol: "APP2", uri: "" } },
+        [admin2]
+      );
+      await execute(id);
+
+      const { account: program } = await sdk.fetchProgramInfo();
+      expect(program.name).toEqual("app v2");
+      expect(program.symbol).toEqual("APP2");
+    });
+
+    it("Can Revoke a grant through a proposal", async () => {
+      const { account: userBefore } = await sdk.fetchUserInfo(user1.publicKey);
+      const id = await propose(
+        USER,
+        { revokeVesting: { index: new BN(0), receipient: user1.publicKey } },
+        [admin2]
+      );
+
+      // revoking directly needs a single signer
+      const directTx = await sdk.createRevokeVestingTx(
+        USER.publicKey,
+        user1.publicKey,
+        new BN(0)
+      );
+      await expectToFailWith(
+        connection,
+        directTx,
+        [USER],
+        "Admin changes need an approved proposal"
+      );
+
+      await execute(id);
+
+      const { account: user } = await sdk.fetchUserInfo(user1.publicKey);
+      const { account: grant } = await sdk.fetchVestingInfo(
+        user1.publicKey,
+        new BN(0)
+      );
+      expect(user.balance.lt(userBefore.balance)).toBeTruthy();
+      expect(user.vestingEndTime.eq(grant.endTime)).toBeTruthy();
+      const { account: proposal } = await sdk.fetchProposalInfo(id);
+      expect(proposal.status).toEqual({ executed: {} });
+    });
+
+    it("Can't Propose metadata longer than Metaplex allows", async () => {
+      const tx = await sdk.createProposeTx(USER.publicKey, {
+        updateMetadata: { name: "a".repeat(33), symbol: "APP", uri: "" },
+      });
+      await expectToFailWith(
+        connection,
+        tx,
+        [USER],
+        "Name, symbol or uri is longer than the metadata allows"
+      );
+    });
+  });
 });
@@ -0,0 +1,26 @@
+import {
+  Connection,
+  SendTransactionError,
+  Signer,
+  Transaction,
+  VersionedTransaction,
+} from "@solana/web3.js";
+import sendTransactionForTest from "tests/utils/sendTransactionForTest";
+
+// Sends the transaction and checks the program rejected it with `message`
+export default async function expectToFailWith(
+  connection: Connection,
+  transaction: Transaction | VersionedTransaction,
+  signers: Array<Signer>,
+  message: string
+) {
+  let error: any;
+  try {
+    await sendTransactionForTest(connection, transaction, signers);
+  } catch (err) {
+    error = err;
+  }
+
+  expect(error).toBeInstanceOf(SendTransactionError);
+  expect((error.logs as Array<string>).join()).toContain(message);
+}
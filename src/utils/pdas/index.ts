@@ -1,3 +1,4 @@
+import { BN } from "@coral-xyz/anchor";
 import {
   ASSOCIATED_TOKEN_PROGRAM_ID,
   TOKEN_2022_PROGRAM_ID,
@@ -5,11 +6,15 @@ import {
 import { PublicKey } from "@solana/web3.js";
 
 import {
+  ADMIN_REGISTRY,
+  EVENT_AUTHORITY,
   METADATA_SEED,
   MINT,
   MPL_TOKEN_METADATA_PROGRAM_ID,
   PROGRAM,
+  PROPOSAL,
   USER,
+  VESTING,
 } from "../../constants";
 
 export const findMintPda = (programId: PublicKey) =>
@@ -39,3 +44,29 @@ export const findUserAtaPda = (mint: PublicKey, user: PublicKey) =>
     [user.toBuffer(), TOKEN_2022_PROGRAM_ID.toBuffer(), mint.toBuffer()],
     ASSOCIATED_TOKEN_PROGRAM_ID
   );
+
+export const findEventAuthorityPda = (programId: PublicKey) =>
+  PublicKey.findProgramAddressSync([Buffer.from(EVENT_AUTHORITY)], programId);
+
+export const findAdminRegistryPda = (programId: PublicKey) =>
+  PublicKey.findProgramAddressSync([Buffer.from(ADMIN_REGISTRY)], programId);
+
+export const findProposalPda = (id: BN, programId: PublicKey) =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from(PROPOSAL), id.toArrayLike(Buffer, "le", 8)],
+    programId
+  );
+
+export const findVestingPda = (
+  receipient: PublicKey,
+  index: BN,
+  programId: PublicKey
+) =>
+  PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(VESTING),
+      receipient.toBuffer(),
+      index.toArrayLike(Buffer, "le", 8),
+    ],
+    programId
+  );